@@ -0,0 +1,184 @@
+//! The `deployment_changelog::api::asana` module provides a client for the Asana REST API, used to link
+//! Asana tasks referenced in pull request descriptions to a changelog.
+//!
+//! Tasks are referenced by their URL (e.g. `https://app.asana.com/0/1200000000000000/1200000000000001`).
+//! Each task is resolved into a `TrackedIssue` whose status is the name of the section the task is in, or
+//! `Completed` once the task is done.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use deployment_changelog::api::asana::AsanaClient;
+//!
+//! let asana_client = AsanaClient::new("https://app.asana.com", "personal-access-token").unwrap();
+//! let task = asana_client.get_task("1200000000000001").await.unwrap();
+//!
+//! println!("{} (completed: {})", task.name, task.completed);
+//! ```
+use std::fmt::Display;
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION};
+use serde::{Deserialize, Serialize};
+
+use super::{rest::{RestClient, RestClientBuilder}, tracker::{IssueTracker, TrackedIssue}};
+
+enum AsanaEndpoints {
+    GetTask
+}
+
+impl AsanaEndpoints {
+    fn url(&self) -> &'static str {
+        match self {
+            AsanaEndpoints::GetTask => "api/1.0/tasks/{taskGid}?opt_fields=name,permalink_url,completed,memberships.section.name"
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct AsanaData<T> {
+    data: T
+}
+
+/// The `AsanaTask` struct represents an Asana task and the sections it is in.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct AsanaTask {
+    pub gid: String,
+    pub name: String,
+    pub permalink_url: String,
+    pub completed: bool,
+
+    #[serde(default)]
+    pub memberships: Vec<AsanaMembership>
+}
+
+impl Display for AsanaTask {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match serde_json::to_string_pretty(&self) {
+            Ok(json) => write!(f, "{json}"),
+            Err(error) => panic!("Error serializing Asana task: {error}")
+        }
+    }
+}
+
+/// The `AsanaMembership` struct represents the membership of a task in a project, and the section it is in.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct AsanaMembership {
+    pub section: Option<AsanaSection>
+}
+
+/// The `AsanaSection` struct represents a section of an Asana project.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct AsanaSection {
+    pub name: String
+}
+
+/// The `AsanaClient` struct provides access to Asana tasks using a personal access token.
+///
+/// It implements `IssueTracker`, finding Asana task URLs in text and resolving them into `TrackedIssue` entries.
+///
+/// # Example
+///
+/// ```rust
+/// use deployment_changelog::api::asana::AsanaClient;
+///
+/// let asana_client = AsanaClient::new("https://app.asana.com", "personal-access-token").unwrap();
+/// ```
+#[derive(Debug)]
+pub struct AsanaClient {
+    client: RestClient
+}
+
+impl AsanaClient {
+    /// Creates a new `AsanaClient` with the given base URL and personal access token.
+    ///
+    /// # Arguments
+    ///
+    /// * `base_url` - The base URL of Asana, usually `https://app.asana.com`.
+    /// * `access_token` - The personal access token used to authenticate to the Asana API.
+    pub fn new(base_url: &str, access_token: &str) -> Result<Self> {
+        Self::from_builder(RestClient::builder(base_url)?, access_token)
+    }
+
+    /// Creates a new `AsanaClient` from a `RestClientBuilder` of the Asana base URL, such as one with a custom
+    /// retry policy or User-Agent, authenticated with the given personal access token.
+    pub fn from_builder(mut client_builder: RestClientBuilder, access_token: &str) -> Result<Self> {
+        let mut headers = HeaderMap::with_capacity(1);
+        let authorization = HeaderValue::from_str(&format!("Bearer {access_token}"))
+            .with_context(|| "Error creating the Asana authorization header")?;
+        headers.insert(AUTHORIZATION, authorization);
+
+        client_builder.client_builder = client_builder.client_builder
+            .default_headers(headers);
+
+        Ok(Self {
+            client: client_builder.build()?
+        })
+    }
+
+    /// Creates a new `AsanaClient` from an existing, already authenticated `RestClient`.
+    pub fn from_client(client: RestClient) -> Self {
+        Self {
+            client
+        }
+    }
+
+    /// Fetches an Asana task, along with the sections it is in, by its GID.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_gid` - The GID of the task.
+    pub async fn get_task(&self, task_gid: &str) -> Result<AsanaTask> {
+        let task_path: String = AsanaEndpoints::GetTask.url()
+            .replace("{taskGid}", task_gid);
+
+        Ok(self.client.get::<AsanaData<AsanaTask>>(&task_path, None).await?.data)
+    }
+}
+
+#[async_trait::async_trait]
+impl IssueTracker for AsanaClient {
+    fn name(&self) -> &'static str {
+        "asana"
+    }
+
+    fn find_references(&self, text: &str) -> Vec<String> {
+        let task_url_regex = Regex::new(r"https?://app\.asana\.com/0/[0-9]+/([0-9]+)")
+            .expect("Error compiling Asana task URL regex");
+
+        let mut task_gids: Vec<String> = Vec::new();
+
+        task_url_regex.captures_iter(text)
+            .for_each(|captures| {
+                let task_gid = captures[1].to_string();
+
+                if !task_gids.contains(&task_gid) {
+                    task_gids.push(task_gid);
+                }
+            });
+
+        task_gids
+    }
+
+    async fn get_tracked_issue(&self, reference: &str) -> Result<TrackedIssue> {
+        let task = self.get_task(reference).await?;
+
+        let status = if task.completed {
+            Some(String::from("Completed"))
+        } else {
+            task.memberships.into_iter()
+                .find_map(|membership| membership.section)
+                .map(|section| section.name)
+        };
+
+        Ok(TrackedIssue {
+            tracker: self.name().to_string(),
+            id: task.gid,
+            url: task.permalink_url,
+            title: task.name,
+            status,
+            entry_id: None
+        })
+    }
+}
@@ -0,0 +1,647 @@
+//! The `deployment_changelog::api::azure_devops` module provides a high-level API for interacting with the
+//! Azure DevOps Repos REST API, so repositories hosted on Azure DevOps can be used to generate changelogs.
+//!
+//! The base URL of an `AzureDevOpsClient` is the URL of the Azure DevOps organization (for example
+//! `https://dev.azure.com/my-organization/`). The changelog's `project` and `repo` are the Azure DevOps
+//! project and Git repository names.
+//!
+//! Azure DevOps list endpoints paginate with continuation tokens returned in the `x-ms-continuationtoken`
+//! response header rather than with page offsets; `AzureDevOpsPaginated` takes care of following them.
+//!
+//! Azure DevOps pull requests link to Azure Boards work items rather than Jira issues, so when used as an
+//! `ScmProvider` the client links pull requests to the Jira issue keys mentioned in their title and description.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use deployment_changelog::api::azure_devops::AzureDevOpsClient;
+//! use deployment_changelog::api::rest::Paginated;
+//!
+//! let azure_devops_client = AzureDevOpsClient::new("https://dev.azure.com/my-organization/")
+//!     .unwrap();
+//!
+//! let commits = azure_devops_client.compare_commits("MyProject", "my-repo", "abcdef123456", "fedcba654321")
+//!     .all()
+//!     .await
+//!     .unwrap();
+//!
+//! for commit in commits {
+//!     println!("{}", commit);
+//! }
+//! ```
+use std::{fmt::Display, collections::HashMap, marker::PhantomData};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use reqwest::Url;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use super::{rest::{RestClient, Paginated}, scm::{ScmProvider, extract_issue_keys}, bitbucket::{BitbucketAuthor, BitbucketCommit, BitbucketPullRequest, BitbucketPullRequestAuthor, BitbucketPullRequestIssue, BitbucketPullRequestSize}};
+
+/// The response header Azure DevOps uses to return the token for the next page of results.
+const CONTINUATION_TOKEN_HEADER: &str = "x-ms-continuationtoken";
+
+/// The length of the abbreviated commit IDs, matching the display IDs used by Bitbucket.
+const DISPLAY_ID_LENGTH: usize = 11;
+
+enum AzureDevOpsEndpoints {
+    CompareCommits,
+    PullRequestQuery,
+    PullRequest,
+    PullRequestCommits,
+    Tags,
+    CommitDiffs
+}
+
+impl AzureDevOpsEndpoints {
+    fn url(&self) -> &'static str {
+        match self {
+            AzureDevOpsEndpoints::CompareCommits => "{project}/_apis/git/repositories/{repositoryId}/commits?searchCriteria.itemVersion.version={from}&searchCriteria.itemVersion.versionType=commit&searchCriteria.compareVersion.version={to}&searchCriteria.compareVersion.versionType=commit&api-version=7.0",
+            AzureDevOpsEndpoints::PullRequestQuery => "{project}/_apis/git/repositories/{repositoryId}/pullrequestquery?api-version=7.0",
+            AzureDevOpsEndpoints::PullRequest => "{project}/_apis/git/repositories/{repositoryId}/pullrequests/{pullRequestId}?api-version=7.0",
+            AzureDevOpsEndpoints::PullRequestCommits => "{project}/_apis/git/repositories/{repositoryId}/pullRequests/{pullRequestId}/commits?api-version=7.0",
+            AzureDevOpsEndpoints::Tags => "{project}/_apis/git/repositories/{repositoryId}/refs?filter=tags/{tag}&peelTags=true&api-version=7.0",
+            AzureDevOpsEndpoints::CommitDiffs => "{project}/_apis/git/repositories/{repositoryId}/diffs/commits?baseVersion={to}&baseVersionType=commit&targetVersion={from}&targetVersionType=commit&api-version=7.0"
+        }
+    }
+}
+
+enum AzureDevOpsOptions {
+    ContinuationToken,
+    Skip,
+    Top
+}
+
+impl AzureDevOpsOptions {
+    fn option(&self) -> &'static str {
+        match self {
+            AzureDevOpsOptions::ContinuationToken => "continuationToken",
+            AzureDevOpsOptions::Skip => "$skip",
+            AzureDevOpsOptions::Top => "$top"
+        }
+    }
+}
+
+/// The number of changes requested per page of a commit diff.
+const DIFF_PAGE_SIZE: usize = 1000;
+
+/// The `AzureDevOpsList` struct represents a list of results returned by the Azure DevOps API.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AzureDevOpsList<T> {
+    pub count: u32,
+    pub value: Vec<T>
+}
+
+/// The `AzureDevOpsPaginated` struct fetches the pages of an Azure DevOps list endpoint, following the
+/// continuation token returned with each page until no more pages are available.
+///
+/// You usually don't need to create an `AzureDevOpsPaginated` object manually, as the methods from
+/// `AzureDevOpsClient` return one when necessary.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use deployment_changelog::api::azure_devops::AzureDevOpsClient;
+/// use deployment_changelog::api::rest::Paginated;
+///
+/// // Suppose you have an AzureDevOpsClient named 'client'
+/// let mut commits_iter = client.compare_commits("MyProject", "my-repo", "abcdef", "123456");
+///
+/// while !commits_iter.is_last() {
+///     let commits = commits_iter.next().await.unwrap();
+///     println!("Fetched {} commits", commits.len());
+/// }
+/// ```
+pub struct AzureDevOpsPaginated<'a, T> {
+    client: &'a AzureDevOpsClient,
+    url: String,
+    query: HashMap<String, String>,
+    is_last_page: bool,
+    phantom: PhantomData<T>
+}
+
+impl<'a, T> AzureDevOpsPaginated<'a, T> {
+    fn new(client: &'a AzureDevOpsClient, url: String) -> Self {
+        AzureDevOpsPaginated {
+            client,
+            url,
+            query: HashMap::with_capacity(1),
+            is_last_page: false,
+            phantom: PhantomData
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<T: DeserializeOwned + Send> Paginated<T> for AzureDevOpsPaginated<'_, T> {
+    async fn next(&mut self) -> Result<Vec<T>> {
+        let (headers, page) = self.client.client.get_with_headers::<AzureDevOpsList<T>>(&self.url, Some(&self.query)).await?;
+
+        let continuation_token = headers.get(CONTINUATION_TOKEN_HEADER)
+            .and_then(|token| token.to_str().ok());
+
+        match continuation_token {
+            Some(token) => {
+                self.query.insert(AzureDevOpsOptions::ContinuationToken.option().to_string(), token.to_string());
+            },
+            None => self.is_last_page = true
+        };
+
+        Ok(page.value)
+    }
+
+    fn is_last(&self) -> bool {
+        self.is_last_page
+    }
+}
+
+/// The `AzureDevOpsCommit` struct represents a single commit returned by the Azure DevOps API.
+///
+/// Azure DevOps truncates long commit messages in list results, in which case `comment_truncated` is set.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AzureDevOpsCommit {
+    pub commit_id: String,
+    pub author: AzureDevOpsGitUser,
+    pub committer: AzureDevOpsGitUser,
+    pub comment: String,
+
+    #[serde(default)]
+    pub comment_truncated: bool
+}
+
+impl Display for AzureDevOpsCommit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match serde_json::to_string_pretty(&self) {
+            Ok(json) => write!(f, "{json}"),
+            Err(error) => panic!("Error serializing Azure DevOps commit: {error}")
+        }
+    }
+}
+
+/// The `AzureDevOpsRef` struct represents a Git ref of an Azure DevOps repository. For annotated tags,
+/// `object_id` is the ID of the tag object and `peeled_object_id` the commit it points to.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AzureDevOpsRef {
+    pub name: String,
+    pub object_id: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub peeled_object_id: Option<String>
+}
+
+impl Display for AzureDevOpsRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match serde_json::to_string_pretty(&self) {
+            Ok(json) => write!(f, "{json}"),
+            Err(error) => panic!("Error serializing Azure DevOps ref: {error}")
+        }
+    }
+}
+
+/// The `AzureDevOpsGitUser` struct represents the author or committer of an Azure DevOps commit.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AzureDevOpsGitUser {
+    pub name: String,
+    pub email: String,
+    pub date: DateTime<Utc>
+}
+
+/// The `AzureDevOpsPullRequest` struct represents a pull request returned by the Azure DevOps API.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use deployment_changelog::api::azure_devops::AzureDevOpsClient;
+///
+/// // Suppose you have an AzureDevOpsClient named 'client'
+/// let pull_request = client.get_pull_request("MyProject", "my-repo", 42).await.unwrap();
+///
+/// println!("#{} {} ({})", pull_request.pull_request_id, pull_request.title, pull_request.status);
+/// ```
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AzureDevOpsPullRequest {
+    pub pull_request_id: u64,
+    pub title: String,
+    pub description: Option<String>,
+    pub status: String,
+    pub created_by: AzureDevOpsIdentity,
+    pub creation_date: DateTime<Utc>,
+    pub closed_date: Option<DateTime<Utc>>
+}
+
+impl Display for AzureDevOpsPullRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match serde_json::to_string_pretty(&self) {
+            Ok(json) => write!(f, "{json}"),
+            Err(error) => panic!("Error serializing Azure DevOps pull request: {error}")
+        }
+    }
+}
+
+/// The `AzureDevOpsIdentity` struct represents an Azure DevOps user, such as the creator of a pull request.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AzureDevOpsIdentity {
+    pub id: String,
+    pub display_name: String,
+    pub unique_name: String
+}
+
+/// The `AzureDevOpsCommitDiffs` struct represents a page of the changes between two commits of an Azure DevOps
+/// repository. `all_changes_included` is unset when more pages of changes are available.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AzureDevOpsCommitDiffs {
+    #[serde(default)]
+    pub all_changes_included: bool,
+
+    #[serde(default)]
+    pub changes: Vec<AzureDevOpsChange>
+}
+
+/// The `AzureDevOpsChange` struct represents a file or folder changed between two commits.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AzureDevOpsChange {
+    pub item: AzureDevOpsItem,
+    pub change_type: String
+}
+
+impl Display for AzureDevOpsChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match serde_json::to_string_pretty(&self) {
+            Ok(json) => write!(f, "{json}"),
+            Err(error) => panic!("Error serializing Azure DevOps change: {error}")
+        }
+    }
+}
+
+/// The `AzureDevOpsItem` struct represents a file or folder of an Azure DevOps repository, with its path
+/// relative to the repository root starting with `/`.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AzureDevOpsItem {
+    pub path: String,
+
+    #[serde(default)]
+    pub is_folder: bool
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct PullRequestQueryBody {
+    queries: Vec<PullRequestQueryInput>
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct PullRequestQueryInput {
+    #[serde(rename = "type")]
+    query_type: String,
+    items: Vec<String>
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct PullRequestQueryResponse {
+    results: Vec<HashMap<String, Vec<AzureDevOpsPullRequest>>>
+}
+
+/// The `AzureDevOpsClient` struct is a high-level API client for working with Azure DevOps Repos.
+///
+/// It provides methods for comparing commits, looking up the pull requests that contain a commit, and
+/// fetching a pull request. It also implements `ScmProvider`, so it can be used in place of a
+/// `BitbucketClient` to generate a changelog.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use deployment_changelog::api::azure_devops::AzureDevOpsClient;
+///
+/// let client = AzureDevOpsClient::new("https://dev.azure.com/my-organization/").unwrap();
+/// let pull_requests = client.get_pull_requests("MyProject", "my-repo", "abcdef").await.unwrap();
+/// ```
+#[derive(Debug)]
+pub struct AzureDevOpsClient {
+    client: RestClient
+}
+
+impl AzureDevOpsClient {
+    /// Creates a new AzureDevOpsClient instance given the base URL of the Azure DevOps organization.
+    ///
+    /// # Arguments
+    ///
+    /// * `base_url` - The base URL of the Azure DevOps organization.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing an AzureDevOpsClient instance or an error if the provided base URL is invalid.
+    pub fn new(base_url: &str) -> Result<Self> {
+        Ok(Self {
+            client: RestClient::new(base_url)?
+        })
+    }
+
+    /// Constructs an AzureDevOpsClient instance from a pre-initialized RestClient.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - An instance of RestClient.
+    pub fn from_client(client: RestClient) -> Self {
+        Self {
+            client
+        }
+    }
+
+    /// Returns an `AzureDevOpsPaginated<AzureDevOpsCommit>` instance for fetching the commits reachable
+    /// from `start_commit` but not from `end_commit` in an Azure DevOps repository.
+    ///
+    /// # Arguments
+    ///
+    /// * `project` - The Azure DevOps project name.
+    /// * `repo` - The Git repository name or ID.
+    /// * `start_commit` - The most recent commit of the range.
+    /// * `end_commit` - The oldest commit of the range, which is excluded.
+    pub fn compare_commits(&self, project: &str, repo: &str, start_commit: &str, end_commit: &str) -> AzureDevOpsPaginated<'_, AzureDevOpsCommit> {
+        let compare_commits_path: String = AzureDevOpsEndpoints::CompareCommits.url()
+            .replace("{project}", project)
+            .replace("{repositoryId}", repo)
+            .replace("{from}", start_commit)
+            .replace("{to}", end_commit);
+
+        AzureDevOpsPaginated::new(self, compare_commits_path)
+    }
+
+    /// Fetches the files and folders changed between `end_commit` and `start_commit` in an Azure DevOps repository.
+    ///
+    /// # Arguments
+    ///
+    /// * `project` - The Azure DevOps project name.
+    /// * `repo` - The Git repository name or ID.
+    /// * `start_commit` - The most recent commit of the range.
+    /// * `end_commit` - The oldest commit of the range.
+    pub async fn get_commit_diffs(&self, project: &str, repo: &str, start_commit: &str, end_commit: &str) -> Result<Vec<AzureDevOpsChange>> {
+        let commit_diffs_path: String = AzureDevOpsEndpoints::CommitDiffs.url()
+            .replace("{project}", project)
+            .replace("{repositoryId}", repo)
+            .replace("{from}", start_commit)
+            .replace("{to}", end_commit);
+
+        let mut query: HashMap<String, String> = HashMap::from([
+            (AzureDevOpsOptions::Top.option().to_string(), DIFF_PAGE_SIZE.to_string())
+        ]);
+
+        let mut changes: Vec<AzureDevOpsChange> = Vec::new();
+
+        // The diff endpoint pages with offsets rather than continuation tokens.
+        loop {
+            query.insert(AzureDevOpsOptions::Skip.option().to_string(), changes.len().to_string());
+
+            let page = self.client.get::<AzureDevOpsCommitDiffs>(&commit_diffs_path, Some(&query))
+                .await
+                .with_context(|| format!("Error fetching the changes between {end_commit} and {start_commit}"))?;
+
+            let is_last_page = page.all_changes_included || page.changes.is_empty();
+            changes.extend(page.changes);
+
+            if is_last_page {
+                return Ok(changes);
+            }
+        }
+    }
+
+    /// Fetches the pull requests of an Azure DevOps repository that contain the given commit.
+    ///
+    /// # Arguments
+    ///
+    /// * `project` - The Azure DevOps project name.
+    /// * `repo` - The Git repository name or ID.
+    /// * `commit` - The commit ID to fetch the pull requests for.
+    pub async fn get_pull_requests(&self, project: &str, repo: &str, commit: &str) -> Result<Vec<AzureDevOpsPullRequest>> {
+        let pull_request_query_path: String = AzureDevOpsEndpoints::PullRequestQuery.url()
+            .replace("{project}", project)
+            .replace("{repositoryId}", repo);
+
+        let body = PullRequestQueryBody {
+            queries: vec![PullRequestQueryInput {
+                query_type: String::from("commit"),
+                items: vec![commit.to_string()]
+            }]
+        };
+
+        let response = self.client.post_json_idempotent::<PullRequestQueryResponse, PullRequestQueryBody>(&pull_request_query_path, &body)
+            .await
+            .with_context(|| format!("Error querying the pull requests for commit {commit}"))?;
+
+        Ok(response.results
+            .into_iter()
+            .flat_map(|result| result.into_values())
+            .flatten()
+            .collect())
+    }
+
+    /// Fetches a single pull request of an Azure DevOps repository.
+    ///
+    /// # Arguments
+    ///
+    /// * `project` - The Azure DevOps project name.
+    /// * `repo` - The Git repository name or ID.
+    /// * `pull_request_id` - The ID of the pull request.
+    pub async fn get_pull_request(&self, project: &str, repo: &str, pull_request_id: u64) -> Result<AzureDevOpsPullRequest> {
+        let pull_request_path: String = AzureDevOpsEndpoints::PullRequest.url()
+            .replace("{project}", project)
+            .replace("{repositoryId}", repo)
+            .replace("{pullRequestId}", &pull_request_id.to_string());
+
+        self.client.get::<AzureDevOpsPullRequest>(&pull_request_path, None).await
+    }
+
+    /// Returns an `AzureDevOpsPaginated<AzureDevOpsCommit>` instance for fetching the commits of a pull request.
+    ///
+    /// # Arguments
+    ///
+    /// * `project` - The Azure DevOps project name.
+    /// * `repo` - The Git repository name or ID.
+    /// * `pull_request_id` - The ID of the pull request.
+    pub fn get_pull_request_commits(&self, project: &str, repo: &str, pull_request_id: u64) -> AzureDevOpsPaginated<'_, AzureDevOpsCommit> {
+        let pull_request_commits_path: String = AzureDevOpsEndpoints::PullRequestCommits.url()
+            .replace("{project}", project)
+            .replace("{repositoryId}", repo)
+            .replace("{pullRequestId}", &pull_request_id.to_string());
+
+        AzureDevOpsPaginated::new(self, pull_request_commits_path)
+    }
+
+    /// Fetches a tag of an Azure DevOps repository.
+    ///
+    /// # Arguments
+    ///
+    /// * `project` - The Azure DevOps project name or ID.
+    /// * `repo` - The repository name or ID.
+    /// * `tag` - The name of the tag.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the `AzureDevOpsRef` of the tag, or an error if the request fails or the tag does not exist.
+    pub async fn get_tag(&self, project: &str, repo: &str, tag: &str) -> Result<AzureDevOpsRef> {
+        let tags_path: String = AzureDevOpsEndpoints::Tags.url()
+            .replace("{project}", project)
+            .replace("{repositoryId}", repo)
+            .replace("{tag}", tag);
+
+        let tag_ref = format!("refs/tags/{tag}");
+
+        // The filter matches tags by prefix, so the exact tag has to be picked out.
+        self.client.get::<AzureDevOpsList<AzureDevOpsRef>>(&tags_path, None)
+            .await?
+            .value
+            .into_iter()
+            .find(|azure_devops_ref| azure_devops_ref.name == tag_ref)
+            .with_context(|| format!("Tag {tag} was not found in repository {project}/{repo}"))
+    }
+}
+
+#[async_trait::async_trait]
+impl ScmProvider for AzureDevOpsClient {
+    fn base_url(&self) -> &Url {
+        &self.client.base_url
+    }
+
+    async fn commits_between(&self, project: &str, repo: &str, start_commit: &str, end_commit: &str) -> Result<Vec<BitbucketCommit>> {
+        let commits = self.compare_commits(project, repo, start_commit, end_commit)
+            .all()
+            .await?;
+
+        Ok(commits.into_iter()
+            .map(BitbucketCommit::from)
+            .collect())
+    }
+
+    async fn changed_files(&self, project: &str, repo: &str, start_commit: &str, end_commit: &str) -> Result<Vec<String>> {
+        Ok(self.get_commit_diffs(project, repo, start_commit, end_commit)
+            .await?
+            .into_iter()
+            .filter(|change| !change.item.is_folder)
+            .map(|change| change.item.path.trim_start_matches('/').to_string())
+            .collect())
+    }
+
+    async fn resolve_tag(&self, project: &str, repo: &str, tag: &str) -> Result<String> {
+        let tag_ref = self.get_tag(project, repo, tag).await?;
+
+        Ok(tag_ref.peeled_object_id.unwrap_or(tag_ref.object_id))
+    }
+
+    async fn pull_requests_for_commit(&self, project: &str, repo: &str, commit: &str) -> Result<Vec<BitbucketPullRequest>> {
+        Ok(self.get_pull_requests(project, repo, commit)
+            .await?
+            .into_iter()
+            .map(BitbucketPullRequest::from)
+            .collect())
+    }
+
+    async fn pull_request(&self, project: &str, repo: &str, pull_request_id: u64) -> Result<BitbucketPullRequest> {
+        Ok(self.get_pull_request(project, repo, pull_request_id)
+            .await?
+            .into())
+    }
+
+    async fn pull_request_size(&self, project: &str, repo: &str, pull_request_id: u64) -> Result<BitbucketPullRequestSize> {
+        let commits = self.get_pull_request_commits(project, repo, pull_request_id)
+            .all()
+            .await?;
+
+        // The files changed by a pull request are only listed per iteration of its pushes.
+        Ok(BitbucketPullRequestSize {
+            commit_count: commits.len(),
+            file_count: None
+        })
+    }
+
+    async fn issues_for_pull_request(&self, project: &str, repo: &str, pull_request_id: u64) -> Result<Vec<BitbucketPullRequestIssue>> {
+        let pull_request = self.get_pull_request(project, repo, pull_request_id).await?;
+
+        let text = format!("{}\n{}", pull_request.title, pull_request.description.unwrap_or_default());
+
+        Ok(extract_issue_keys(&text)
+            .into_iter()
+            .map(|key| BitbucketPullRequestIssue {
+                key,
+                url: String::new()
+            })
+            .collect())
+    }
+}
+
+impl From<AzureDevOpsGitUser> for BitbucketAuthor {
+    fn from(user: AzureDevOpsGitUser) -> Self {
+        Self {
+            display_name: user.name.clone(),
+            name: user.name,
+            email_address: user.email
+        }
+    }
+}
+
+impl From<AzureDevOpsCommit> for BitbucketCommit {
+    fn from(commit: AzureDevOpsCommit) -> Self {
+        Self {
+            display_id: commit.commit_id.chars().take(DISPLAY_ID_LENGTH).collect(),
+            id: commit.commit_id,
+            author_timestamp: commit.author.date,
+            author: commit.author.into(),
+            committer: commit.committer.into(),
+            message: commit.comment,
+            entry_id: None
+        }
+    }
+}
+
+impl From<AzureDevOpsIdentity> for BitbucketAuthor {
+    fn from(identity: AzureDevOpsIdentity) -> Self {
+        Self {
+            name: identity.unique_name.clone(),
+            email_address: identity.unique_name,
+            display_name: identity.display_name
+        }
+    }
+}
+
+/// Maps the status of an Azure DevOps pull request, `active`, `completed` or `abandoned`, to the state of a
+/// Bitbucket pull request.
+fn azure_devops_pull_request_state(status: &str) -> &str {
+    match status {
+        "active" => "OPEN",
+        "completed" => "MERGED",
+        "abandoned" => "DECLINED",
+        status => status
+    }
+}
+
+impl From<AzureDevOpsPullRequest> for BitbucketPullRequest {
+    fn from(pull_request: AzureDevOpsPullRequest) -> Self {
+        Self {
+            id: pull_request.pull_request_id,
+            title: pull_request.title,
+            description: pull_request.description.unwrap_or_default(),
+            open: pull_request.status == "active",
+            state: Some(azure_devops_pull_request_state(&pull_request.status).to_string()),
+            author: BitbucketPullRequestAuthor {
+                user: pull_request.created_by.into(),
+                approved: false
+            },
+            created_date: pull_request.creation_date,
+            updated_date: pull_request.closed_date.unwrap_or(pull_request.creation_date),
+            closed_date: pull_request.closed_date,
+            from_ref: None,
+            to_ref: None,
+            release_note: None,
+            size: None,
+            entry_id: None
+        }
+    }
+}
@@ -59,25 +59,55 @@
 use std::{fmt::Display, collections::HashMap, marker::PhantomData};
 
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
-use serde_with::chrono::{DateTime, Local};
+use serde_with::chrono::{DateTime, Utc};
 use serde_with::TimestampMilliSeconds;
 use serde_with::formats::Flexible;
-use anyhow::Result;
+use anyhow::{Context, Result};
 
-use super::rest::{RestClient, Paginated};
+use reqwest::Url;
+
+use super::{rest::{Credentials, RestClient, Paginated, is_not_found}, scm::{ScmProvider, DiffLine}};
 
 enum BitbucketEndpoints {
     CompareCommits,
+    CompareChanges,
+    CompareDiff,
     PullRequestsForCommit,
-    IssuesForPullRequest
+    IssuesForPullRequest,
+    Projects,
+    Repos,
+    DefaultBranch,
+    Tag,
+    Commit,
+    Commits,
+    MergeBase,
+    PullRequests,
+    PullRequest,
+    PullRequestCommits,
+    PullRequestChanges,
+    PullRequestComments
 }
 
 impl BitbucketEndpoints {
     fn url(&self) -> &'static str {
         match self {
             BitbucketEndpoints::CompareCommits => "rest/api/latest/projects/{projectKey}/repos/{repositorySlug}/compare/commits?from={from}&to={to}",
+            BitbucketEndpoints::CompareChanges => "rest/api/latest/projects/{projectKey}/repos/{repositorySlug}/compare/changes?from={from}&to={to}",
+            BitbucketEndpoints::CompareDiff => "rest/api/latest/projects/{projectKey}/repos/{repositorySlug}/compare/diff?from={from}&to={to}&contextLines=0",
             BitbucketEndpoints::PullRequestsForCommit => "rest/api/latest/projects/{projectKey}/repos/{repositorySlug}/commits/{commitId}/pull-requests",
-            BitbucketEndpoints::IssuesForPullRequest => "/rest/jira/latest/projects/{projectKey}/repos/{repositorySlug}/pull-requests/{pullRequestId}/issues"
+            BitbucketEndpoints::IssuesForPullRequest => "/rest/jira/latest/projects/{projectKey}/repos/{repositorySlug}/pull-requests/{pullRequestId}/issues",
+            BitbucketEndpoints::Projects => "rest/api/latest/projects",
+            BitbucketEndpoints::Repos => "rest/api/latest/projects/{projectKey}/repos",
+            BitbucketEndpoints::DefaultBranch => "rest/api/latest/projects/{projectKey}/repos/{repositorySlug}/default-branch",
+            BitbucketEndpoints::Tag => "rest/api/latest/projects/{projectKey}/repos/{repositorySlug}/tags/{name}",
+            BitbucketEndpoints::Commit => "rest/api/latest/projects/{projectKey}/repos/{repositorySlug}/commits/{commitId}",
+            BitbucketEndpoints::Commits => "rest/api/latest/projects/{projectKey}/repos/{repositorySlug}/commits",
+            BitbucketEndpoints::MergeBase => "rest/api/latest/projects/{projectKey}/repos/{repositorySlug}/commits/{commitId}/merge-base?otherCommitId={otherCommitId}",
+            BitbucketEndpoints::PullRequests => "rest/api/latest/projects/{projectKey}/repos/{repositorySlug}/pull-requests",
+            BitbucketEndpoints::PullRequest => "rest/api/latest/projects/{projectKey}/repos/{repositorySlug}/pull-requests/{pullRequestId}",
+            BitbucketEndpoints::PullRequestCommits => "rest/api/latest/projects/{projectKey}/repos/{repositorySlug}/pull-requests/{pullRequestId}/commits",
+            BitbucketEndpoints::PullRequestChanges => "rest/api/latest/projects/{projectKey}/repos/{repositorySlug}/pull-requests/{pullRequestId}/changes",
+            BitbucketEndpoints::PullRequestComments => "rest/api/latest/projects/{projectKey}/repos/{repositorySlug}/pull-requests/{pullRequestId}/comments"
         }
     }
 }
@@ -300,14 +330,22 @@ impl<T: DeserializeOwned + Send> Paginated<T> for BitbucketPaginated<'_, T> {
 ///     println!("Message: {}", commit.message);
 /// }
 /// ```
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash)]
+#[serde_with::serde_as]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct BitbucketCommit {
     pub id: String,
     pub display_id: String,
     pub author: BitbucketAuthor,
+
+    #[serde_as(as = "TimestampMilliSeconds<String, Flexible>")]
+    pub author_timestamp: DateTime<Utc>,
+
     pub committer: BitbucketAuthor,
-    pub message: String
+    pub message: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entry_id: Option<String>
 }
 
 impl Display for BitbucketCommit {
@@ -350,7 +388,7 @@ impl Display for BitbucketCommit {
 ///     println!("Author display name: {}", author.display_name);
 /// }
 /// ```
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct BitbucketAuthor {
     pub name: String,
@@ -367,9 +405,15 @@ impl Display for BitbucketAuthor {
     }
 }
 
+/// The marker delimiting the release notes section of a pull request description.
+const RELEASE_NOTES_MARKER: &str = "<!-- release-notes -->";
+
 /// The `BitbucketPullRequest` struct represents a pull request returned by the Bitbucket API.
 ///
 /// It contains information about the pull request, such as the ID, title, description, open status, author, and creation and update dates.
+/// The `state` is `OPEN`, `MERGED` or `DECLINED`, and the other source control providers map the states of
+/// their pull requests to these. The `release_note` is not returned by Bitbucket, but extracted from the
+/// description with `BitbucketPullRequest::extract_release_note()`.
 ///
 /// This struct is usually used when working with the `BitbucketClient` to fetch pull requests associated with a commit.
 ///
@@ -400,20 +444,94 @@ impl Display for BitbucketAuthor {
 /// }
 /// ```
 #[serde_with::serde_as]
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct BitbucketPullRequest {
     pub id: u64,
     pub title: String,
     pub description: String,
     pub open: bool,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+
     pub author: BitbucketPullRequestAuthor,
 
     #[serde_as(as = "TimestampMilliSeconds<String, Flexible>")]
-    pub created_date: DateTime<Local>,
+    pub created_date: DateTime<Utc>,
 
     #[serde_as(as = "TimestampMilliSeconds<String, Flexible>")]
-    pub updated_date: DateTime<Local>
+    pub updated_date: DateTime<Utc>,
+
+    #[serde_as(as = "Option<TimestampMilliSeconds<String, Flexible>>")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub closed_date: Option<DateTime<Utc>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_ref: Option<BitbucketRef>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_ref: Option<BitbucketRef>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub release_note: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<BitbucketPullRequestSize>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entry_id: Option<String>
+}
+
+impl BitbucketPullRequest {
+    /// Returns the project key and repository slug of the repository the pull request targets, if known.
+    ///
+    /// Pull request IDs are scoped to their target repository, which differs from the repository the commits
+    /// were listed in when the pull request comes from a fork or a personal repository.
+    pub fn target_repository(&self) -> Option<(&str, &str)> {
+        self.to_ref.as_ref()
+            .map(|to_ref| (to_ref.repository.project.key.as_str(), to_ref.repository.slug.as_str()))
+    }
+
+    /// Returns whether the pull request was merged, rather than still open or declined.
+    pub fn is_merged(&self) -> bool {
+        self.state.as_deref() == Some("MERGED")
+    }
+
+    /// Returns the review latency of the pull request, the time between its creation and its merge, in seconds,
+    /// if it was merged and the server returned when it was closed.
+    pub fn review_latency_seconds(&self) -> Option<i64> {
+        self.closed_date
+            .filter(|_| self.is_merged())
+            .map(|closed_date| (closed_date - self.created_date).num_seconds())
+    }
+
+    /// Extracts the release note of the pull request: the section of its description between two
+    /// `<!-- release-notes -->` markers, collapsed to a single line. Renderers prefer the release note to the
+    /// title of the pull request.
+    ///
+    /// # Returns
+    ///
+    /// An `Option<String>` containing the release note, or `None` if the description has no non-empty
+    /// release notes section.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// // Suppose you fetched a BitbucketPullRequest named 'pull_request' with the description
+    /// // "Refactors the retries\n<!-- release-notes -->\nPayments are retried on timeouts\n<!-- release-notes -->"
+    /// assert_eq!(pull_request.extract_release_note().as_deref(), Some("Payments are retried on timeouts"));
+    /// ```
+    pub fn extract_release_note(&self) -> Option<String> {
+        let (_, section) = self.description.split_once(RELEASE_NOTES_MARKER)?;
+        let (release_note, _) = section.split_once(RELEASE_NOTES_MARKER)?;
+
+        let release_note = release_note.split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" ");
+
+        (!release_note.is_empty()).then_some(release_note)
+    }
 }
 
 impl Display for BitbucketPullRequest {
@@ -425,6 +543,185 @@ impl Display for BitbucketPullRequest {
     }
 }
 
+/// The `BitbucketRef` struct represents the source or target branch of a pull request, along with the
+/// repository it belongs to.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BitbucketRef {
+    pub id: String,
+    pub display_id: String,
+    pub repository: BitbucketRepository
+}
+
+/// The `BitbucketRepository` struct represents a Bitbucket repository. Personal repositories belong to
+/// a project whose key is the owner's username prefixed with `~`.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BitbucketRepository {
+    pub slug: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    pub project: BitbucketProject
+}
+
+impl Display for BitbucketRepository {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match serde_json::to_string_pretty(&self) {
+            Ok(json) => write!(f, "{json}"),
+            Err(error) => panic!("Error serializing Bitbucket repository: {error}")
+        }
+    }
+}
+
+/// The `BitbucketProject` struct represents a Bitbucket project, such as the one a repository belongs to.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BitbucketProject {
+    pub key: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>
+}
+
+impl Display for BitbucketProject {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match serde_json::to_string_pretty(&self) {
+            Ok(json) => write!(f, "{json}"),
+            Err(error) => panic!("Error serializing Bitbucket project: {error}")
+        }
+    }
+}
+
+/// The `BitbucketBranch` struct represents a branch of a Bitbucket repository, such as its default branch.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BitbucketBranch {
+    pub id: String,
+    pub display_id: String,
+    pub latest_commit: Option<String>,
+
+    #[serde(default)]
+    pub is_default: bool
+}
+
+impl Display for BitbucketBranch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match serde_json::to_string_pretty(&self) {
+            Ok(json) => write!(f, "{json}"),
+            Err(error) => panic!("Error serializing Bitbucket branch: {error}")
+        }
+    }
+}
+
+/// The `BitbucketTag` struct represents a tag of a Bitbucket repository. `latest_commit` is the commit the tag
+/// points to, and `hash` is the ID of the tag object itself for annotated tags.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BitbucketTag {
+    pub id: String,
+    pub display_id: String,
+    pub latest_commit: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>
+}
+
+impl Display for BitbucketTag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match serde_json::to_string_pretty(&self) {
+            Ok(json) => write!(f, "{json}"),
+            Err(error) => panic!("Error serializing Bitbucket tag: {error}")
+        }
+    }
+}
+
+/// The `BitbucketChange` struct represents a file changed between two commits of a Bitbucket repository.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BitbucketChange {
+    pub path: BitbucketPath,
+
+    #[serde(rename = "type")]
+    pub change_type: String
+}
+
+impl Display for BitbucketChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match serde_json::to_string_pretty(&self) {
+            Ok(json) => write!(f, "{json}"),
+            Err(error) => panic!("Error serializing Bitbucket change: {error}")
+        }
+    }
+}
+
+/// The `BitbucketPath` struct represents the path of a file in a Bitbucket repository. `to_string` is the full path.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BitbucketPath {
+    #[serde(rename = "toString")]
+    pub to_string: String
+}
+
+/// The `BitbucketDiffs` struct represents the diff between two commits of a Bitbucket repository, with a
+/// `BitbucketDiff` for every changed file.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BitbucketDiffs {
+    #[serde(default)]
+    pub diffs: Vec<BitbucketDiff>
+}
+
+impl Display for BitbucketDiffs {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match serde_json::to_string_pretty(&self) {
+            Ok(json) => write!(f, "{json}"),
+            Err(error) => panic!("Error serializing Bitbucket diffs: {error}")
+        }
+    }
+}
+
+/// The `BitbucketDiff` struct represents the changes to a single file. `source` is unset for added files,
+/// and `destination` for deleted files.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BitbucketDiff {
+    pub source: Option<BitbucketPath>,
+    pub destination: Option<BitbucketPath>,
+
+    #[serde(default)]
+    pub hunks: Vec<BitbucketDiffHunk>
+}
+
+/// The `BitbucketDiffHunk` struct represents a hunk of a `BitbucketDiff`, made of segments of context, added
+/// or removed lines.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BitbucketDiffHunk {
+    #[serde(default)]
+    pub segments: Vec<BitbucketDiffSegment>
+}
+
+/// The `BitbucketDiffSegment` struct represents consecutive lines of a hunk with the same `segment_type`,
+/// which is one of `ADDED`, `REMOVED` or `CONTEXT`.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BitbucketDiffSegment {
+    #[serde(rename = "type")]
+    pub segment_type: String,
+
+    #[serde(default)]
+    pub lines: Vec<BitbucketDiffLine>
+}
+
+/// The `BitbucketDiffLine` struct represents a line of a `BitbucketDiffSegment`.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BitbucketDiffLine {
+    pub line: String
+}
+
 /// The `BitbucketPullRequestAuthor` struct represents the author of a pull request returned by the Bitbucket API.
 ///
 /// It contains information about the author, such as the user and whether the pull request has been approved by the author.
@@ -454,7 +751,7 @@ impl Display for BitbucketPullRequest {
 ///     println!("Author approval status: {}", pr.author.approved);
 /// }
 /// ```
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct BitbucketPullRequestAuthor {
     pub user: BitbucketAuthor,
@@ -470,6 +767,31 @@ impl Display for BitbucketPullRequestAuthor {
     }
 }
 
+/// The `BitbucketPullRequestSize` struct represents the size of a pull request, found with
+/// `ScmProvider::pull_request_size()`. It contains the following fields:
+///
+/// - `commit_count`: The number of commits of the pull request.
+/// - `file_count`: The number of files the pull request changes, if the source control server reports them.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use deployment_changelog::api::scm::ScmProvider;
+///
+/// // Suppose you have a BitbucketClient named 'client'
+/// let size = client.pull_request_size("PROJECT", "my-repo", 42).await.unwrap();
+///
+/// println!("{} commits, {:?} files", size.commit_count, size.file_count);
+/// ```
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Eq, Hash, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BitbucketPullRequestSize {
+    pub commit_count: usize,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_count: Option<usize>
+}
+
 /// The `BitbucketPullRequestIssue` struct represents an issue associated with a pull request returned by the Bitbucket API.
 ///
 /// It contains information about the issue, such as the key and URL of the issue.
@@ -519,6 +841,28 @@ impl Display for BitbucketPullRequestIssue {
     }
 }
 
+/// The `BitbucketComment` struct represents a comment on a Bitbucket pull request, as returned when it is posted.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BitbucketComment {
+    pub id: u64,
+    pub text: String
+}
+
+impl Display for BitbucketComment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match serde_json::to_string_pretty(&self) {
+            Ok(json) => write!(f, "{json}"),
+            Err(error) => panic!("Error serializing Bitbucket comment: {error}")
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+struct BitbucketCommentBody<'a> {
+    text: &'a str
+}
+
 /// The `BitbucketClient` struct is a high-level API client for working with the Bitbucket API.
 ///
 /// It provides methods for common operations like comparing commits, fetching pull requests for a commit, and getting issues associated with a pull request.
@@ -594,6 +938,39 @@ impl BitbucketClient {
         })
     }
 
+    /// Creates a new BitbucketClient instance given the base URL, authenticating with a token.
+    ///
+    /// # Arguments
+    ///
+    /// * `base_url` - The base URL of the Bitbucket API.
+    /// * `token` - A Bitbucket HTTP access token or personal access token.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing a BitbucketClient instance or an error if the provided base URL is invalid.
+    pub fn with_token(base_url: &str, token: &str) -> Result<Self> {
+        Ok(Self {
+            client: RestClient::with_token(base_url, token)?
+        })
+    }
+
+    /// Creates a new BitbucketClient instance given the base URL, authenticating with the given credentials.
+    ///
+    /// # Arguments
+    ///
+    /// * `base_url` - The base URL of the Bitbucket API.
+    /// * `credentials` - The `Credentials` of the Bitbucket server, such as a personal access token or a username
+    ///   and password.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing a BitbucketClient instance or an error if the provided base URL is invalid.
+    pub fn with_credentials(base_url: &str, credentials: Credentials) -> Result<Self> {
+        Ok(Self {
+            client: RestClient::with_credentials(base_url, credentials)?
+        })
+    }
+
     /// Constructs a BitbucketClient instance from a pre-initialized RestClient.
     ///
     /// # Arguments
@@ -605,6 +982,11 @@ impl BitbucketClient {
         }
     }
 
+    /// Returns the base URL of the Bitbucket server this client talks to.
+    pub fn base_url(&self) -> &Url {
+        &self.client.base_url
+    }
+
     /// Returns a `BitbucketPaginated<BitbucketCommit>` instance for fetching commits between
     /// two commit IDs (start_commit and end_commit) in a specified Bitbucket project and repository.
     ///
@@ -618,14 +1000,60 @@ impl BitbucketClient {
     /// # Returns
     ///
     /// A `BitbucketPaginated<BitbucketCommit>` instance.
-    pub fn compare_commits(&self, project: &str, repo: &str, start_commit: &str, end_commit: &str) -> BitbucketPaginated<BitbucketCommit> {
+    pub fn compare_commits(&self, project: &str, repo: &str, start_commit: &str, end_commit: &str) -> BitbucketPaginated<'_, BitbucketCommit> {
         let compare_commits_path: String = BitbucketEndpoints::CompareCommits.url()
             .replace("{projectKey}", project)
             .replace("{repositorySlug}", repo)
             .replace("{from}", start_commit)
             .replace("{to}", end_commit);
 
-        BitbucketPaginated::new(&self, compare_commits_path, None)
+        BitbucketPaginated::new(self, compare_commits_path, None)
+    }
+
+    /// Returns a `BitbucketPaginated<BitbucketChange>` instance for fetching the files changed between
+    /// two commit IDs (start_commit and end_commit) in a specified Bitbucket project and repository.
+    ///
+    /// # Arguments
+    ///
+    /// * `project` - The project key in Bitbucket.
+    /// * `repo` - The repository slug in Bitbucket.
+    /// * `start_commit` - The commit ID to start the comparison from.
+    /// * `end_commit` - The commit ID to end the comparison at.
+    ///
+    /// # Returns
+    ///
+    /// A `BitbucketPaginated<BitbucketChange>` instance.
+    pub fn compare_changes(&self, project: &str, repo: &str, start_commit: &str, end_commit: &str) -> BitbucketPaginated<'_, BitbucketChange> {
+        let compare_changes_path: String = BitbucketEndpoints::CompareChanges.url()
+            .replace("{projectKey}", project)
+            .replace("{repositorySlug}", repo)
+            .replace("{from}", start_commit)
+            .replace("{to}", end_commit);
+
+        BitbucketPaginated::new(self, compare_changes_path, None)
+    }
+
+    /// Fetches the diff between two commit IDs (start_commit and end_commit) in a specified Bitbucket project
+    /// and repository, without any context lines.
+    ///
+    /// # Arguments
+    ///
+    /// * `project` - The project key in Bitbucket.
+    /// * `repo` - The repository slug in Bitbucket.
+    /// * `start_commit` - The commit ID to start the comparison from.
+    /// * `end_commit` - The commit ID to end the comparison at.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the `BitbucketDiffs` or an error if the request fails.
+    pub async fn compare_diff(&self, project: &str, repo: &str, start_commit: &str, end_commit: &str) -> Result<BitbucketDiffs> {
+        let compare_diff_path: String = BitbucketEndpoints::CompareDiff.url()
+            .replace("{projectKey}", project)
+            .replace("{repositorySlug}", repo)
+            .replace("{from}", start_commit)
+            .replace("{to}", end_commit);
+
+        self.client.get::<BitbucketDiffs>(&compare_diff_path, None).await
     }
 
     /// Returns a `BitbucketPaginated<BitbucketPullRequest>` instance for fetching pull requests
@@ -640,13 +1068,37 @@ impl BitbucketClient {
     /// # Returns
     ///
     /// A `BitbucketPaginated<BitbucketPullRequest>` instance.
-    pub fn get_pull_requests(&self, project: &str, repo: &str, commit: &str) -> BitbucketPaginated<BitbucketPullRequest> {
+    pub fn get_pull_requests(&self, project: &str, repo: &str, commit: &str) -> BitbucketPaginated<'_, BitbucketPullRequest> {
         let get_pull_requests_path: String = BitbucketEndpoints::PullRequestsForCommit.url()
             .replace("{projectKey}", project)
             .replace("{repositorySlug}", repo)
             .replace("{commitId}", commit);
 
-        BitbucketPaginated::new(&self, get_pull_requests_path, None)
+        BitbucketPaginated::new(self, get_pull_requests_path, None)
+    }
+
+    /// Returns a `BitbucketPaginated<BitbucketPullRequest>` instance for fetching the pull requests of a Bitbucket
+    /// project and repository in every state, most recently updated first.
+    ///
+    /// # Arguments
+    ///
+    /// * `project` - The project key in Bitbucket.
+    /// * `repo` - The repository slug in Bitbucket.
+    ///
+    /// # Returns
+    ///
+    /// A `BitbucketPaginated<BitbucketPullRequest>` instance.
+    pub fn list_pull_requests(&self, project: &str, repo: &str) -> BitbucketPaginated<'_, BitbucketPullRequest> {
+        let pull_requests_path: String = BitbucketEndpoints::PullRequests.url()
+            .replace("{projectKey}", project)
+            .replace("{repositorySlug}", repo);
+
+        let query: HashMap<String, String> = HashMap::from([
+            (String::from("state"), String::from("ALL")),
+            (String::from("order"), String::from("NEWEST"))
+        ]);
+
+        BitbucketPaginated::new(self, pull_requests_path, Some(&query))
     }
 
     /// Fetches issues associated with a specific pull request in a Bitbucket project and repository.
@@ -668,4 +1120,408 @@ impl BitbucketClient {
 
         self.client.get::<Vec<BitbucketPullRequestIssue>>(&get_pull_request_issues_path, None).await
     }
+
+    /// Returns a `BitbucketPaginated<BitbucketProject>` instance for fetching the projects visible to the client.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use deployment_changelog::api::rest::Paginated;
+    ///
+    /// // Suppose you have a BitbucketClient named 'client'
+    /// for project in client.list_projects().all().await.unwrap() {
+    ///     println!("{}: {:?}", project.key, project.name);
+    /// }
+    /// ```
+    pub fn list_projects(&self) -> BitbucketPaginated<'_, BitbucketProject> {
+        BitbucketPaginated::new(self, BitbucketEndpoints::Projects.url().to_string(), None)
+    }
+
+    /// Returns a `BitbucketPaginated<BitbucketRepository>` instance for fetching the repositories of a Bitbucket project.
+    ///
+    /// # Arguments
+    ///
+    /// * `project` - The project key in Bitbucket.
+    ///
+    /// # Returns
+    ///
+    /// A `BitbucketPaginated<BitbucketRepository>` instance.
+    pub fn list_repos(&self, project: &str) -> BitbucketPaginated<'_, BitbucketRepository> {
+        let list_repos_path: String = BitbucketEndpoints::Repos.url()
+            .replace("{projectKey}", project);
+
+        BitbucketPaginated::new(self, list_repos_path, None)
+    }
+
+    /// Fetches the default branch of a repository in a Bitbucket project.
+    ///
+    /// # Arguments
+    ///
+    /// * `project` - The project key in Bitbucket.
+    /// * `repo` - The repository slug in Bitbucket.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the default `BitbucketBranch` or an error if the request fails.
+    pub async fn get_default_branch(&self, project: &str, repo: &str) -> Result<BitbucketBranch> {
+        let default_branch_path: String = BitbucketEndpoints::DefaultBranch.url()
+            .replace("{projectKey}", project)
+            .replace("{repositorySlug}", repo);
+
+        self.client.get::<BitbucketBranch>(&default_branch_path, None).await
+    }
+
+    /// Fetches a tag of a repository in a Bitbucket project.
+    ///
+    /// # Arguments
+    ///
+    /// * `project` - The project key in Bitbucket.
+    /// * `repo` - The repository slug in Bitbucket.
+    /// * `tag` - The name of the tag.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the `BitbucketTag` or an error if the request fails.
+    pub async fn get_tag(&self, project: &str, repo: &str, tag: &str) -> Result<BitbucketTag> {
+        let tag_path: String = BitbucketEndpoints::Tag.url()
+            .replace("{projectKey}", project)
+            .replace("{repositorySlug}", repo)
+            .replace("{name}", tag);
+
+        self.client.get::<BitbucketTag>(&tag_path, None).await
+    }
+
+    /// Fetches a commit of a repository in a Bitbucket project.
+    ///
+    /// # Arguments
+    ///
+    /// * `project` - The project key in Bitbucket.
+    /// * `repo` - The repository slug in Bitbucket.
+    /// * `commit` - The ID of the commit.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the `BitbucketCommit` or an error if the request fails.
+    pub async fn get_commit(&self, project: &str, repo: &str, commit: &str) -> Result<BitbucketCommit> {
+        let commit_path: String = BitbucketEndpoints::Commit.url()
+            .replace("{projectKey}", project)
+            .replace("{repositorySlug}", repo)
+            .replace("{commitId}", commit);
+
+        self.client.get::<BitbucketCommit>(&commit_path, None).await
+    }
+
+    /// Fetches the merge base of two commits of a repository in a Bitbucket project.
+    ///
+    /// # Arguments
+    ///
+    /// * `project` - The project key in Bitbucket.
+    /// * `repo` - The repository slug in Bitbucket.
+    /// * `commit` and `other_commit` - The IDs of the commits.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the `BitbucketCommit` of the merge base or an error if the request fails.
+    pub async fn get_merge_base(&self, project: &str, repo: &str, commit: &str, other_commit: &str) -> Result<BitbucketCommit> {
+        let merge_base_path: String = BitbucketEndpoints::MergeBase.url()
+            .replace("{projectKey}", project)
+            .replace("{repositorySlug}", repo)
+            .replace("{commitId}", commit)
+            .replace("{otherCommitId}", other_commit);
+
+        self.client.get::<BitbucketCommit>(&merge_base_path, None).await
+    }
+
+    /// Fetches a pull request of a repository in a Bitbucket project.
+    ///
+    /// # Arguments
+    ///
+    /// * `project` - The project key in Bitbucket.
+    /// * `repo` - The repository slug in Bitbucket.
+    /// * `pull_request_id` - The ID of the pull request.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the `BitbucketPullRequest` or an error if the request fails.
+    pub async fn get_pull_request(&self, project: &str, repo: &str, pull_request_id: u64) -> Result<BitbucketPullRequest> {
+        let pull_request_path: String = BitbucketEndpoints::PullRequest.url()
+            .replace("{projectKey}", project)
+            .replace("{repositorySlug}", repo)
+            .replace("{pullRequestId}", &pull_request_id.to_string());
+
+        self.client.get::<BitbucketPullRequest>(&pull_request_path, None).await
+    }
+
+    /// Returns a `BitbucketPaginated<BitbucketCommit>` instance for fetching the commits of a pull request.
+    ///
+    /// # Arguments
+    ///
+    /// * `project` - The project key in Bitbucket.
+    /// * `repo` - The repository slug in Bitbucket.
+    /// * `pull_request_id` - The ID of the pull request.
+    ///
+    /// # Returns
+    ///
+    /// A `BitbucketPaginated<BitbucketCommit>` instance.
+    pub fn get_pull_request_commits(&self, project: &str, repo: &str, pull_request_id: u64) -> BitbucketPaginated<'_, BitbucketCommit> {
+        let pull_request_commits_path: String = BitbucketEndpoints::PullRequestCommits.url()
+            .replace("{projectKey}", project)
+            .replace("{repositorySlug}", repo)
+            .replace("{pullRequestId}", &pull_request_id.to_string());
+
+        BitbucketPaginated::new(self, pull_request_commits_path, None)
+    }
+
+    /// Returns a `BitbucketPaginated<BitbucketChange>` instance for fetching the files changed by a pull request.
+    ///
+    /// # Arguments
+    ///
+    /// * `project` - The project key in Bitbucket.
+    /// * `repo` - The repository slug in Bitbucket.
+    /// * `pull_request_id` - The ID of the pull request.
+    ///
+    /// # Returns
+    ///
+    /// A `BitbucketPaginated<BitbucketChange>` instance.
+    pub fn get_pull_request_changes(&self, project: &str, repo: &str, pull_request_id: u64) -> BitbucketPaginated<'_, BitbucketChange> {
+        let pull_request_changes_path: String = BitbucketEndpoints::PullRequestChanges.url()
+            .replace("{projectKey}", project)
+            .replace("{repositorySlug}", repo)
+            .replace("{pullRequestId}", &pull_request_id.to_string());
+
+        BitbucketPaginated::new(self, pull_request_changes_path, None)
+    }
+
+    /// Posts a comment on a pull request of a repository in a Bitbucket project, such as a note telling its author
+    /// when the change reaches production.
+    ///
+    /// # Arguments
+    ///
+    /// * `project` - The project key in Bitbucket.
+    /// * `repo` - The repository slug in Bitbucket.
+    /// * `pull_request_id` - The ID of the pull request.
+    /// * `text` - The Markdown text of the comment.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the posted `BitbucketComment` or an error if the request fails.
+    pub async fn add_pr_comment(&self, project: &str, repo: &str, pull_request_id: u64, text: &str) -> Result<BitbucketComment> {
+        let comments_path: String = BitbucketEndpoints::PullRequestComments.url()
+            .replace("{projectKey}", project)
+            .replace("{repositorySlug}", repo)
+            .replace("{pullRequestId}", &pull_request_id.to_string());
+
+        self.client.post_json::<BitbucketComment, BitbucketCommentBody>(&comments_path, &BitbucketCommentBody { text })
+            .await
+            .with_context(|| format!("Error commenting on pull request {pull_request_id} of {project}/{repo}"))
+    }
+
+    /// Returns a `BitbucketPaginated<BitbucketCommit>` instance for fetching the commits reachable from a branch of
+    /// a specified Bitbucket project and repository, most recent first.
+    ///
+    /// # Arguments
+    ///
+    /// * `project` - The project key in Bitbucket.
+    /// * `repo` - The repository slug in Bitbucket.
+    /// * `branch` - The branch, tag or commit ID to list the commits from, or `None` for the default branch.
+    ///
+    /// # Returns
+    ///
+    /// A `BitbucketPaginated<BitbucketCommit>` instance.
+    pub fn list_commits(&self, project: &str, repo: &str, branch: Option<&str>) -> BitbucketPaginated<'_, BitbucketCommit> {
+        let commits_path: String = BitbucketEndpoints::Commits.url()
+            .replace("{projectKey}", project)
+            .replace("{repositorySlug}", repo);
+
+        let query: HashMap<String, String> = branch.into_iter()
+            .map(|branch| (String::from("until"), branch.to_string()))
+            .collect();
+
+        BitbucketPaginated::new(self, commits_path, Some(&query))
+    }
+
+    /// Resolves a branch, a tag or an abbreviated commit ID of a repository in a Bitbucket project to the full ID
+    /// of the commit it points to.
+    ///
+    /// # Arguments
+    ///
+    /// * `project` - The project key in Bitbucket.
+    /// * `repo` - The repository slug in Bitbucket.
+    /// * `reference` - The branch, tag or commit ID to resolve.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the full commit ID, `None` if the reference doesn't exist, or an error if the request
+    /// fails.
+    pub async fn resolve_ref(&self, project: &str, repo: &str, reference: &str) -> Result<Option<String>> {
+        let commits_path: String = BitbucketEndpoints::Commits.url()
+            .replace("{projectKey}", project)
+            .replace("{repositorySlug}", repo);
+
+        let query: HashMap<String, String> = HashMap::from([
+            (String::from("until"), reference.to_string()),
+            (String::from("limit"), String::from("1"))
+        ]);
+
+        match self.client.get::<BitbucketPage<BitbucketCommit>>(&commits_path, Some(&query)).await {
+            Ok(commit_page) => Ok(commit_page.values.into_iter().next().map(|commit| commit.id)),
+            Err(error) if is_not_found(&error) => Ok(None),
+            Err(error) => Err(error)
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl ScmProvider for BitbucketClient {
+    fn base_url(&self) -> &Url {
+        &self.client.base_url
+    }
+
+    async fn commits_between(&self, project: &str, repo: &str, start_commit: &str, end_commit: &str) -> Result<Vec<BitbucketCommit>> {
+        self.compare_commits(project, repo, start_commit, end_commit)
+            .all()
+            .await
+    }
+
+    async fn changed_files(&self, project: &str, repo: &str, start_commit: &str, end_commit: &str) -> Result<Vec<String>> {
+        Ok(self.compare_changes(project, repo, start_commit, end_commit)
+            .all()
+            .await?
+            .into_iter()
+            .map(|change| change.path.to_string)
+            .collect())
+    }
+
+    async fn changed_lines(&self, project: &str, repo: &str, start_commit: &str, end_commit: &str) -> Result<Vec<DiffLine>> {
+        let mut lines: Vec<DiffLine> = Vec::new();
+
+        self.compare_diff(project, repo, start_commit, end_commit)
+            .await?
+            .diffs
+            .into_iter()
+            .for_each(|diff| {
+                let path = match diff.destination.or(diff.source) {
+                    Some(path) => path.to_string,
+                    None => return
+                };
+
+                diff.hunks.into_iter()
+                    .flat_map(|hunk| hunk.segments)
+                    .filter(|segment| segment.segment_type != "CONTEXT")
+                    .for_each(|segment| {
+                        let added = segment.segment_type == "ADDED";
+
+                        segment.lines.into_iter()
+                            .for_each(|diff_line| lines.push(DiffLine {
+                                path: path.clone(),
+                                added,
+                                line: diff_line.line
+                            }));
+                    });
+            });
+
+        Ok(lines)
+    }
+
+    async fn resolve_tag(&self, project: &str, repo: &str, tag: &str) -> Result<String> {
+        Ok(self.get_tag(project, repo, tag)
+            .await?
+            .latest_commit)
+    }
+
+    async fn resolve_ref(&self, project: &str, repo: &str, reference: &str) -> Result<Option<String>> {
+        BitbucketClient::resolve_ref(self, project, repo, reference)
+            .await
+    }
+
+    async fn merge_base(&self, project: &str, repo: &str, first_commit: &str, second_commit: &str) -> Result<String> {
+        Ok(self.get_merge_base(project, repo, first_commit, second_commit)
+            .await
+            .with_context(|| format!("Error computing the merge base of {first_commit} and {second_commit} in {project}/{repo}"))?
+            .id)
+    }
+
+    async fn last_commit_before(&self, project: &str, repo: &str, branch: Option<&str>, time: &DateTime<Utc>) -> Result<Option<String>> {
+        let mut commits = self.list_commits(project, repo, branch);
+
+        while !commits.is_last() {
+            if let Some(commit) = commits.next().await?.into_iter().find(|commit| commit.author_timestamp < *time) {
+                return Ok(Some(commit.id));
+            }
+        }
+
+        Ok(None)
+    }
+
+    async fn pull_requests_updated_since(&self, project: &str, repo: &str, time: &DateTime<Utc>) -> Result<Vec<BitbucketPullRequest>> {
+        let mut pull_requests = self.list_pull_requests(project, repo);
+        let mut updated_pull_requests: Vec<BitbucketPullRequest> = Vec::new();
+
+        while !pull_requests.is_last() {
+            for pull_request in pull_requests.next().await? {
+                if pull_request.updated_date < *time {
+                    return Ok(updated_pull_requests);
+                }
+
+                updated_pull_requests.push(pull_request);
+            }
+        }
+
+        Ok(updated_pull_requests)
+    }
+
+    async fn pull_requests_for_commit(&self, project: &str, repo: &str, commit: &str) -> Result<Vec<BitbucketPullRequest>> {
+        self.get_pull_requests(project, repo, commit)
+            .all()
+            .await
+    }
+
+    async fn pull_request(&self, project: &str, repo: &str, pull_request_id: u64) -> Result<BitbucketPullRequest> {
+        self.get_pull_request(project, repo, pull_request_id)
+            .await
+    }
+
+    async fn pull_request_size(&self, project: &str, repo: &str, pull_request_id: u64) -> Result<BitbucketPullRequestSize> {
+        let commits = self.get_pull_request_commits(project, repo, pull_request_id)
+            .all()
+            .await?;
+
+        let changes = self.get_pull_request_changes(project, repo, pull_request_id)
+            .all()
+            .await?;
+
+        Ok(BitbucketPullRequestSize {
+            commit_count: commits.len(),
+            file_count: Some(changes.len())
+        })
+    }
+
+    async fn comment_on_pull_request(&self, project: &str, repo: &str, pull_request_id: u64, text: &str) -> Result<()> {
+        self.add_pr_comment(project, repo, pull_request_id, text)
+            .await
+            .map(|_| ())
+    }
+
+    async fn issues_for_pull_request(&self, project: &str, repo: &str, pull_request_id: u64) -> Result<Vec<BitbucketPullRequestIssue>> {
+        self.get_pull_request_issues(project, repo, pull_request_id)
+            .await
+    }
+
+    async fn list_projects(&self) -> Result<Vec<String>> {
+        Ok(BitbucketClient::list_projects(self)
+            .all()
+            .await?
+            .into_iter()
+            .map(|project| project.key)
+            .collect())
+    }
+
+    async fn list_repos(&self, project: &str) -> Result<Vec<String>> {
+        Ok(BitbucketClient::list_repos(self, project)
+            .all()
+            .await?
+            .into_iter()
+            .map(|repository| repository.slug)
+            .collect())
+    }
 }
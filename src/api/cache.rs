@@ -0,0 +1,245 @@
+//! The `deployment_changelog::api::cache` module provides `HttpCache`, a persistent cache of the responses to
+//! the GET requests of a `RestClient`, so that generating changelogs for overlapping ranges doesn't fetch the same
+//! commits, pull requests and issues again.
+//!
+//! Responses are kept in two tiers: a bounded in-memory LRU cache for the current process, and a content-addressed
+//! disk cache shared between runs. Entries of both tiers expire after the cache's time to live, since pull requests
+//! and issues can change after they were cached.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use std::{sync::Arc, time::Duration};
+//! use deployment_changelog::api::{cache::HttpCache, jira::JiraClient, rest::RestClient};
+//!
+//! let cache = Arc::new(HttpCache::new("/tmp/deployment-changelog", 10_000, Duration::from_secs(3600)));
+//!
+//! let mut rest_client_builder = RestClient::builder("https://jira.example.com").unwrap();
+//! rest_client_builder.cache = Some(cache.clone());
+//!
+//! let jira_client = JiraClient::from_client(rest_client_builder.build().unwrap());
+//! println!("{}", cache.stats().unwrap());
+//! ```
+use std::{collections::BTreeMap, fmt::Display, fs, io::Write, path::PathBuf, sync::Arc, time::{Duration, SystemTime, UNIX_EPOCH}};
+
+use anyhow::{Context, Result};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use serde::{Deserialize, Serialize};
+
+use super::secret::redact_url;
+
+/// The headers and body of a successful GET response.
+#[derive(Debug)]
+pub(crate) struct CachedResponse {
+    pub(crate) headers: HeaderMap,
+    pub(crate) body: Vec<u8>
+}
+
+/// The metadata stored alongside a response body in the disk cache.
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct CachedResponseMetadata {
+    headers: BTreeMap<String, String>
+}
+
+/// The `CacheStats` struct summarizes the contents of the disk tier of an `HttpCache`. It contains the following fields:
+///
+/// - `entries`: The number of cached responses.
+/// - `expired_entries`: The number of cached responses older than the time to live, which are no longer used.
+/// - `size_bytes`: The total size of the cached response bodies, in bytes.
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheStats {
+    pub entries: usize,
+    pub expired_entries: usize,
+    pub size_bytes: usize
+}
+
+impl Display for CacheStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match serde_json::to_string_pretty(&self) {
+            Ok(json) => write!(f, "{json}"),
+            Err(error) => panic!("Error serializing cache stats: {error}")
+        }
+    }
+}
+
+/// The `HttpCache` struct caches the responses to GET requests in memory and on disk, keyed by request URL within
+/// the scope of the client that made them, its cache namespace and the fingerprint of its credentials, so that
+/// responses fetched from one server or for one user are never returned to another.
+///
+/// Failing to read from or write to the disk cache is logged and otherwise ignored, so that a broken cache
+/// never fails a changelog.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+/// use deployment_changelog::api::cache::HttpCache;
+///
+/// let cache = HttpCache::new("/tmp/deployment-changelog", 10_000, Duration::from_secs(3600));
+/// cache.clear().unwrap();
+/// ```
+#[derive(Debug)]
+pub struct HttpCache {
+    directory: PathBuf,
+    time_to_live: Duration,
+    memory: moka::sync::Cache<String, Arc<CachedResponse>>
+}
+
+impl HttpCache {
+    /// Creates a new `HttpCache`.
+    ///
+    /// # Arguments
+    ///
+    /// * `directory` - The directory of the disk cache, created when the first response is cached.
+    /// * `memory_capacity` - The maximum number of responses kept in memory.
+    /// * `time_to_live` - How long a cached response is used for.
+    pub fn new(directory: impl Into<PathBuf>, memory_capacity: u64, time_to_live: Duration) -> Self {
+        Self {
+            directory: directory.into(),
+            time_to_live,
+            memory: moka::sync::Cache::builder()
+                .max_capacity(memory_capacity)
+                .time_to_live(time_to_live)
+                .build()
+        }
+    }
+
+    /// Returns the directory of the disk cache.
+    pub fn directory(&self) -> &PathBuf {
+        &self.directory
+    }
+
+    /// Returns the cached response to a GET request of the given URL within a scope, if there is one that hasn't
+    /// expired.
+    pub(crate) fn get(&self, scope: &str, url: &str) -> Option<Arc<CachedResponse>> {
+        let key = cache_key(scope, url);
+
+        if let Some(response) = self.memory.get(&key) {
+            return Some(response);
+        }
+
+        match self.read(&key) {
+            Ok(Some(response)) => {
+                let response = Arc::new(response);
+                self.memory.insert(key, response.clone());
+
+                Some(response)
+            },
+            Ok(None) => None,
+            Err(error) => {
+                log::warn!("Error reading the cached response to {}: {error:#}", redact_url(url));
+                None
+            }
+        }
+    }
+
+    /// Caches the response to a GET request of the given URL within a scope.
+    pub(crate) fn insert(&self, scope: &str, url: &str, response: Arc<CachedResponse>) {
+        let key = cache_key(scope, url);
+
+        if let Err(error) = self.write(&key, &response) {
+            log::warn!("Error caching the response to {}: {error:#}", redact_url(url));
+        }
+
+        self.memory.insert(key, response);
+    }
+
+    /// Summarizes the contents of the disk cache.
+    pub fn stats(&self) -> Result<CacheStats> {
+        if self.is_empty() {
+            return Ok(CacheStats::default());
+        }
+
+        cacache::list_sync(&self.directory)
+            .try_fold(CacheStats::default(), |mut stats, entry| {
+                let entry = entry
+                    .with_context(|| format!("Error listing the cache entries in {}", self.directory.display()))?;
+
+                stats.entries += 1;
+                stats.size_bytes += entry.size;
+
+                if self.is_expired(entry.time) {
+                    stats.expired_entries += 1;
+                }
+
+                Ok(stats)
+            })
+    }
+
+    /// Removes every cached response, in memory and on disk.
+    pub fn clear(&self) -> Result<()> {
+        self.memory.invalidate_all();
+
+        if self.is_empty() {
+            return Ok(());
+        }
+
+        cacache::clear_sync(&self.directory)
+            .with_context(|| format!("Error clearing the cache in {}", self.directory.display()))
+    }
+
+    fn read(&self, key: &str) -> Result<Option<CachedResponse>> {
+        let entry = match cacache::metadata_sync(&self.directory, key)? {
+            Some(entry) if !self.is_expired(entry.time) => entry,
+            _ => return Ok(None)
+        };
+
+        let metadata: CachedResponseMetadata = serde_json::from_value(entry.metadata)
+            .with_context(|| "Error deserializing the cached response headers")?;
+
+        let mut headers = HeaderMap::with_capacity(metadata.headers.len());
+        metadata.headers.into_iter()
+            .try_for_each(|(name, value)| -> Result<()> {
+                headers.insert(HeaderName::try_from(name)?, HeaderValue::try_from(value)?);
+                Ok(())
+            })?;
+
+        let body = cacache::read_hash_sync(&self.directory, &entry.integrity)?;
+
+        Ok(Some(CachedResponse {
+            headers,
+            body
+        }))
+    }
+
+    fn write(&self, key: &str, response: &CachedResponse) -> Result<()> {
+        // Headers that aren't valid strings can't be stored in the JSON metadata, and are not needed by any client.
+        let metadata = CachedResponseMetadata {
+            headers: response.headers.iter()
+                .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+                .collect()
+        };
+
+        let mut writer = cacache::WriteOpts::new()
+            .metadata(serde_json::to_value(metadata)?)
+            .size(response.body.len())
+            .open_sync(&self.directory, key)?;
+
+        writer.write_all(&response.body)?;
+        writer.commit()?;
+
+        Ok(())
+    }
+
+    /// Returns whether nothing was ever cached in the directory, or it was cleared.
+    fn is_empty(&self) -> bool {
+        fs::read_dir(&self.directory)
+            .map(|mut entries| entries.next().is_none())
+            .unwrap_or(true)
+    }
+
+    fn is_expired(&self, written_at_millis: u128) -> bool {
+        let now_millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|now| now.as_millis())
+            .unwrap_or_default();
+
+        now_millis.saturating_sub(written_at_millis) > self.time_to_live.as_millis()
+    }
+}
+
+/// Returns the key of the response to a GET request of the given URL within a scope.
+fn cache_key(scope: &str, url: &str) -> String {
+    format!("{scope} {url}")
+}
@@ -0,0 +1,133 @@
+//! The `deployment_changelog::api::confluence` module provides a client for the Confluence REST API, used to
+//! publish rendered changelogs as attachments of a Confluence page, such as a release page.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use deployment_changelog::api::confluence::ConfluenceClient;
+//!
+//! let confluence_client = ConfluenceClient::new("https://confluence.example.com").unwrap();
+//! let attachments = confluence_client.add_attachment("123456", "changelog.md", "text/markdown", b"# Changelog".to_vec())
+//!     .await
+//!     .unwrap();
+//!
+//! println!("Attached {}", attachments[0].title);
+//! ```
+use std::fmt::Display;
+
+use anyhow::{Context, Result};
+use reqwest::{header::{HeaderMap, HeaderValue}, multipart::{Form, Part}};
+use serde::{Deserialize, Serialize};
+
+use super::rest::{Credentials, RestClient};
+
+/// The header Confluence requires on attachment uploads, to disable its XSRF check.
+const ATLASSIAN_TOKEN_HEADER: &str = "X-Atlassian-Token";
+
+enum ConfluenceEndpoints {
+    Attachments
+}
+
+impl ConfluenceEndpoints {
+    fn url(&self) -> &'static str {
+        match self {
+            ConfluenceEndpoints::Attachments => "rest/api/content/{contentId}/child/attachment"
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct ConfluenceResults<T> {
+    results: Vec<T>
+}
+
+/// The `ConfluenceAttachment` struct represents a file attached to a Confluence page.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct ConfluenceAttachment {
+    pub id: String,
+    pub title: String
+}
+
+impl Display for ConfluenceAttachment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match serde_json::to_string_pretty(&self) {
+            Ok(json) => write!(f, "{json}"),
+            Err(error) => panic!("Error serializing Confluence attachment: {error}")
+        }
+    }
+}
+
+/// The `ConfluenceClient` struct provides access to the pages of a Confluence server.
+///
+/// # Example
+///
+/// ```rust
+/// use deployment_changelog::api::confluence::ConfluenceClient;
+///
+/// let confluence_client = ConfluenceClient::new("https://confluence.example.com").unwrap();
+/// ```
+#[derive(Debug)]
+pub struct ConfluenceClient {
+    client: RestClient
+}
+
+impl ConfluenceClient {
+    /// Creates a new `ConfluenceClient` with the given base URL.
+    ///
+    /// # Arguments
+    ///
+    /// * `base_url` - The base URL of the Confluence server.
+    pub fn new(base_url: &str) -> Result<Self> {
+        Ok(Self {
+            client: RestClient::new(base_url)?
+        })
+    }
+
+    /// Creates a new `ConfluenceClient` with the given base URL, authenticating with the given credentials.
+    ///
+    /// # Arguments
+    ///
+    /// * `base_url` - The base URL of the Confluence server.
+    /// * `credentials` - The `Credentials` of the Confluence server.
+    pub fn with_credentials(base_url: &str, credentials: Credentials) -> Result<Self> {
+        Ok(Self {
+            client: RestClient::with_credentials(base_url, credentials)?
+        })
+    }
+
+    /// Creates a new `ConfluenceClient` from an existing `RestClient`.
+    pub fn from_client(client: RestClient) -> Self {
+        Self {
+            client
+        }
+    }
+
+    /// Attaches a file to a Confluence page.
+    ///
+    /// # Arguments
+    ///
+    /// * `page_id` - The ID of the page to attach the file to.
+    /// * `file_name` - The name of the attached file, which must not already be attached to the page.
+    /// * `mime_type` - The MIME type of the file.
+    /// * `contents` - The contents of the file.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the created `ConfluenceAttachment`s or an error if the upload fails.
+    pub async fn add_attachment(&self, page_id: &str, file_name: &str, mime_type: &str, contents: Vec<u8>) -> Result<Vec<ConfluenceAttachment>> {
+        let attachments_path: String = ConfluenceEndpoints::Attachments.url()
+            .replace("{contentId}", page_id);
+
+        let part = Part::bytes(contents)
+            .file_name(file_name.to_string())
+            .mime_str(mime_type)?;
+
+        let mut headers = HeaderMap::with_capacity(1);
+        headers.insert(ATLASSIAN_TOKEN_HEADER, HeaderValue::from_static("no-check"));
+
+        Ok(self.client.post_multipart::<ConfluenceResults<ConfluenceAttachment>>(&attachments_path, Form::new().part("file", part), headers)
+            .await
+            .with_context(|| format!("Error attaching {file_name} to Confluence page {page_id}"))?
+            .results)
+    }
+}
@@ -0,0 +1,211 @@
+//! The `deployment_changelog::api::fault` module injects faults into the requests of every `RestClient`, such as
+//! random latency and error responses, to check how changelog generation copes with slow and failing servers:
+//! whether requests are retried within their budget, and whether the changelog is still generated from what
+//! could be fetched. It is only built with the `fault-injection` feature.
+//!
+//! Faults are drawn from a seed, the URL of the request and the number of times that URL was requested before,
+//! so that a run with the same seed injects the same faults into the same requests, whatever order the
+//! concurrent requests are sent in.
+//!
+//! Like progress reporting, fault injection is global: no fault is injected until an injector is installed with
+//! `set_injector`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use deployment_changelog::api::fault::{set_injector, FaultInjector};
+//!
+//! set_injector(FaultInjector::chaos(42)).unwrap();
+//!
+//! // Every request now waits up to 500 ms, and one in five fails with 503 Service Unavailable.
+//! ```
+use std::{collections::HashMap, str::FromStr, sync::{Mutex, OnceLock}, time::Duration};
+
+use anyhow::{anyhow, bail, Context, Result};
+use reqwest::{StatusCode, Url};
+
+use super::secret::redact_url;
+
+static INJECTOR: OnceLock<FaultInjector> = OnceLock::new();
+
+/// The `FaultRule` struct describes the faults injected into the requests to an endpoint. It contains the
+/// following fields:
+///
+/// - `endpoint`: The part of the URL path the rule applies to, such as `/rest/api/2/issue`, or an empty string
+///   for every endpoint.
+/// - `error_rate`: The probability, between 0 and 1, of a request failing with `status`.
+/// - `status`: The status of the injected error responses.
+/// - `max_latency`: The latency added to every request is drawn between none and this one.
+///
+/// A rule can be parsed from `<endpoint>=<error rate>[,<max latency in milliseconds>]`, such as
+/// `/rest/api/2/issue=0.5,2000`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FaultRule {
+    pub endpoint: String,
+    pub error_rate: f64,
+    pub status: StatusCode,
+    pub max_latency: Duration
+}
+
+impl FromStr for FaultRule {
+    type Err = anyhow::Error;
+
+    fn from_str(rule: &str) -> Result<Self> {
+        let (endpoint, faults) = rule.split_once('=')
+            .ok_or_else(|| anyhow!("Expected <endpoint>=<error rate>[,<max latency in ms>], got '{rule}'"))?;
+
+        let (error_rate, max_latency) = match faults.split_once(',') {
+            Some((error_rate, max_latency)) => (error_rate, Some(max_latency)),
+            None => (faults, None)
+        };
+
+        let error_rate: f64 = error_rate.trim().parse()
+            .with_context(|| format!("Invalid error rate '{error_rate}'"))?;
+
+        if !(0.0..=1.0).contains(&error_rate) {
+            bail!("The error rate must be between 0 and 1, got {error_rate}");
+        }
+
+        let max_latency = match max_latency {
+            Some(max_latency) => Duration::from_millis(max_latency.trim().parse()
+                .with_context(|| format!("Invalid max latency '{max_latency}'"))?),
+            None => Duration::ZERO
+        };
+
+        Ok(Self {
+            endpoint: endpoint.to_string(),
+            error_rate,
+            status: StatusCode::SERVICE_UNAVAILABLE,
+            max_latency
+        })
+    }
+}
+
+/// The `FaultInjector` struct injects the faults of its `FaultRule`s into requests, drawn from its seed. The
+/// first rule whose endpoint is part of the path of a request applies to it, and requests that no rule applies
+/// to are sent as is.
+#[derive(Debug)]
+pub struct FaultInjector {
+    seed: u64,
+    rules: Vec<FaultRule>,
+    attempts: Mutex<HashMap<String, u64>>
+}
+
+impl FaultInjector {
+    /// Creates a new `FaultInjector` with the given seed and rules.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The seed the faults are drawn from.
+    /// * `rules` - The `FaultRule`s of the endpoints to inject faults into.
+    ///
+    /// # Returns
+    ///
+    /// A new `FaultInjector` instance.
+    pub fn new(seed: u64, rules: Vec<FaultRule>) -> Self {
+        Self {
+            seed,
+            rules,
+            attempts: Mutex::new(HashMap::new())
+        }
+    }
+
+    /// Creates the `FaultInjector` of the `chaos` profile, which adds up to 500 ms of latency to every request
+    /// and fails one in five of them with 503 Service Unavailable, a rate the default retry policy should
+    /// recover from.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The seed the faults are drawn from.
+    ///
+    /// # Returns
+    ///
+    /// A new `FaultInjector` instance.
+    pub fn chaos(seed: u64) -> Self {
+        Self::new(seed, vec![FaultRule {
+            endpoint: String::new(),
+            error_rate: 0.2,
+            status: StatusCode::SERVICE_UNAVAILABLE,
+            max_latency: Duration::from_millis(500)
+        }])
+    }
+
+    /// Draws the latency and the error status, if any, of the next request to the given URL.
+    fn draw(&self, url: &Url) -> Option<(Duration, Option<StatusCode>)> {
+        let rule = self.rules.iter()
+            .find(|rule| url.path().contains(&rule.endpoint))?;
+
+        let key = request_key(url);
+        let attempt = {
+            let mut attempts = self.attempts.lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+            let attempt = attempts.entry(key.clone()).or_default();
+            *attempt += 1;
+            *attempt
+        };
+
+        let draw = splitmix64(self.seed ^ fnv1a(&key) ^ attempt.wrapping_mul(0x9E37_79B9_7F4A_7C15));
+        let latency = rule.max_latency.mul_f64(unit(draw));
+        let status = (unit(splitmix64(draw)) < rule.error_rate).then_some(rule.status);
+
+        Some((latency, status))
+    }
+}
+
+/// Installs the global fault injector. It can only be installed once.
+pub fn set_injector(injector: FaultInjector) -> Result<()> {
+    INJECTOR.set(injector)
+        .map_err(|_| anyhow!("A fault injector is already installed"))
+}
+
+/// Waits for the latency injected into a request to the given URL, and returns the status of the error response
+/// to fail it with instead of sending it, if any.
+pub(crate) async fn inject(url: &Url) -> Option<StatusCode> {
+    let (latency, status) = INJECTOR.get()?.draw(url)?;
+
+    if !latency.is_zero() {
+        log::debug!("Injecting {} ms of latency into {}", latency.as_millis(), redact_url(url.as_str()));
+        tokio::time::sleep(latency).await;
+    }
+
+    if let Some(status) = status {
+        log::debug!("Injecting {status} into {}", redact_url(url.as_str()));
+    }
+
+    status
+}
+
+/// Returns the URL with its query parameters sorted, since some clients build them from hash maps, whose order
+/// changes with every run.
+fn request_key(url: &Url) -> String {
+    let mut query: Vec<(String, String)> = url.query_pairs()
+        .map(|(name, value)| (name.into_owned(), value.into_owned()))
+        .collect();
+    query.sort();
+
+    let mut key = url.clone();
+    key.query_pairs_mut()
+        .clear()
+        .extend_pairs(query);
+
+    key.to_string()
+}
+
+/// Maps a draw to a number between 0 (inclusive) and 1 (exclusive).
+fn unit(draw: u64) -> f64 {
+    (draw >> 11) as f64 / (1u64 << 53) as f64
+}
+
+fn splitmix64(state: u64) -> u64 {
+    let mut z = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Hashes a string with FNV-1a, which unlike the hasher of the standard library is stable across releases, so
+/// that a seed injects the same faults with every build.
+fn fnv1a(value: &str) -> u64 {
+    value.bytes().fold(0xCBF2_9CE4_8422_2325, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01B3))
+}
@@ -0,0 +1,703 @@
+//! The `deployment_changelog::api::gerrit` module provides a high-level API for interacting with the
+//! Gerrit REST API, so Gerrit-based teams can generate changelogs with the same pipeline as Bitbucket users.
+//!
+//! Gerrit has no pull requests; the unit of review is a change, identified by its `Change-Id`. The
+//! `GerritClient` looks up the changes a commit belongs to, resolves the issues linked to a change from its
+//! tracking IDs and its topic, and lists the commits in a range through the Gitiles plugin bundled with Gerrit.
+//!
+//! Gerrit project names are hierarchical (e.g. `platform/payments`). When used as an `ScmProvider`, the
+//! changelog's `project` and `repo` are joined with a `/` to form the Gerrit project name, and an empty
+//! `project` refers to a top-level Gerrit project named after `repo`.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use deployment_changelog::api::gerrit::GerritClient;
+//!
+//! let gerrit_client = GerritClient::new("https://gerrit.example.com")
+//!     .unwrap();
+//!
+//! let changes = gerrit_client.get_changes_for_commit("platform/payments", "abcdef123456").await.unwrap();
+//!
+//! for change in changes {
+//!     println!("{} ({}): {}", change.number, change.change_id, change.subject);
+//! }
+//! ```
+use std::{fmt::Display, collections::{BTreeSet, HashMap}};
+
+use anyhow::{Context, Result};
+use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use regex::Regex;
+use reqwest::Url;
+use serde::{Deserialize, Serialize, de::{DeserializeOwned, IgnoredAny}};
+
+use super::{rest::{Credentials, RestClient, Paginated}, scm::{ScmProvider, DiffLine, parse_unified_diff}, bitbucket::{BitbucketAuthor, BitbucketCommit, BitbucketPullRequest, BitbucketPullRequestAuthor, BitbucketPullRequestIssue, BitbucketPullRequestSize}};
+
+/// Gerrit prefixes every JSON response with this string to prevent cross-site script inclusion.
+const XSSI_PREFIX: &str = ")]}'";
+
+/// The length of the abbreviated commit IDs, matching the display IDs used by Bitbucket.
+const DISPLAY_ID_LENGTH: usize = 11;
+
+enum GerritEndpoints {
+    ChangesForCommit,
+    Change,
+    ChangesForTopic,
+    ChangeFiles,
+    Tag,
+    GitilesLog,
+    GitilesLogWithFiles,
+    GitilesDiff
+}
+
+impl GerritEndpoints {
+    fn url(&self) -> &'static str {
+        match self {
+            GerritEndpoints::ChangesForCommit => "changes/?q=commit:{commitId}+project:{project}&o=CURRENT_REVISION&o=CURRENT_COMMIT&o=DETAILED_ACCOUNTS&o=TRACKING_IDS",
+            GerritEndpoints::Change => "changes/{project}~{changeNumber}?o=CURRENT_REVISION&o=CURRENT_COMMIT&o=DETAILED_ACCOUNTS&o=TRACKING_IDS",
+            GerritEndpoints::ChangesForTopic => "changes/?q=topic:{topic}&o=DETAILED_ACCOUNTS&o=TRACKING_IDS",
+            GerritEndpoints::ChangeFiles => "changes/{project}~{changeNumber}/revisions/current/files",
+            GerritEndpoints::Tag => "projects/{project}/tags/{tag}",
+            GerritEndpoints::GitilesLog => "plugins/gitiles/{project}/+log/{to}..{from}?format=JSON",
+            GerritEndpoints::GitilesLogWithFiles => "plugins/gitiles/{project}/+log/{to}..{from}?format=JSON&name-status=1",
+            GerritEndpoints::GitilesDiff => "plugins/gitiles/{project}/+diff/{to}..{from}/?format=TEXT"
+        }
+    }
+}
+
+enum GerritOptions {
+    GitilesPageStart
+}
+
+impl GerritOptions {
+    fn option(&self) -> &'static str {
+        match self {
+            GerritOptions::GitilesPageStart => "s"
+        }
+    }
+}
+
+/// The `GerritChange` struct represents a change returned by the Gerrit REST API.
+///
+/// A change is Gerrit's equivalent of a pull request. It is identified by the `Change-Id` footer of its
+/// commits (`change_id`) and by a server-wide `number`, and can be grouped with related changes through a `topic`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use deployment_changelog::api::gerrit::GerritClient;
+///
+/// // Suppose you have a GerritClient named 'client'
+/// let change = client.get_change("platform/payments", 4242).await.unwrap();
+///
+/// println!("Change-Id: {}", change.change_id);
+/// println!("Subject: {}", change.subject);
+/// println!("Topic: {:?}", change.topic);
+/// println!("Tracking IDs: {:?}", change.tracking_ids);
+/// ```
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct GerritChange {
+    pub id: String,
+    pub project: String,
+    pub branch: String,
+    pub topic: Option<String>,
+    pub change_id: String,
+    pub subject: String,
+    pub status: String,
+    pub created: String,
+    pub updated: String,
+    pub submitted: Option<String>,
+
+    #[serde(rename = "_number")]
+    pub number: u64,
+
+    pub owner: GerritAccount,
+
+    #[serde(default)]
+    pub tracking_ids: Vec<GerritTrackingId>,
+
+    pub current_revision: Option<String>,
+
+    #[serde(default)]
+    pub revisions: HashMap<String, GerritRevision>
+}
+
+impl GerritChange {
+    /// Returns the full commit message of the change's current revision, if it was requested.
+    pub fn current_commit_message(&self) -> Option<&str> {
+        self.current_revision.as_ref()
+            .and_then(|revision| self.revisions.get(revision))
+            .and_then(|revision| revision.commit.as_ref())
+            .map(|commit| commit.message.as_str())
+    }
+
+    /// Returns the keys of the issues linked to the change, taken from its tracking IDs and from its
+    /// topic when the topic is an issue key itself.
+    pub fn issue_keys(&self) -> Vec<String> {
+        let issue_key_regex = Regex::new(r"^[A-Z][A-Z0-9_]+-[0-9]+$")
+            .expect("Error compiling issue key regex");
+
+        let mut issue_keys: Vec<String> = self.tracking_ids.iter()
+            .map(|tracking_id| tracking_id.id.clone())
+            .collect();
+
+        if let Some(topic) = &self.topic {
+            if issue_key_regex.is_match(topic) && !issue_keys.contains(topic) {
+                issue_keys.push(topic.clone());
+            }
+        }
+
+        issue_keys
+    }
+}
+
+impl Display for GerritChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match serde_json::to_string_pretty(&self) {
+            Ok(json) => write!(f, "{json}"),
+            Err(error) => panic!("Error serializing Gerrit change: {error}")
+        }
+    }
+}
+
+/// The `GerritAccount` struct represents a Gerrit user, such as the owner of a change.
+///
+/// The name, email and username are only populated when the account details were requested.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct GerritAccount {
+    #[serde(rename = "_account_id")]
+    pub account_id: u64,
+
+    pub name: Option<String>,
+    pub email: Option<String>,
+    pub username: Option<String>
+}
+
+impl Display for GerritAccount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match serde_json::to_string_pretty(&self) {
+            Ok(json) => write!(f, "{json}"),
+            Err(error) => panic!("Error serializing Gerrit account: {error}")
+        }
+    }
+}
+
+/// The `GerritTrackingId` struct represents an issue reference Gerrit extracted from a change's commit
+/// message, according to the tracking ID rules configured on the server.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct GerritTrackingId {
+    pub system: String,
+    pub id: String
+}
+
+/// The `GerritRevision` struct represents a patch set of a change.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct GerritRevision {
+    pub commit: Option<GerritRevisionCommit>
+}
+
+/// The `GerritRevisionCommit` struct represents the commit of a patch set.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct GerritRevisionCommit {
+    pub subject: String,
+    pub message: String
+}
+
+/// The `GerritTag` struct represents a tag of a Gerrit project. `revision` is the ID of the tag object for
+/// annotated tags, in which case `object` is the commit it points to.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct GerritTag {
+    #[serde(rename = "ref")]
+    pub tag_ref: String,
+    pub revision: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub object: Option<String>
+}
+
+impl GerritTag {
+    /// Returns the ID of the commit the tag points to.
+    pub fn commit(&self) -> &str {
+        self.object.as_deref().unwrap_or(&self.revision)
+    }
+}
+
+/// The `GitilesLog` struct represents a page of commits returned by the Gitiles log endpoint.
+///
+/// `next` holds the commit to continue from when more commits are available.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GitilesLog {
+    pub log: Vec<GitilesCommit>,
+    pub next: Option<String>
+}
+
+/// The `GitilesCommit` struct represents a single commit returned by the Gitiles log endpoint.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct GitilesCommit {
+    pub commit: String,
+    pub author: GitilesPerson,
+    pub committer: GitilesPerson,
+    pub message: String,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tree_diff: Vec<GitilesTreeDiff>
+}
+
+/// The `GitilesTreeDiff` struct represents a file changed by a Gitiles commit, listed when the log is requested
+/// with the files. The path of an added file's `old_path`, or a deleted file's `new_path`, is `/dev/null`.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct GitilesTreeDiff {
+    #[serde(rename = "type")]
+    pub change_type: String,
+    pub old_path: String,
+    pub new_path: String
+}
+
+/// The `GitilesPerson` struct represents the author or committer of a Gitiles commit.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct GitilesPerson {
+    pub name: String,
+    pub email: String,
+    pub time: String
+}
+
+/// The `GitilesLogPaginated` struct fetches the pages of a Gitiles log, following the `next` commit
+/// returned with each page until the whole range has been listed.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use deployment_changelog::api::gerrit::GerritClient;
+/// use deployment_changelog::api::rest::Paginated;
+///
+/// // Suppose you have a GerritClient named 'client'
+/// let mut log = client.get_log("platform/payments", "abcdef", "123456");
+/// let commits = log.all().await.unwrap();
+/// ```
+pub struct GitilesLogPaginated<'a> {
+    client: &'a GerritClient,
+    url: String,
+    next: Option<String>,
+    is_last_page: bool
+}
+
+#[async_trait::async_trait]
+impl Paginated<GitilesCommit> for GitilesLogPaginated<'_> {
+    async fn next(&mut self) -> Result<Vec<GitilesCommit>> {
+        let query = self.next.as_ref()
+            .map(|next| HashMap::from([(GerritOptions::GitilesPageStart.option().to_string(), next.clone())]));
+
+        let page = self.client.get::<GitilesLog>(&self.url, query.as_ref()).await?;
+
+        self.is_last_page = page.next.is_none();
+        self.next = page.next;
+
+        Ok(page.log)
+    }
+
+    fn is_last(&self) -> bool {
+        self.is_last_page
+    }
+}
+
+/// The `GerritClient` struct is a high-level API client for working with the Gerrit REST API.
+///
+/// It provides methods for looking up the changes a commit belongs to, fetching a change with its tracking
+/// IDs, listing the changes of a topic, and listing the commits of a range through Gitiles. It also
+/// implements `ScmProvider`, so it can be used in place of a `BitbucketClient` to generate a changelog.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use deployment_changelog::api::gerrit::GerritClient;
+///
+/// let client = GerritClient::new("https://gerrit.example.com").unwrap();
+/// let changes = client.get_changes_for_topic("ABC-123").await.unwrap();
+/// ```
+#[derive(Debug)]
+pub struct GerritClient {
+    client: RestClient
+}
+
+impl GerritClient {
+    /// Creates a new `GerritClient` instance given the base URL.
+    ///
+    /// Use the `/a/` prefixed base URL (e.g. `https://gerrit.example.com/a/`) for authenticated access.
+    ///
+    /// # Arguments
+    ///
+    /// * `base_url` - The base URL of the Gerrit server.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing a GerritClient instance or an error if the provided base URL is invalid.
+    pub fn new(base_url: &str) -> Result<Self> {
+        Ok(Self {
+            client: RestClient::new(base_url)?
+        })
+    }
+
+    /// Creates a new `GerritClient` instance given the base URL, authenticating with the given credentials.
+    ///
+    /// # Arguments
+    ///
+    /// * `base_url` - The `/a/` prefixed base URL of the Gerrit server.
+    /// * `credentials` - The `Credentials` of the Gerrit server, usually a username and HTTP password.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing a GerritClient instance or an error if the provided base URL is invalid.
+    pub fn with_credentials(base_url: &str, credentials: Credentials) -> Result<Self> {
+        Ok(Self {
+            client: RestClient::with_credentials(base_url, credentials)?
+        })
+    }
+
+    /// Constructs a GerritClient instance from a pre-initialized RestClient.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - An instance of RestClient.
+    pub fn from_client(client: RestClient) -> Self {
+        Self {
+            client
+        }
+    }
+
+    /// Fetches the changes of a Gerrit project that contain the given commit.
+    ///
+    /// # Arguments
+    ///
+    /// * `project` - The Gerrit project name.
+    /// * `commit` - The commit ID to fetch the changes for.
+    pub async fn get_changes_for_commit(&self, project: &str, commit: &str) -> Result<Vec<GerritChange>> {
+        let changes_path: String = GerritEndpoints::ChangesForCommit.url()
+            .replace("{commitId}", commit)
+            .replace("{project}", project);
+
+        self.get::<Vec<GerritChange>>(&changes_path, None).await
+    }
+
+    /// Fetches a single change of a Gerrit project by its number.
+    ///
+    /// # Arguments
+    ///
+    /// * `project` - The Gerrit project name.
+    /// * `change_number` - The number of the change.
+    pub async fn get_change(&self, project: &str, change_number: u64) -> Result<GerritChange> {
+        let change_path: String = GerritEndpoints::Change.url()
+            .replace("{project}", &project.replace('/', "%2F"))
+            .replace("{changeNumber}", &change_number.to_string());
+
+        self.get::<GerritChange>(&change_path, None).await
+    }
+
+    /// Fetches the paths of the files changed by the current revision of a change, without the `/COMMIT_MSG` and
+    /// `/MERGE_LIST` magic files.
+    ///
+    /// # Arguments
+    ///
+    /// * `project` - The Gerrit project name.
+    /// * `change_number` - The number of the change.
+    pub async fn get_change_files(&self, project: &str, change_number: u64) -> Result<Vec<String>> {
+        let change_files_path: String = GerritEndpoints::ChangeFiles.url()
+            .replace("{project}", &project.replace('/', "%2F"))
+            .replace("{changeNumber}", &change_number.to_string());
+
+        let files = self.get::<HashMap<String, IgnoredAny>>(&change_files_path, None).await?;
+
+        Ok(files.into_keys()
+            .filter(|path| !path.starts_with('/'))
+            .collect())
+    }
+
+    /// Fetches all changes, across projects, that share the given topic.
+    ///
+    /// # Arguments
+    ///
+    /// * `topic` - The topic to fetch the changes for.
+    pub async fn get_changes_for_topic(&self, topic: &str) -> Result<Vec<GerritChange>> {
+        let changes_path: String = GerritEndpoints::ChangesForTopic.url()
+            .replace("{topic}", topic);
+
+        self.get::<Vec<GerritChange>>(&changes_path, None).await
+    }
+
+    /// Fetches a tag of a Gerrit project.
+    ///
+    /// # Arguments
+    ///
+    /// * `project` - The Gerrit project name.
+    /// * `tag` - The name of the tag.
+    pub async fn get_tag(&self, project: &str, tag: &str) -> Result<GerritTag> {
+        let tag_path: String = GerritEndpoints::Tag.url()
+            .replace("{project}", &project.replace('/', "%2F"))
+            .replace("{tag}", tag);
+
+        self.get::<GerritTag>(&tag_path, None).await
+    }
+
+    /// Returns a `GitilesLogPaginated` for listing the commits reachable from `start_commit` but not
+    /// from `end_commit` in a Gerrit project.
+    ///
+    /// # Arguments
+    ///
+    /// * `project` - The Gerrit project name.
+    /// * `start_commit` - The most recent commit of the range.
+    /// * `end_commit` - The oldest commit of the range, which is excluded.
+    pub fn get_log(&self, project: &str, start_commit: &str, end_commit: &str) -> GitilesLogPaginated<'_> {
+        self.get_log_from(GerritEndpoints::GitilesLog, project, start_commit, end_commit)
+    }
+
+    /// Returns a `GitilesLogPaginated` for listing the commits reachable from `start_commit` but not
+    /// from `end_commit` in a Gerrit project, along with the files each commit changed in `tree_diff`.
+    ///
+    /// # Arguments
+    ///
+    /// * `project` - The Gerrit project name.
+    /// * `start_commit` - The most recent commit of the range.
+    /// * `end_commit` - The oldest commit of the range, which is excluded.
+    pub fn get_log_with_files(&self, project: &str, start_commit: &str, end_commit: &str) -> GitilesLogPaginated<'_> {
+        self.get_log_from(GerritEndpoints::GitilesLogWithFiles, project, start_commit, end_commit)
+    }
+
+    fn get_log_from(&self, endpoint: GerritEndpoints, project: &str, start_commit: &str, end_commit: &str) -> GitilesLogPaginated<'_> {
+        let log_path: String = endpoint.url()
+            .replace("{project}", project)
+            .replace("{from}", start_commit)
+            .replace("{to}", end_commit);
+
+        GitilesLogPaginated {
+            client: self,
+            url: log_path,
+            next: None,
+            is_last_page: false
+        }
+    }
+
+    /// Fetches the unified diff between `end_commit` and `start_commit` of a Gerrit project from Gitiles.
+    ///
+    /// # Arguments
+    ///
+    /// * `project` - The Gerrit project name.
+    /// * `start_commit` - The most recent commit of the range.
+    /// * `end_commit` - The oldest commit of the range.
+    pub async fn get_diff(&self, project: &str, start_commit: &str, end_commit: &str) -> Result<String> {
+        let diff_path: String = GerritEndpoints::GitilesDiff.url()
+            .replace("{project}", project)
+            .replace("{from}", start_commit)
+            .replace("{to}", end_commit);
+
+        // Gitiles returns plain text diffs encoded in base64.
+        let encoded_diff = self.client.get_text(&diff_path, None).await?;
+        let diff = BASE64.decode(encoded_diff.trim())
+            .with_context(|| format!("Error decoding the diff between {end_commit} and {start_commit}"))?;
+
+        Ok(String::from_utf8_lossy(&diff).into_owned())
+    }
+
+    /// Sends a GET request to Gerrit, strips the XSSI protection prefix from the response and
+    /// deserializes it to the generic type R.
+    async fn get<R: DeserializeOwned>(&self, url: &str, query: Option<&HashMap<String, String>>) -> Result<R> {
+        let body = self.client.get_text(url, query).await?;
+
+        serde_json::from_str::<R>(body.trim_start_matches(XSSI_PREFIX))
+            .with_context(|| format!("Error deserializing Gerrit response from {url}"))
+    }
+}
+
+#[async_trait::async_trait]
+impl ScmProvider for GerritClient {
+    fn base_url(&self) -> &Url {
+        &self.client.base_url
+    }
+
+    async fn commits_between(&self, project: &str, repo: &str, start_commit: &str, end_commit: &str) -> Result<Vec<BitbucketCommit>> {
+        let commits = self.get_log(&gerrit_project(project, repo), start_commit, end_commit)
+            .all()
+            .await?;
+
+        commits.into_iter()
+            .map(BitbucketCommit::try_from)
+            .collect()
+    }
+
+    async fn changed_files(&self, project: &str, repo: &str, start_commit: &str, end_commit: &str) -> Result<Vec<String>> {
+        let commits = self.get_log_with_files(&gerrit_project(project, repo), start_commit, end_commit)
+            .all()
+            .await?;
+
+        Ok(commits.into_iter()
+            .flat_map(|commit| commit.tree_diff)
+            .flat_map(|tree_diff| [tree_diff.old_path, tree_diff.new_path])
+            .filter(|path| path != "/dev/null")
+            .collect::<BTreeSet<String>>()
+            .into_iter()
+            .collect())
+    }
+
+    async fn changed_lines(&self, project: &str, repo: &str, start_commit: &str, end_commit: &str) -> Result<Vec<DiffLine>> {
+        let diff = self.get_diff(&gerrit_project(project, repo), start_commit, end_commit).await?;
+
+        Ok(parse_unified_diff(&diff))
+    }
+
+    async fn resolve_tag(&self, project: &str, repo: &str, tag: &str) -> Result<String> {
+        Ok(self.get_tag(&gerrit_project(project, repo), tag)
+            .await?
+            .commit()
+            .to_string())
+    }
+
+    async fn pull_requests_for_commit(&self, project: &str, repo: &str, commit: &str) -> Result<Vec<BitbucketPullRequest>> {
+        self.get_changes_for_commit(&gerrit_project(project, repo), commit)
+            .await?
+            .into_iter()
+            .map(BitbucketPullRequest::try_from)
+            .collect()
+    }
+
+    async fn pull_request(&self, project: &str, repo: &str, pull_request_id: u64) -> Result<BitbucketPullRequest> {
+        self.get_change(&gerrit_project(project, repo), pull_request_id)
+            .await?
+            .try_into()
+    }
+
+    async fn pull_request_size(&self, project: &str, repo: &str, pull_request_id: u64) -> Result<BitbucketPullRequestSize> {
+        let files = self.get_change_files(&gerrit_project(project, repo), pull_request_id).await?;
+
+        // A change is reviewed and submitted as a single commit.
+        Ok(BitbucketPullRequestSize {
+            commit_count: 1,
+            file_count: Some(files.len())
+        })
+    }
+
+    async fn issues_for_pull_request(&self, project: &str, repo: &str, pull_request_id: u64) -> Result<Vec<BitbucketPullRequestIssue>> {
+        let change = self.get_change(&gerrit_project(project, repo), pull_request_id).await?;
+
+        Ok(change.issue_keys()
+            .into_iter()
+            .map(|key| BitbucketPullRequestIssue {
+                key,
+                url: String::new()
+            })
+            .collect())
+    }
+}
+
+impl From<GitilesPerson> for BitbucketAuthor {
+    fn from(person: GitilesPerson) -> Self {
+        Self {
+            display_name: person.name.clone(),
+            name: person.name,
+            email_address: person.email
+        }
+    }
+}
+
+impl TryFrom<GitilesCommit> for BitbucketCommit {
+    type Error = anyhow::Error;
+
+    fn try_from(commit: GitilesCommit) -> Result<Self> {
+        let author_timestamp = parse_gitiles_timestamp(&commit.author.time)?;
+
+        Ok(Self {
+            display_id: commit.commit.chars().take(DISPLAY_ID_LENGTH).collect(),
+            id: commit.commit,
+            author: commit.author.into(),
+            author_timestamp,
+            committer: commit.committer.into(),
+            message: commit.message,
+            entry_id: None
+        })
+    }
+}
+
+impl From<GerritAccount> for BitbucketAuthor {
+    fn from(account: GerritAccount) -> Self {
+        let display_name = account.name.clone()
+            .or_else(|| account.username.clone())
+            .unwrap_or_else(|| account.account_id.to_string());
+
+        Self {
+            name: account.username.unwrap_or_else(|| display_name.clone()),
+            email_address: account.email.unwrap_or_default(),
+            display_name
+        }
+    }
+}
+
+impl TryFrom<GerritChange> for BitbucketPullRequest {
+    type Error = anyhow::Error;
+
+    fn try_from(change: GerritChange) -> Result<Self> {
+        let created_date = parse_gerrit_timestamp(&change.created)?;
+        let updated_date = parse_gerrit_timestamp(&change.updated)?;
+
+        let closed_date = match (change.status.as_str(), &change.submitted) {
+            ("NEW", _) => None,
+            (_, Some(submitted)) => Some(parse_gerrit_timestamp(submitted)?),
+            (_, None) => Some(updated_date)
+        };
+
+        let description = change.current_commit_message()
+            .unwrap_or_default()
+            .to_string();
+
+        Ok(Self {
+            id: change.number,
+            title: change.subject,
+            description,
+            open: change.status == "NEW",
+            state: Some(gerrit_pull_request_state(&change.status).to_string()),
+            author: BitbucketPullRequestAuthor {
+                user: change.owner.into(),
+                approved: false
+            },
+            created_date,
+            updated_date,
+            closed_date,
+            from_ref: None,
+            to_ref: None,
+            release_note: None,
+            size: None,
+            entry_id: None
+        })
+    }
+}
+
+/// Maps the status of a Gerrit change, `NEW`, `MERGED` or `ABANDONED`, to the state of a Bitbucket pull request.
+fn gerrit_pull_request_state(status: &str) -> &str {
+    match status {
+        "NEW" => "OPEN",
+        "ABANDONED" => "DECLINED",
+        status => status
+    }
+}
+
+/// Joins a changelog project and repository into a Gerrit project name.
+fn gerrit_project(project: &str, repo: &str) -> String {
+    if project.is_empty() {
+        repo.to_string()
+    } else {
+        format!("{project}/{repo}")
+    }
+}
+
+/// Parses a Gerrit timestamp, which is expressed in UTC as `2013-02-01 09:59:32.126000000`.
+fn parse_gerrit_timestamp(timestamp: &str) -> Result<DateTime<Utc>> {
+    let naive = NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%d %H:%M:%S%.f")
+        .with_context(|| format!("Error parsing Gerrit timestamp {timestamp}"))?;
+
+    Ok(Utc.from_utc_datetime(&naive))
+}
+
+/// Parses a Gitiles timestamp, which uses Git's default date format such as `Wed Feb 01 09:59:32 2013 +0100`.
+fn parse_gitiles_timestamp(timestamp: &str) -> Result<DateTime<Utc>> {
+    let date_time = DateTime::parse_from_str(timestamp, "%a %b %d %H:%M:%S %Y %z")
+        .with_context(|| format!("Error parsing Gitiles timestamp {timestamp}"))?;
+
+    Ok(date_time.with_timezone(&Utc))
+}
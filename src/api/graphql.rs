@@ -47,13 +47,625 @@
 //!
 //! For more detailed examples and further documentation, please refer to the individual struct and method
 //! documentation.
-use anyhow::{Context, Result};
+use std::{collections::{BTreeSet, HashMap, HashSet}, fmt::Display};
+
+use anyhow::{anyhow, bail, Context, Result};
 use graphql_client::{GraphQLQuery, QueryBody, Response};
+use graphql_parser::{query, schema};
+use reqwest::Url;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
-use super::rest::RestClient;
+use super::rest::{Credentials, RestClient};
 
 const GRAPHQL_ENDPOINT: &str = "graphql";
 
+/// The name of the root query type when the schema does not declare one.
+const DEFAULT_QUERY_TYPE: &str = "Query";
+
+/// A GraphQL query loaded at runtime, which takes the place of a query compiled into a client.
+///
+/// The query is validated against a schema when it is created: every selected field must exist in the schema,
+/// and the query must select at least the fields of the compiled query it replaces, with the same variables,
+/// so that its responses still deserialize into the compiled response types. Any extra fields it selects are
+/// kept in the raw response.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use deployment_changelog::api::graphql::RuntimeQuery;
+///
+/// let query = std::fs::read_to_string("queries/queries.graphql")?;
+/// let schema = std::fs::read_to_string("queries/schema.graphql")?;
+///
+/// let runtime_query = RuntimeQuery::new(my_query::OPERATION_NAME, my_query::QUERY, &query, &schema)?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct RuntimeQuery {
+    pub query: String,
+    pub operation_name: String
+}
+
+impl RuntimeQuery {
+    /// Validates a query document against a schema and creates a `RuntimeQuery` replacing a compiled query.
+    ///
+    /// # Arguments
+    ///
+    /// * `operation_name` - The name of the compiled query operation, such as `my_query::OPERATION_NAME`.
+    /// * `compiled_query` - The document of the compiled query, such as `my_query::QUERY`.
+    /// * `query` - The GraphQL document, which must contain a query operation named `operation_name`.
+    /// * `schema` - The GraphQL schema, in the schema definition language, to validate the query against.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query or schema cannot be parsed, if the query selects fields that do not exist
+    /// in the schema, or if it does not select every field of the compiled query.
+    pub fn new(operation_name: &str, compiled_query: &str, query: &str, schema: &str) -> Result<Self> {
+        let schema = schema::parse_schema::<String>(schema)
+            .with_context(|| "Error parsing the GraphQL schema")?;
+        let schema = SchemaTypes::new(&schema);
+
+        let compiled_document = query::parse_query::<String>(compiled_query)
+            .with_context(|| format!("Error parsing the compiled GraphQL query {operation_name}"))?;
+        let document = query::parse_query::<String>(query)
+            .with_context(|| format!("Error parsing the GraphQL query {operation_name}"))?;
+
+        let (required_variables, required_paths) = schema.validate(&compiled_document, operation_name)
+            .with_context(|| format!("The compiled GraphQL query {operation_name} does not match the schema"))?;
+        let (variables, paths) = schema.validate(&document, operation_name)
+            .with_context(|| format!("The GraphQL query {operation_name} does not match the schema"))?;
+
+        if let Some(variable) = variables.difference(&required_variables).next() {
+            bail!("The GraphQL query {operation_name} declares the variable ${variable}, which is not provided");
+        }
+
+        if let Some(variable) = required_variables.difference(&variables).next() {
+            bail!("The GraphQL query {operation_name} does not declare the variable ${variable}");
+        }
+
+        if let Some(path) = required_paths.difference(&paths).next() {
+            bail!("The GraphQL query {operation_name} does not select {path}, which is required");
+        }
+
+        Ok(Self {
+            query: query.to_string(),
+            operation_name: operation_name.to_string()
+        })
+    }
+}
+
+/// The object and interface types of a schema, by name, with the fields they declare.
+struct SchemaTypes<'a> {
+    query_type: String,
+    fields: HashMap<String, Vec<&'a schema::Field<'a, String>>>,
+    abstract_types: HashSet<String>
+}
+
+impl<'a> SchemaTypes<'a> {
+    fn field(&self, type_name: &str, field_name: &str) -> Option<&'a schema::Field<'a, String>> {
+        self.fields.get(type_name)
+            .and_then(|fields| fields.iter().find(|schema_field| schema_field.name == field_name))
+            .copied()
+    }
+
+    fn new(document: &'a schema::Document<'a, String>) -> Self {
+        let mut query_type = DEFAULT_QUERY_TYPE.to_string();
+        let mut fields = HashMap::<String, Vec<&'a schema::Field<'a, String>>>::new();
+        let mut abstract_types = HashSet::<String>::new();
+
+        document.definitions.iter()
+            .for_each(|definition| match definition {
+                schema::Definition::SchemaDefinition(schema_definition) => {
+                    if let Some(name) = &schema_definition.query {
+                        query_type = name.clone();
+                    }
+                },
+                schema::Definition::TypeDefinition(schema::TypeDefinition::Object(object)) => {
+                    fields.entry(object.name.clone()).or_default().extend(object.fields.iter());
+                },
+                schema::Definition::TypeDefinition(schema::TypeDefinition::Interface(interface)) => {
+                    fields.entry(interface.name.clone()).or_default().extend(interface.fields.iter());
+                },
+                schema::Definition::TypeDefinition(schema::TypeDefinition::Union(union)) => {
+                    abstract_types.insert(union.name.clone());
+                },
+                schema::Definition::TypeExtension(schema::TypeExtension::Object(object)) => {
+                    fields.entry(object.name.clone()).or_default().extend(object.fields.iter());
+                },
+                _ => ()
+            });
+
+        Self {
+            query_type,
+            fields,
+            abstract_types
+        }
+    }
+
+    /// Validates the named query operation of a document, returning the names of its variables and the
+    /// response paths of every field it selects.
+    fn validate<'q>(
+        &self,
+        document: &'q query::Document<'q, String>,
+        operation_name: &str
+    ) -> Result<(BTreeSet<String>, BTreeSet<String>)> {
+        let fragments: HashMap<&str, &query::FragmentDefinition<'q, String>> = document.definitions.iter()
+            .filter_map(|definition| match definition {
+                query::Definition::Fragment(fragment) => Some((fragment.name.as_str(), fragment)),
+                _ => None
+            })
+            .collect();
+
+        let operation = document.definitions.iter()
+            .find_map(|definition| match definition {
+                query::Definition::Operation(query::OperationDefinition::Query(operation))
+                    if operation.name.as_deref() == Some(operation_name) => Some(operation),
+                _ => None
+            })
+            .with_context(|| format!("No query named {operation_name} was found"))?;
+
+        let variables = operation.variable_definitions.iter()
+            .map(|variable| variable.name.clone())
+            .collect();
+
+        let mut paths = BTreeSet::new();
+        self.validate_selection_set(&self.query_type, &operation.selection_set, "", &fragments, &mut Vec::new(), &mut paths)?;
+
+        Ok((variables, paths))
+    }
+
+    fn validate_selection_set<'q>(
+        &self,
+        type_name: &str,
+        selection_set: &'q query::SelectionSet<'q, String>,
+        path: &str,
+        fragments: &HashMap<&str, &'q query::FragmentDefinition<'q, String>>,
+        visited_fragments: &mut Vec<&'q str>,
+        paths: &mut BTreeSet<String>
+    ) -> Result<()> {
+        for selection in &selection_set.items {
+            match selection {
+                query::Selection::Field(field) => {
+                    let response_key = field.alias.as_ref().unwrap_or(&field.name);
+                    let field_path = if path.is_empty() { response_key.clone() } else { format!("{path}.{response_key}") };
+
+                    paths.insert(field_path.clone());
+
+                    if field.name == "__typename" {
+                        continue;
+                    }
+
+                    let schema_field = self.field(type_name, &field.name)
+                        .ok_or_else(|| anyhow!("Field {} does not exist on type {type_name}", field.name))?;
+
+                    let field_type = named_type(&schema_field.field_type);
+                    let is_composite = self.fields.contains_key(field_type) || self.abstract_types.contains(field_type);
+
+                    match (is_composite, field.selection_set.items.is_empty()) {
+                        (true, true) => bail!("Field {type_name}.{} of type {field_type} must select subfields", field.name),
+                        (false, false) => bail!("Field {type_name}.{} of type {field_type} cannot select subfields", field.name),
+                        (true, false) => self.validate_selection_set(
+                            field_type,
+                            &field.selection_set,
+                            &field_path,
+                            fragments,
+                            visited_fragments,
+                            paths
+                        )?,
+                        (false, true) => ()
+                    }
+                },
+                query::Selection::FragmentSpread(spread) => {
+                    let fragment = fragments.get(spread.fragment_name.as_str())
+                        .with_context(|| format!("Fragment {} is not defined", spread.fragment_name))?;
+
+                    if visited_fragments.contains(&fragment.name.as_str()) {
+                        bail!("Fragment {} spreads itself", fragment.name);
+                    }
+
+                    let query::TypeCondition::On(fragment_type) = &fragment.type_condition;
+
+                    visited_fragments.push(&fragment.name);
+                    self.validate_selection_set(fragment_type, &fragment.selection_set, path, fragments, visited_fragments, paths)?;
+                    visited_fragments.pop();
+                },
+                query::Selection::InlineFragment(inline_fragment) => {
+                    let fragment_type = match &inline_fragment.type_condition {
+                        Some(query::TypeCondition::On(fragment_type)) => fragment_type.as_str(),
+                        None => type_name
+                    };
+
+                    self.validate_selection_set(fragment_type, &inline_fragment.selection_set, path, fragments, visited_fragments, paths)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Compares the fields selected by a selection set of a compiled query, whose types are looked up in this
+    /// schema, with the same fields in the schema of a server, reporting the differences that would break the
+    /// query as sentences following the name of the query.
+    fn compare_selection_set<'q>(
+        &self,
+        server: &SchemaTypes,
+        (type_name, server_type_name): (&str, &str),
+        selection_set: &'q query::SelectionSet<'q, String>,
+        path: &str,
+        fragments: &HashMap<&str, &'q query::FragmentDefinition<'q, String>>,
+        incompatibilities: &mut Vec<String>
+    ) {
+        selection_set.items.iter()
+            .for_each(|selection| match selection {
+                query::Selection::Field(field) => {
+                    let response_key = field.alias.as_ref().unwrap_or(&field.name);
+                    let field_path = if path.is_empty() { response_key.clone() } else { format!("{path}.{response_key}") };
+
+                    let compiled_field = match self.field(type_name, &field.name) {
+                        Some(compiled_field) => compiled_field,
+                        None => return
+                    };
+
+                    let server_field = match server.field(server_type_name, &field.name) {
+                        Some(server_field) => server_field,
+                        None => {
+                            incompatibilities.push(format!(
+                                "selects {field_path}, but {server_type_name}.{} does not exist",
+                                field.name
+                            ));
+                            return;
+                        }
+                    };
+
+                    let server_field_name = format!("{server_type_name}.{}", field.name);
+
+                    field.arguments.iter()
+                        .for_each(|(argument, _)| {
+                            let compiled_argument = compiled_field.arguments.iter()
+                                .find(|compiled_argument| &compiled_argument.name == argument);
+                            let server_argument = server_field.arguments.iter()
+                                .find(|server_argument| &server_argument.name == argument);
+
+                            match (compiled_argument, server_argument) {
+                                (Some(_), None) => incompatibilities.push(format!(
+                                    "passes the argument {argument} to {field_path}, but {server_field_name} does not accept it"
+                                )),
+                                (Some(compiled_argument), Some(server_argument))
+                                    if !is_compatible(&server_argument.value_type, &compiled_argument.value_type) => incompatibilities.push(format!(
+                                        "passes the argument {argument} to {field_path} as {}, but {server_field_name} expects {}",
+                                        compiled_argument.value_type,
+                                        server_argument.value_type
+                                    )),
+                                _ => ()
+                            }
+                        });
+
+                    server_field.arguments.iter()
+                        .filter(|server_argument| matches!(server_argument.value_type, schema::Type::NonNullType(_)))
+                        .filter(|server_argument| server_argument.default_value.is_none())
+                        .filter(|server_argument| !field.arguments.iter().any(|(argument, _)| argument == &server_argument.name))
+                        .for_each(|server_argument| incompatibilities.push(format!(
+                            "does not pass the argument {} to {field_path}, which {server_field_name} requires",
+                            server_argument.name
+                        )));
+
+                    if !is_compatible(&compiled_field.field_type, &server_field.field_type) {
+                        incompatibilities.push(format!(
+                            "expects {field_path} to be {}, but {server_field_name} is {}",
+                            compiled_field.field_type,
+                            server_field.field_type
+                        ));
+                    }
+
+                    // A field that only became nullable still has the same subfields to compare.
+                    if !field.selection_set.items.is_empty() && named_type(&compiled_field.field_type) == named_type(&server_field.field_type) {
+                        self.compare_selection_set(
+                            server,
+                            (named_type(&compiled_field.field_type), named_type(&server_field.field_type)),
+                            &field.selection_set,
+                            &field_path,
+                            fragments,
+                            incompatibilities
+                        );
+                    }
+                },
+                query::Selection::FragmentSpread(spread) => {
+                    if let Some(fragment) = fragments.get(spread.fragment_name.as_str()) {
+                        let query::TypeCondition::On(fragment_type) = &fragment.type_condition;
+
+                        self.compare_selection_set(server, (fragment_type, fragment_type), &fragment.selection_set, path, fragments, incompatibilities);
+                    }
+                },
+                query::Selection::InlineFragment(inline_fragment) => {
+                    let fragment_types = match &inline_fragment.type_condition {
+                        Some(query::TypeCondition::On(fragment_type)) => (fragment_type.as_str(), fragment_type.as_str()),
+                        None => (type_name, server_type_name)
+                    };
+
+                    self.compare_selection_set(server, fragment_types, &inline_fragment.selection_set, path, fragments, incompatibilities);
+                }
+            });
+    }
+}
+
+fn named_type<'a>(field_type: &'a schema::Type<'a, String>) -> &'a str {
+    match field_type {
+        schema::Type::NamedType(name) => name,
+        schema::Type::ListType(inner) | schema::Type::NonNullType(inner) => named_type(inner)
+    }
+}
+
+/// Returns whether the values of type `actual` can be read as the type `expected`: the named types must be the
+/// same, and a non-null type can't become nullable, while a nullable type can become non-null.
+fn is_compatible(expected: &schema::Type<String>, actual: &schema::Type<String>) -> bool {
+    match (expected, actual) {
+        (schema::Type::NonNullType(expected), schema::Type::NonNullType(actual)) => is_compatible(expected, actual),
+        (schema::Type::NonNullType(_), _) => false,
+        (expected, schema::Type::NonNullType(actual)) => is_compatible(expected, actual),
+        (schema::Type::ListType(expected), schema::Type::ListType(actual)) => is_compatible(expected, actual),
+        (schema::Type::NamedType(expected), schema::Type::NamedType(actual)) => expected == actual,
+        _ => false
+    }
+}
+
+/// Checks a compiled query against the schema a server actually serves, such as after a server upgrade, and
+/// returns every incompatibility that would make the query fail or its response no longer deserialize: a
+/// selected field or passed argument that was removed, a field whose type changed or became nullable, an
+/// argument whose type changed, or a new required argument.
+///
+/// # Arguments
+///
+/// * `operation_name` - The name of the compiled query operation, such as `my_query::OPERATION_NAME`.
+/// * `compiled_query` - The document of the compiled query, such as `my_query::QUERY`.
+/// * `compiled_schema` - The schema the query was compiled against, in the schema definition language.
+/// * `server_schema` - The schema of the server, such as returned by `GraphQLClient::introspect_schema`.
+///
+/// # Errors
+///
+/// Returns an error if the query or the schemas cannot be parsed, or if the query does not match the schema it
+/// was compiled against.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use deployment_changelog::api::graphql::{schema_incompatibilities, GraphQLClient};
+///
+/// let graphql_client = GraphQLClient::new("https://api.example.com")?;
+/// let server_schema = graphql_client.introspect_schema().await?;
+///
+/// schema_incompatibilities(my_query::OPERATION_NAME, my_query::QUERY, MY_SCHEMA, &server_schema)?
+///     .iter()
+///     .for_each(|incompatibility| println!("{incompatibility}"));
+/// ```
+pub fn schema_incompatibilities(operation_name: &str, compiled_query: &str, compiled_schema: &str, server_schema: &str) -> Result<Vec<String>> {
+    let compiled_schema = schema::parse_schema::<String>(compiled_schema)
+        .with_context(|| "Error parsing the compiled GraphQL schema")?;
+    let compiled_schema = SchemaTypes::new(&compiled_schema);
+
+    let server_schema = schema::parse_schema::<String>(server_schema)
+        .with_context(|| "Error parsing the GraphQL schema of the server")?;
+    let server_schema = SchemaTypes::new(&server_schema);
+
+    let document = query::parse_query::<String>(compiled_query)
+        .with_context(|| format!("Error parsing the compiled GraphQL query {operation_name}"))?;
+
+    compiled_schema.validate(&document, operation_name)
+        .with_context(|| format!("The compiled GraphQL query {operation_name} does not match its schema"))?;
+
+    let fragments: HashMap<&str, &query::FragmentDefinition<String>> = document.definitions.iter()
+        .filter_map(|definition| match definition {
+            query::Definition::Fragment(fragment) => Some((fragment.name.as_str(), fragment)),
+            _ => None
+        })
+        .collect();
+
+    let operation = document.definitions.iter()
+        .find_map(|definition| match definition {
+            query::Definition::Operation(query::OperationDefinition::Query(operation))
+                if operation.name.as_deref() == Some(operation_name) => Some(operation),
+            _ => None
+        })
+        .with_context(|| format!("No query named {operation_name} was found"))?;
+
+    let mut incompatibilities = Vec::new();
+    compiled_schema.compare_selection_set(
+        &server_schema,
+        (&compiled_schema.query_type, &server_schema.query_type),
+        &operation.selection_set,
+        "",
+        &fragments,
+        &mut incompatibilities
+    );
+
+    Ok(incompatibilities.into_iter()
+        .map(|incompatibility| format!("{operation_name} {incompatibility}"))
+        .collect())
+}
+
+/// The introspection query of the types of a schema, with their fields, arguments, interfaces, union members,
+/// enum values and input fields. Types are nested up to seven levels deep, such as `[[String!]!]!`.
+const INTROSPECTION_QUERY: &str = r#"
+query IntrospectionQuery {
+  __schema {
+    queryType { name }
+    types {
+      kind
+      name
+      fields(includeDeprecated: true) {
+        name
+        args { name type { ...TypeRef } }
+        type { ...TypeRef }
+      }
+      inputFields { name type { ...TypeRef } }
+      interfaces { name }
+      possibleTypes { name }
+      enumValues(includeDeprecated: true) { name }
+    }
+  }
+}
+
+fragment TypeRef on __Type {
+  kind
+  name
+  ofType { kind name ofType { kind name ofType { kind name ofType { kind name ofType { kind name ofType { kind name ofType { kind name } } } } } } }
+}
+"#;
+
+/// The scalars every GraphQL schema has, which schema documents don't declare.
+const BUILT_IN_SCALARS: [&str; 5] = ["String", "Int", "Float", "Boolean", "ID"];
+
+#[derive(Deserialize)]
+struct IntrospectionResponse {
+    #[serde(rename = "__schema")]
+    schema: IntrospectionSchema
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IntrospectionSchema {
+    query_type: IntrospectionName,
+    types: Vec<IntrospectionType>
+}
+
+#[derive(Deserialize)]
+struct IntrospectionName {
+    name: String
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IntrospectionType {
+    kind: String,
+    name: String,
+    fields: Option<Vec<IntrospectionField>>,
+    input_fields: Option<Vec<IntrospectionInputValue>>,
+    interfaces: Option<Vec<IntrospectionName>>,
+    possible_types: Option<Vec<IntrospectionName>>,
+    enum_values: Option<Vec<IntrospectionName>>
+}
+
+#[derive(Deserialize)]
+struct IntrospectionField {
+    name: String,
+    args: Vec<IntrospectionInputValue>,
+    #[serde(rename = "type")]
+    field_type: IntrospectionTypeRef
+}
+
+#[derive(Deserialize)]
+struct IntrospectionInputValue {
+    name: String,
+    #[serde(rename = "type")]
+    value_type: IntrospectionTypeRef
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IntrospectionTypeRef {
+    kind: String,
+    name: Option<String>,
+    of_type: Option<Box<IntrospectionTypeRef>>
+}
+
+impl Display for IntrospectionTypeRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.kind.as_str(), &self.of_type) {
+            ("NON_NULL", Some(of_type)) => write!(f, "{of_type}!"),
+            ("LIST", Some(of_type)) => write!(f, "[{of_type}]"),
+            _ => write!(f, "{}", self.name.as_deref().unwrap_or_default())
+        }
+    }
+}
+
+impl IntrospectionSchema {
+    /// Writes the schema in the schema definition language, leaving out descriptions, directives and default
+    /// values, which don't affect the compatibility of queries.
+    fn to_sdl(&self) -> String {
+        let mut sdl = format!("schema {{\n  query: {}\n}}\n", self.query_type.name);
+
+        self.types.iter()
+            .filter(|introspection_type| !introspection_type.name.starts_with("__"))
+            .for_each(|introspection_type| {
+                let name = &introspection_type.name;
+
+                let definition = match introspection_type.kind.as_str() {
+                    "SCALAR" if BUILT_IN_SCALARS.contains(&name.as_str()) => return,
+                    "SCALAR" => format!("scalar {name}"),
+                    "OBJECT" | "INTERFACE" => {
+                        let keyword = if introspection_type.kind == "OBJECT" { "type" } else { "interface" };
+
+                        let interfaces = introspection_type.interfaces.iter()
+                            .flatten()
+                            .map(|interface| interface.name.as_str())
+                            .collect::<Vec<&str>>()
+                            .join(" & ");
+                        let implements = if interfaces.is_empty() { String::new() } else { format!(" implements {interfaces}") };
+
+                        let fields = introspection_type.fields.iter()
+                            .flatten()
+                            .map(|field| {
+                                let arguments = field.args.iter()
+                                    .map(|argument| format!("{}: {}", argument.name, argument.value_type))
+                                    .collect::<Vec<String>>()
+                                    .join(", ");
+
+                                match arguments.is_empty() {
+                                    true => format!("  {}: {}", field.name, field.field_type),
+                                    false => format!("  {}({arguments}): {}", field.name, field.field_type)
+                                }
+                            })
+                            .collect::<Vec<String>>()
+                            .join("\n");
+
+                        format!("{keyword} {name}{implements} {{\n{fields}\n}}")
+                    },
+                    "UNION" => {
+                        let members = introspection_type.possible_types.iter()
+                            .flatten()
+                            .map(|member| member.name.as_str())
+                            .collect::<Vec<&str>>()
+                            .join(" | ");
+
+                        format!("union {name} = {members}")
+                    },
+                    "ENUM" => {
+                        let values = introspection_type.enum_values.iter()
+                            .flatten()
+                            .map(|value| format!("  {}", value.name))
+                            .collect::<Vec<String>>()
+                            .join("\n");
+
+                        format!("enum {name} {{\n{values}\n}}")
+                    },
+                    "INPUT_OBJECT" => {
+                        let fields = introspection_type.input_fields.iter()
+                            .flatten()
+                            .map(|field| format!("  {}: {}", field.name, field.value_type))
+                            .collect::<Vec<String>>()
+                            .join("\n");
+
+                        format!("input {name} {{\n{fields}\n}}")
+                    },
+                    _ => return
+                };
+
+                sdl.push('\n');
+                sdl.push_str(&definition);
+                sdl.push('\n');
+            });
+
+        sdl
+    }
+}
+
+/// The body of a `RuntimeQuery` request, mirroring `graphql_client::QueryBody` for queries that are not compiled in.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RuntimeQueryBody<'a, V: Serialize> {
+    variables: V,
+    query: &'a str,
+    operation_name: &'a str
+}
+
 /// A GraphQL client for communicating with a GraphQL API endpoint.
 ///
 /// `GraphQLClient` provides an easy way to execute GraphQL queries and handle their responses.
@@ -124,6 +736,48 @@ impl GraphQLClient {
         })
     }
 
+    /// Creates a new `GraphQLClient` instance with the given base URL, authenticating its requests with a
+    /// bearer token.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use deployment_changelog::api::rest::graphql::GraphQLClient;
+    ///
+    /// let graphql_client = GraphQLClient::with_token("https://api.example.com", "token")?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the base URL cannot be parsed or if there is an error
+    /// creating the underlying `RestClient`.
+    pub fn with_token(base_url: &str, token: &str) -> Result<Self> {
+        Ok(Self {
+            client: RestClient::with_token(base_url, token)?
+        })
+    }
+
+    /// Creates a new `GraphQLClient` instance with the given base URL, authenticating its requests with the
+    /// given credentials.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use deployment_changelog::api::{graphql::GraphQLClient, rest::Credentials, secret::SecretString};
+    ///
+    /// let graphql_client = GraphQLClient::with_credentials("https://api.example.com", Credentials::Cookie(SecretString::from("SESSION=abc123")))?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the base URL cannot be parsed or if there is an error
+    /// creating the underlying `RestClient`.
+    pub fn with_credentials(base_url: &str, credentials: Credentials) -> Result<Self> {
+        Ok(Self {
+            client: RestClient::with_credentials(base_url, credentials)?
+        })
+    }
+
     /// Creates a new `GraphQLClient` instance using an existing `RestClient`.
     ///
     /// This method can be useful if you want to share a single `RestClient` instance
@@ -144,6 +798,16 @@ impl GraphQLClient {
         }
     }
 
+    /// Returns the base URL of the GraphQL API server this client talks to.
+    pub fn base_url(&self) -> &Url {
+        &self.client.base_url
+    }
+
+    /// Returns the underlying `RestClient`, for the REST endpoints served next to the GraphQL endpoint.
+    pub fn rest_client(&self) -> &RestClient {
+        &self.client
+    }
+
     /// Executes a GraphQL query with the given variables and returns the response.
     ///
     /// The query is defined using the `GraphQLQuery` trait from the `graphql_client` crate.
@@ -177,7 +841,7 @@ impl GraphQLClient {
     /// or if the GraphQL API returns an error.
     pub async fn post<Q: GraphQLQuery>(&self, variables: Q::Variables) -> Result<Response<Q::ResponseData>> {
         let body = Q::build_query(variables);
-        self.client.post_json::<Response<Q::ResponseData>, QueryBody<Q::Variables>>(GRAPHQL_ENDPOINT, &body)
+        self.client.post_json_idempotent::<Response<Q::ResponseData>, QueryBody<Q::Variables>>(GRAPHQL_ENDPOINT, &body)
             .await
             .with_context(|| {
                 match serde_json::to_string_pretty(&body) {
@@ -186,5 +850,57 @@ impl GraphQLClient {
                 }
             })
     }
+
+    /// Executes a `RuntimeQuery` with the given variables and returns the response.
+    ///
+    /// Unlike `post`, the response data is not tied to a compiled query, so it can be deserialized into
+    /// any type, such as a `serde_json::Value` keeping every field the query selected.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is an issue with the HTTP request or response handling.
+    pub async fn post_runtime<V: Serialize, R: DeserializeOwned>(
+        &self,
+        query: &RuntimeQuery,
+        variables: V
+    ) -> Result<Response<R>> {
+        let body = RuntimeQueryBody {
+            variables,
+            query: &query.query,
+            operation_name: &query.operation_name
+        };
+
+        self.client.post_json_idempotent::<Response<R>, RuntimeQueryBody<V>>(GRAPHQL_ENDPOINT, &body)
+            .await
+            .with_context(|| format!("Error making GraphQL call with query {}", query.operation_name))
+    }
+
+    /// Fetches the schema of the GraphQL API with an introspection query, in the schema definition language, to
+    /// check compiled queries against with `schema_incompatibilities`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is an issue with the HTTP request or response handling, or if the server
+    /// disables introspection.
+    pub async fn introspect_schema(&self) -> Result<String> {
+        let body = RuntimeQueryBody {
+            variables: serde_json::Value::Null,
+            query: INTROSPECTION_QUERY,
+            operation_name: "IntrospectionQuery"
+        };
+
+        let response = self.client.post_json_idempotent::<Response<IntrospectionResponse>, _>(GRAPHQL_ENDPOINT, &body)
+            .await
+            .with_context(|| "Error making the GraphQL introspection call")?;
+
+        if let Some(errors) = response.errors {
+            bail!("Received errors from the GraphQL introspection call {:#?}", errors);
+        }
+
+        let data = response.data
+            .with_context(|| "No data received for the GraphQL introspection call but no errors were found")?;
+
+        Ok(data.schema.to_sdl())
+    }
 }
 
@@ -0,0 +1,25 @@
+//! HMAC-SHA256 (RFC 2104), shared by the request signatures of the crate, such as those of webhooks and of AWS
+//! Signature Version 4.
+use sha2::{Digest, Sha256};
+
+/// Computes the HMAC-SHA256 of a message with a key.
+pub(crate) fn hmac_sha256(key: &[u8], message: &[u8]) -> Vec<u8> {
+    const BLOCK_SIZE: usize = 64;
+
+    let mut block_key = if key.len() > BLOCK_SIZE { Sha256::digest(key).to_vec() } else { key.to_vec() };
+    block_key.resize(BLOCK_SIZE, 0);
+
+    let inner_key: Vec<u8> = block_key.iter().map(|byte| byte ^ 0x36).collect();
+    let outer_key: Vec<u8> = block_key.iter().map(|byte| byte ^ 0x5c).collect();
+
+    let inner_hash = Sha256::new()
+        .chain_update(inner_key)
+        .chain_update(message)
+        .finalize();
+
+    Sha256::new()
+        .chain_update(outer_key)
+        .chain_update(inner_hash)
+        .finalize()
+        .to_vec()
+}
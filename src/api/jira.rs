@@ -37,27 +37,125 @@
 //!     }
 //! }
 //! ```
-use std::fmt::Display;
+use std::{fmt::Display, collections::HashMap};
 
 use serde::{Serialize, Deserialize};
-use chrono::{DateTime, Local};
+use chrono::{DateTime, Utc};
 
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
+use futures::future::try_join_all;
 
-use super::rest::RestClient;
+use reqwest::{Url, header::{HeaderMap, HeaderValue}, multipart::{Form, Part}};
+
+use super::{rest::{Credentials, OAuth2ClientCredentials, RestClient, Paginated}, bitbucket::{BitbucketAuthor, BitbucketCommit, BitbucketProject, BitbucketPullRequest, BitbucketPullRequestAuthor, BitbucketRef, BitbucketRepository}};
+
+/// The development tool integration the dev-status API is queried for, which is Bitbucket Server.
+const DEV_STATUS_APPLICATION_TYPE: &str = "stash";
+
+/// The header Jira requires on attachment uploads, to disable its XSRF check.
+const ATLASSIAN_TOKEN_HEADER: &str = "X-Atlassian-Token";
+
+/// The permission needed to view the issues of a project.
+const BROWSE_PROJECTS_PERMISSION: &str = "BROWSE_PROJECTS";
 
 enum JiraEndpoints {
-    GetIssue
+    GetIssue,
+    Search,
+    DevStatusDetail,
+    Attachments,
+    MyPermissions,
+    Worklogs
 }
 
 impl JiraEndpoints {
     fn url(&self) -> &'static str {
         match self {
-            JiraEndpoints::GetIssue => "rest/api/latest/issue/{issueKey}"
+            JiraEndpoints::GetIssue => "rest/api/latest/issue/{issueKey}?expand=changelog",
+            JiraEndpoints::Search => "rest/api/latest/search?expand=changelog",
+            JiraEndpoints::DevStatusDetail => "rest/dev-status/latest/issue/detail?issueId={issueId}&applicationType={applicationType}&dataType={dataType}",
+            JiraEndpoints::Attachments => "rest/api/latest/issue/{issueKey}/attachments",
+            JiraEndpoints::MyPermissions => "rest/api/latest/mypermissions?projectKey={projectKey}&permissions={permissions}",
+            JiraEndpoints::Worklogs => "rest/api/latest/issue/{issueKey}/worklog"
+        }
+    }
+}
+
+enum JiraOptions {
+    Jql,
+    StartAt
+}
+
+impl JiraOptions {
+    fn option(&self) -> &'static str {
+        match self {
+            JiraOptions::Jql => "jql",
+            JiraOptions::StartAt => "startAt"
         }
     }
 }
 
+/// Quotes a value, such as a fix version or project key, as a JQL string, escaping its backslashes and
+/// double quotes so that it can't end the string early.
+///
+/// # Example
+///
+/// ```rust
+/// use deployment_changelog::api::jira::jql_string;
+///
+/// assert_eq!(jql_string(r#"2.0 "beta""#), r#""2.0 \"beta\"""#);
+/// ```
+pub fn jql_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// The `JiraSearchResults` struct represents a page of issues returned by the Jira search API.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraSearchResults {
+    pub start_at: u32,
+    pub max_results: u32,
+    pub total: u32,
+    pub issues: Vec<JiraIssue>
+}
+
+/// The `JiraSearchPaginated` struct fetches the pages of issues matching a JQL query.
+///
+/// You usually don't need to create a `JiraSearchPaginated` object manually, as `JiraClient::search_issues()`
+/// returns one.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use deployment_changelog::api::rest::Paginated;
+///
+/// // Suppose you have a JiraClient named 'client'
+/// let issues = client.search_issues("fixVersion = 2024.06").all().await.unwrap();
+/// ```
+pub struct JiraSearchPaginated<'a> {
+    client: &'a JiraClient,
+    query: HashMap<String, String>,
+    next_start_at: u32,
+    is_last_page: bool
+}
+
+#[async_trait::async_trait]
+impl Paginated<JiraIssue> for JiraSearchPaginated<'_> {
+    async fn next(&mut self) -> Result<Vec<JiraIssue>> {
+        self.query.insert(JiraOptions::StartAt.option().to_string(), self.next_start_at.to_string());
+
+        let page = self.client.client.get::<JiraSearchResults>(JiraEndpoints::Search.url(), Some(&self.query)).await?;
+
+        self.next_start_at = page.start_at + page.issues.len() as u32;
+        self.is_last_page = page.issues.is_empty() || self.next_start_at >= page.total;
+
+        Ok(page.issues)
+    }
+
+    fn is_last(&self) -> bool {
+        self.is_last_page
+    }
+}
+
 /// The `JiraIssue` struct represents a Jira issue and its associated fields.
 ///
 /// # Example
@@ -95,11 +193,75 @@ impl JiraEndpoints {
 ///     println!("{}", issue); // Outputs the formatted JSON representation of the issue
 /// }
 /// ```
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct JiraIssue {
+    #[serde(default)]
+    pub id: String,
+
     pub key: String,
-    pub fields: JiraIssueFields
+    pub fields: JiraIssueFields,
+
+    #[serde(default, skip_serializing)]
+    pub changelog: Option<JiraChangelog>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cycle_time: Option<JiraCycleTime>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_spent_seconds: Option<i64>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entry_id: Option<String>
+}
+
+impl JiraIssue {
+    /// Computes the cycle time of the issue from its status transition history, as the time between
+    /// its first transition to `start_status` and its last transition to `end_status` afterwards.
+    /// Status names are compared case-insensitively.
+    ///
+    /// Returns `None` if the history wasn't fetched, or if the issue never went through both statuses.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use deployment_changelog::api::jira::JiraClient;
+    ///
+    /// // Suppose you have a JiraClient named 'client'
+    /// let issue = client.get_issue("DEMO-123").await.unwrap();
+    ///
+    /// if let Some(cycle_time) = issue.compute_cycle_time("In Progress", "Done") {
+    ///     println!("DEMO-123 took {} seconds", cycle_time.cycle_time_seconds);
+    /// }
+    /// ```
+    pub fn compute_cycle_time(&self, start_status: &str, end_status: &str) -> Option<JiraCycleTime> {
+        let mut status_transitions: Vec<(DateTime<Utc>, &str)> = self.changelog.as_ref()?
+            .histories
+            .iter()
+            .flat_map(|history| {
+                history.items.iter()
+                    .filter(|item| item.field == "status")
+                    .filter_map(move |item| item.to_string.as_deref().map(|status| (history.created, status)))
+            })
+            .collect();
+
+        status_transitions.sort_by_key(|(created, _)| *created);
+
+        let started = status_transitions.iter()
+            .find(|(_, status)| status.eq_ignore_ascii_case(start_status))
+            .map(|(created, _)| *created)?;
+
+        let completed = status_transitions.iter()
+            .rev()
+            .find(|(created, status)| *created >= started && status.eq_ignore_ascii_case(end_status))
+            .map(|(created, _)| *created)?;
+
+        Some(JiraCycleTime {
+            started,
+            completed,
+            cycle_time_seconds: (completed - started).num_seconds()
+        })
+    }
 }
 
 impl Display for JiraIssue {
@@ -111,6 +273,267 @@ impl Display for JiraIssue {
     }
 }
 
+/// The `JiraPermissions` struct represents the permissions the authenticated user has on a project, as returned
+/// by the my permissions API, keyed by permission, such as `BROWSE_PROJECTS`.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraPermissions {
+    pub permissions: HashMap<String, JiraPermission>
+}
+
+/// The `JiraPermission` struct represents whether the authenticated user has a permission.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraPermission {
+    pub key: String,
+    pub have_permission: bool
+}
+
+/// The `JiraDevStatus` struct represents the development information Jira holds about an issue, as returned
+/// by the dev-status API backing the development panel of the issue.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraDevStatus {
+    pub detail: Vec<JiraDevStatusDetail>
+}
+
+/// The `JiraDevStatusDetail` struct represents the development information from a single development tool instance.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraDevStatusDetail {
+    #[serde(default)]
+    pub pull_requests: Vec<JiraDevStatusPullRequest>,
+
+    #[serde(default)]
+    pub repositories: Vec<JiraDevStatusRepository>
+}
+
+/// The `JiraDevStatusPullRequest` struct represents a pull request linked to a Jira issue in its development panel.
+/// Its `id` is displayed by Bitbucket as `#42`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraDevStatusPullRequest {
+    pub id: String,
+    pub name: String,
+    pub url: String,
+    pub status: String,
+    pub author: JiraDevStatusAuthor,
+    pub last_update: DateTime<Utc>
+}
+
+impl JiraDevStatusPullRequest {
+    /// Returns the numeric ID of the pull request, stripped of the `#` prefix.
+    pub fn number(&self) -> Option<u64> {
+        self.id.trim_start_matches('#')
+            .parse()
+            .ok()
+    }
+
+    /// Returns the project key and repository slug of the pull request, parsed from its Bitbucket URL such as
+    /// `https://bitbucket.example.com/projects/PROJ/repos/my-repo/pull-requests/42`. Personal repositories, under
+    /// `users/{username}`, belong to the project `~{username}`.
+    pub fn repository(&self) -> Option<(String, String)> {
+        let url = Url::parse(&self.url).ok()?;
+        let segments: Vec<&str> = url.path_segments()?.collect();
+
+        segments.windows(4)
+            .find_map(|window| match window {
+                ["projects", project, "repos", repo] => Some((project.to_string(), repo.to_string())),
+                ["users", user, "repos", repo] => Some((format!("~{user}"), repo.to_string())),
+                _ => None
+            })
+    }
+}
+
+/// The `JiraDevStatusRepository` struct represents a repository containing commits linked to a Jira issue.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraDevStatusRepository {
+    pub name: String,
+    pub url: String,
+
+    #[serde(default)]
+    pub commits: Vec<JiraDevStatusCommit>
+}
+
+/// The `JiraDevStatusCommit` struct represents a commit linked to a Jira issue in its development panel.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraDevStatusCommit {
+    pub id: String,
+    pub display_id: String,
+    pub message: String,
+    pub author: JiraDevStatusAuthor,
+    pub author_timestamp: DateTime<Utc>
+}
+
+/// The `JiraDevStatusAuthor` struct represents the author of a commit or pull request in the development panel.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraDevStatusAuthor {
+    pub name: String,
+    pub email: Option<String>
+}
+
+impl From<JiraDevStatusAuthor> for BitbucketAuthor {
+    fn from(author: JiraDevStatusAuthor) -> Self {
+        Self {
+            display_name: author.name.clone(),
+            email_address: author.email.unwrap_or_default(),
+            name: author.name
+        }
+    }
+}
+
+impl From<JiraDevStatusCommit> for BitbucketCommit {
+    fn from(commit: JiraDevStatusCommit) -> Self {
+        Self {
+            id: commit.id,
+            display_id: commit.display_id,
+            author: commit.author.clone().into(),
+            committer: commit.author.into(),
+            author_timestamp: commit.author_timestamp,
+            message: commit.message,
+            entry_id: None
+        }
+    }
+}
+
+impl TryFrom<JiraDevStatusPullRequest> for BitbucketPullRequest {
+    type Error = anyhow::Error;
+
+    fn try_from(pull_request: JiraDevStatusPullRequest) -> Result<Self> {
+        let id = pull_request.number()
+            .ok_or_else(|| anyhow::anyhow!("Invalid pull request ID {} in the Jira development panel", pull_request.id))?;
+
+        // The development panel doesn't name the branches, only the repository the pull request belongs to.
+        let to_ref = pull_request.repository()
+            .map(|(project, repo)| BitbucketRef {
+                id: String::new(),
+                display_id: String::new(),
+                repository: BitbucketRepository {
+                    slug: repo,
+                    name: None,
+                    project: BitbucketProject {
+                        key: project,
+                        name: None
+                    }
+                }
+            });
+
+        Ok(Self {
+            id,
+            title: pull_request.name,
+            description: String::new(),
+            open: pull_request.status == "OPEN",
+            state: Some(pull_request.status),
+            author: BitbucketPullRequestAuthor {
+                user: pull_request.author.into(),
+                approved: false
+            },
+            created_date: pull_request.last_update,
+            updated_date: pull_request.last_update,
+            closed_date: None,
+            from_ref: None,
+            to_ref,
+            release_note: None,
+            size: None,
+            entry_id: None
+        })
+    }
+}
+
+/// The `JiraChangelog` struct represents the change history of a Jira issue, as returned when the issue
+/// is fetched with the `changelog` expansion. Jira only returns the most recent 100 histories this way.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraChangelog {
+    pub histories: Vec<JiraChangelogHistory>
+}
+
+/// The `JiraChangelogHistory` struct represents a single change to a Jira issue, which can update several fields at once.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraChangelogHistory {
+    pub created: DateTime<Utc>,
+    pub items: Vec<JiraChangelogItem>
+}
+
+/// The `JiraChangelogItem` struct represents the change of a single field of a Jira issue, such as a status transition.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraChangelogItem {
+    pub field: String,
+    pub from_string: Option<String>,
+    pub to_string: Option<String>
+}
+
+/// The `JiraCycleTime` struct represents the time a Jira issue took to go from being started to being done.
+/// It contains the following fields:
+///
+/// - `started`: When the issue first transitioned to the start status.
+/// - `completed`: When the issue last transitioned to the end status.
+/// - `cycle_time_seconds`: The time between the two, in seconds.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraCycleTime {
+    pub started: DateTime<Utc>,
+    pub completed: DateTime<Utc>,
+    pub cycle_time_seconds: i64
+}
+
+impl Display for JiraCycleTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match serde_json::to_string_pretty(&self) {
+            Ok(json) => write!(f, "{json}"),
+            Err(error) => panic!("Error serializing Jira cycle time: {error}")
+        }
+    }
+}
+
+/// The `JiraWorklogs` struct represents a page of the work logged on a Jira issue, returned by the worklog API.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraWorklogs {
+    pub start_at: u32,
+    pub max_results: u32,
+    pub total: u32,
+    pub worklogs: Vec<JiraWorklog>
+}
+
+/// The `JiraWorklog` struct represents time logged on a Jira issue. It contains the following fields:
+///
+/// - `id`: The ID of the worklog.
+/// - `started`: When the logged work started.
+/// - `time_spent_seconds`: The time logged, in seconds.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// // Suppose you have a JiraClient named 'client'
+/// let worklogs = client.get_worklogs("DEMO-123").await.unwrap();
+///
+/// let time_spent_seconds: i64 = worklogs.iter()
+///     .map(|worklog| worklog.time_spent_seconds)
+///     .sum();
+/// ```
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraWorklog {
+    pub id: String,
+    pub started: DateTime<Utc>,
+    pub time_spent_seconds: i64
+}
+
+impl Display for JiraWorklog {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match serde_json::to_string_pretty(&self) {
+            Ok(json) => write!(f, "{json}"),
+            Err(error) => panic!("Error serializing Jira worklog: {error}")
+        }
+    }
+}
+
 /// The `JiraIssueFields` struct represents the fields associated with a Jira issue.
 ///
 /// # Example
@@ -141,14 +564,20 @@ impl Display for JiraIssue {
 ///     }
 /// }
 /// ```
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct JiraIssueFields {
     pub summary: String,
     pub description: Option<String>,
     pub comment: Comments,
-    pub created: DateTime<Local>,
-    pub updated: DateTime<Local>
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+
+    #[serde(default, rename = "issuetype", skip_serializing_if = "Option::is_none")]
+    pub issue_type: Option<JiraIssueType>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<JiraStatus>
 }
 
 impl Display for JiraIssueFields {
@@ -160,6 +589,39 @@ impl Display for JiraIssueFields {
     }
 }
 
+/// The `JiraIssueType` struct represents the type of a Jira issue, such as `Bug` or `Story`.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraIssueType {
+    pub name: String
+}
+
+/// The `JiraStatus` struct represents the status of a Jira issue, such as `In Review`, and the category of the
+/// status in the workflow.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraStatus {
+    pub name: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_category: Option<JiraStatusCategory>
+}
+
+/// The `JiraStatusCategory` struct represents the category of a Jira status, which every workflow shares:
+///
+/// - `key`: The key of the category, `new`, `indeterminate` or `done`.
+/// - `name`: The name of the category, such as `To Do`, `In Progress` or `Done`, which may be translated.
+/// - `color_name`: The color Jira displays the category with, such as `blue-gray`, `yellow` or `green`.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraStatusCategory {
+    pub key: String,
+    pub name: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color_name: Option<String>
+}
+
 /// The `Comments` struct represents a collection of comments associated with a Jira issue.
 ///
 /// # Example
@@ -186,7 +648,7 @@ impl Display for JiraIssueFields {
 ///     }
 /// }
 /// ```
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Comments {
     pub comments: Vec<Comment>
@@ -201,13 +663,13 @@ impl Display for Comments {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Comment {
     pub author: JiraAuthor,
     pub body: String,
-    pub created: DateTime<Local>,
-    pub updated: DateTime<Local>
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>
 }
 
 impl Display for Comment {
@@ -245,7 +707,7 @@ impl Display for Comment {
 ///     }
 /// }
 /// ```
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct JiraAuthor {
     pub name: String,
@@ -262,6 +724,26 @@ impl Display for JiraAuthor {
     }
 }
 
+/// The `JiraAttachment` struct represents a file attached to a Jira issue. `content` is the URL the file
+/// can be downloaded from.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraAttachment {
+    pub id: String,
+    pub filename: String,
+    pub size: u64,
+    pub content: String
+}
+
+impl Display for JiraAttachment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match serde_json::to_string_pretty(&self) {
+            Ok(json) => write!(f, "{json}"),
+            Err(error) => panic!("Error serializing Jira attachment: {error}")
+        }
+    }
+}
+
 /// The `JiraClient` struct provides a high-level interface to interact with the Jira REST API. It includes methods for fetching Jira issues and working with their data.
 ///
 /// # Example
@@ -302,6 +784,62 @@ impl JiraClient {
         })
     }
 
+    /// Creates a new `JiraClient` instance with the specified Jira base URL, authenticating with a personal
+    /// access token.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use deployment_changelog::api::jira::JiraClient;
+    ///
+    /// let jira_client = JiraClient::with_token("https://your-jira-instance.com", "personal-access-token").unwrap();
+    /// ```
+    pub fn with_token(base_url: &str, token: &str) -> Result<Self> {
+        Ok(Self {
+            client: RestClient::with_token(base_url, token)?
+        })
+    }
+
+    /// Creates a new `JiraClient` instance with the specified Jira base URL, authenticating with OAuth2 tokens
+    /// obtained with the client credentials flow, such as with a Jira Cloud service account.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use deployment_changelog::api::{jira::JiraClient, rest::{OAuth2ClientCredentials, ATLASSIAN_TOKEN_URL}, secret::SecretString};
+    ///
+    /// let jira_client = JiraClient::with_oauth2("https://api.atlassian.com/ex/jira/my-cloud-id", OAuth2ClientCredentials {
+    ///     token_url: ATLASSIAN_TOKEN_URL.parse().unwrap(),
+    ///     client_id: String::from("client-id"),
+    ///     client_secret: SecretString::from("client-secret"),
+    ///     scope: None
+    /// }).unwrap();
+    /// ```
+    pub fn with_oauth2(base_url: &str, credentials: OAuth2ClientCredentials) -> Result<Self> {
+        Ok(Self {
+            client: RestClient::with_oauth2(base_url, credentials)?
+        })
+    }
+
+    /// Creates a new `JiraClient` instance with the specified Jira base URL, authenticating with the given
+    /// credentials, such as a username and password.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use deployment_changelog::api::{jira::JiraClient, rest::Credentials, secret::SecretString};
+    ///
+    /// let jira_client = JiraClient::with_credentials("https://your-jira-instance.com", Credentials::Basic {
+    ///     username: String::from("username"),
+    ///     password: SecretString::from("password")
+    /// }).unwrap();
+    /// ```
+    pub fn with_credentials(base_url: &str, credentials: Credentials) -> Result<Self> {
+        Ok(Self {
+            client: RestClient::with_credentials(base_url, credentials)?
+        })
+    }
+
     /// Creates a new `JiraClient` instance from an existing `RestClient` instance.
     ///
     /// # Example
@@ -320,7 +858,12 @@ impl JiraClient {
         }
     }
 
-    /// Fetches a Jira issue with the specified issue key.
+    /// Returns the base URL of the Jira server this client talks to.
+    pub fn base_url(&self) -> &Url {
+        &self.client.base_url
+    }
+
+    /// Fetches a Jira issue with the specified issue key, along with its change history.
     ///
     /// # Example
     ///
@@ -342,4 +885,252 @@ impl JiraClient {
 
         self.client.get::<JiraIssue>(&issue_path, None).await
     }
+
+    /// Searches for the Jira issues matching a JQL query, along with their change history.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use deployment_changelog::api::rest::Paginated;
+    ///
+    /// // Suppose you have a JiraClient named 'jira_client'
+    /// let issues = jira_client.search_issues("project = DEMO AND fixVersion = 2024.06").all().await.unwrap();
+    /// ```
+    pub fn search_issues(&self, jql: &str) -> JiraSearchPaginated<'_> {
+        JiraSearchPaginated {
+            client: self,
+            query: HashMap::from([(JiraOptions::Jql.option().to_string(), jql.to_string())]),
+            next_start_at: 0,
+            is_last_page: false
+        }
+    }
+
+    /// Fetches the pull requests linked to a Jira issue in its development panel, using Jira's dev-status API.
+    ///
+    /// # Arguments
+    ///
+    /// * `issue_id` - The numeric ID of the issue, not its key.
+    pub async fn get_dev_status_pull_requests(&self, issue_id: &str) -> Result<Vec<JiraDevStatusPullRequest>> {
+        let dev_status = self.get_dev_status(issue_id, "pullrequest").await?;
+
+        Ok(dev_status.detail
+            .into_iter()
+            .flat_map(|detail| detail.pull_requests)
+            .collect())
+    }
+
+    /// Fetches the commits linked to a Jira issue in its development panel, using Jira's dev-status API.
+    ///
+    /// # Arguments
+    ///
+    /// * `issue_id` - The numeric ID of the issue, not its key.
+    pub async fn get_dev_status_commits(&self, issue_id: &str) -> Result<Vec<JiraDevStatusCommit>> {
+        let dev_status = self.get_dev_status(issue_id, "repository").await?;
+
+        Ok(dev_status.detail
+            .into_iter()
+            .flat_map(|detail| detail.repositories)
+            .flat_map(|repository| repository.commits)
+            .collect())
+    }
+
+    async fn get_dev_status(&self, issue_id: &str, data_type: &str) -> Result<JiraDevStatus> {
+        let dev_status_path: String = JiraEndpoints::DevStatusDetail.url()
+            .replace("{issueId}", issue_id)
+            .replace("{applicationType}", DEV_STATUS_APPLICATION_TYPE)
+            .replace("{dataType}", data_type);
+
+        self.client.get::<JiraDevStatus>(&dev_status_path, None).await
+    }
+
+    /// Fetches the permissions the authenticated user has on a project.
+    ///
+    /// # Arguments
+    ///
+    /// * `project_key` - The key of the project, such as `DEMO`.
+    /// * `permissions` - The keys of the permissions to fetch, such as `BROWSE_PROJECTS`.
+    pub async fn get_my_permissions(&self, project_key: &str, permissions: &[&str]) -> Result<JiraPermissions> {
+        let permissions_path: String = JiraEndpoints::MyPermissions.url()
+            .replace("{projectKey}", project_key)
+            .replace("{permissions}", &permissions.join(","));
+
+        self.client.get::<JiraPermissions>(&permissions_path, None).await
+    }
+
+    /// Fetches the work logged on a Jira issue, following the pages of its worklogs.
+    ///
+    /// # Arguments
+    ///
+    /// * `issue_key` - The key of the issue, such as `DEMO-123`.
+    pub async fn get_worklogs(&self, issue_key: &str) -> Result<Vec<JiraWorklog>> {
+        let worklogs_path: String = JiraEndpoints::Worklogs.url()
+            .replace("{issueKey}", issue_key);
+
+        let mut worklogs: Vec<JiraWorklog> = Vec::new();
+
+        loop {
+            let query = HashMap::from([(JiraOptions::StartAt.option().to_string(), worklogs.len().to_string())]);
+
+            let page = self.client.get::<JiraWorklogs>(&worklogs_path, Some(&query))
+                .await
+                .with_context(|| format!("Error fetching the worklogs of {issue_key}"))?;
+
+            let is_last_page = page.worklogs.is_empty() || worklogs.len() + page.worklogs.len() >= page.total as usize;
+            worklogs.extend(page.worklogs);
+
+            if is_last_page {
+                return Ok(worklogs);
+            }
+        }
+    }
+
+    /// Attaches a file to a Jira issue, such as a rendered changelog attached to a release ticket.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Suppose you have a JiraClient named 'jira_client'
+    /// let attachments = jira_client.add_attachment("REL-42", "changelog.md", "text/markdown", b"# Changelog".to_vec()).await.unwrap();
+    /// println!("Uploaded to {}", attachments[0].content);
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `issue_key` - The key of the issue to attach the file to.
+    /// * `file_name` - The name of the attached file.
+    /// * `mime_type` - The MIME type of the file.
+    /// * `contents` - The contents of the file.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the created `JiraAttachment`s or an error if the upload fails.
+    pub async fn add_attachment(&self, issue_key: &str, file_name: &str, mime_type: &str, contents: Vec<u8>) -> Result<Vec<JiraAttachment>> {
+        let attachments_path: String = JiraEndpoints::Attachments.url()
+            .replace("{issueKey}", issue_key);
+
+        let part = Part::bytes(contents)
+            .file_name(file_name.to_string())
+            .mime_str(mime_type)?;
+
+        let mut headers = HeaderMap::with_capacity(1);
+        headers.insert(ATLASSIAN_TOKEN_HEADER, HeaderValue::from_static("no-check"));
+
+        self.client.post_multipart::<Vec<JiraAttachment>>(&attachments_path, Form::new().part("file", part), headers)
+            .await
+            .with_context(|| format!("Error attaching {file_name} to Jira issue {issue_key}"))
+    }
+}
+
+/// The `JiraProvider` trait provides the Jira operations needed to generate a changelog. `JiraClient` implements it
+/// against a Jira server, and applications embedding the crate can implement it to inject mocks, or decorators
+/// such as an extra cache, as a `&dyn JiraProvider` or an `Arc<dyn JiraProvider>`.
+///
+/// # Example
+///
+/// ```rust
+/// use deployment_changelog::api::jira::JiraProvider;
+///
+/// async fn print_issue_summary(jira_provider: &dyn JiraProvider) {
+///     let issue = jira_provider.issue("DEMO-123").await.unwrap();
+///     println!("{}: {}", issue.key, issue.fields.summary);
+/// }
+/// ```
+#[async_trait::async_trait]
+pub trait JiraProvider: Send + Sync {
+    /// Returns the base URL of the Jira server.
+    fn base_url(&self) -> &Url;
+
+    /// Fetches the issue with the given key, along with its change history.
+    async fn issue(&self, issue_key: &str) -> Result<JiraIssue>;
+
+    /// Fetches every issue matching a JQL query, along with their change history.
+    async fn search(&self, jql: &str) -> Result<Vec<JiraIssue>>;
+
+    /// Fetches the pull requests linked to an issue in its development panel.
+    ///
+    /// # Arguments
+    ///
+    /// * `issue_id` - The numeric ID of the issue, not its key.
+    async fn pull_requests_for_issue(&self, issue_id: &str) -> Result<Vec<JiraDevStatusPullRequest>>;
+
+    /// Fetches the commits linked to an issue in its development panel.
+    ///
+    /// # Arguments
+    ///
+    /// * `issue_id` - The numeric ID of the issue, not its key.
+    async fn commits_for_issue(&self, issue_id: &str) -> Result<Vec<JiraDevStatusCommit>>;
+
+    /// Checks that the issues of the given projects can be viewed before they are fetched, so that missing
+    /// permissions fail with a single error instead of one per issue. Providers that can't check permissions
+    /// don't need to implement it.
+    ///
+    /// # Arguments
+    ///
+    /// * `project_keys` - The keys of the projects, such as `DEMO`.
+    async fn check_browse_permission(&self, _project_keys: &[String]) -> Result<()> {
+        Ok(())
+    }
+
+    /// Fetches the work logged on an issue. Providers that can't fetch worklogs return an error, which is the
+    /// default.
+    ///
+    /// # Arguments
+    ///
+    /// * `issue_key` - The key of the issue, such as `DEMO-123`.
+    async fn worklogs(&self, issue_key: &str) -> Result<Vec<JiraWorklog>> {
+        bail!("The Jira server at {} can't fetch the worklogs of {issue_key}", self.base_url())
+    }
+}
+
+#[async_trait::async_trait]
+impl JiraProvider for JiraClient {
+    fn base_url(&self) -> &Url {
+        &self.client.base_url
+    }
+
+    async fn issue(&self, issue_key: &str) -> Result<JiraIssue> {
+        self.get_issue(issue_key).await
+    }
+
+    async fn search(&self, jql: &str) -> Result<Vec<JiraIssue>> {
+        self.search_issues(jql)
+            .all()
+            .await
+    }
+
+    async fn pull_requests_for_issue(&self, issue_id: &str) -> Result<Vec<JiraDevStatusPullRequest>> {
+        self.get_dev_status_pull_requests(issue_id).await
+    }
+
+    async fn commits_for_issue(&self, issue_id: &str) -> Result<Vec<JiraDevStatusCommit>> {
+        self.get_dev_status_commits(issue_id).await
+    }
+
+    async fn worklogs(&self, issue_key: &str) -> Result<Vec<JiraWorklog>> {
+        self.get_worklogs(issue_key).await
+    }
+
+    async fn check_browse_permission(&self, project_keys: &[String]) -> Result<()> {
+        let project_permissions = try_join_all(project_keys.iter()
+            .map(|project_key| async move {
+                self.get_my_permissions(project_key, &[BROWSE_PROJECTS_PERMISSION])
+                    .await
+                    .with_context(|| format!("Error checking the Jira permissions on project {project_key}"))
+            }))
+            .await?;
+
+        // A server that leaves the permission out of its answer is given the benefit of the doubt.
+        let forbidden_projects: Vec<&str> = project_keys.iter()
+            .zip(project_permissions)
+            .filter(|(_, project_permissions)| project_permissions.permissions.get(BROWSE_PROJECTS_PERMISSION)
+                .is_some_and(|permission| !permission.have_permission))
+            .map(|(project_key, _)| project_key.as_str())
+            .collect();
+
+        match forbidden_projects.as_slice() {
+            [] => Ok(()),
+            [project_key] => bail!("The Jira credentials lack browse permission on project {project_key}"),
+            project_keys => bail!("The Jira credentials lack browse permission on projects {}", project_keys.join(", "))
+        }
+    }
 }
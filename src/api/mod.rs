@@ -1,6 +1,29 @@
 //! A module for providing easy-to-use clients to deal with external APIs
 pub mod rest;
+pub mod secret;
+pub mod cache;
+pub mod retry;
+pub mod priority;
+pub mod rate_limit;
+pub mod netrc;
+pub(crate) mod hmac;
+#[cfg(feature = "aws-sigv4")]
+pub mod sigv4;
+#[cfg(feature = "spnego")]
+pub mod spnego;
+#[cfg(feature = "fault-injection")]
+pub mod fault;
 pub mod bitbucket;
 pub mod jira;
+#[cfg(feature = "spinnaker")]
 pub mod graphql;
+#[cfg(feature = "spinnaker")]
 pub mod spinnaker;
+pub mod scm;
+pub mod gerrit;
+pub mod azure_devops;
+pub mod tracker;
+pub mod trello;
+pub mod asana;
+pub mod confluence;
+pub mod suggest;
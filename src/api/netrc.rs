@@ -0,0 +1,236 @@
+//! The `deployment_changelog::api::netrc` module reads credentials from a `.netrc` file, keyed by host, so that
+//! the tool works where the tokens of the servers are already managed there, such as for Git or curl.
+//!
+//! The file is read from the path in the `NETRC` environment variable, or `~/.netrc` (`%USERPROFILE%\_netrc` on
+//! Windows). A `RestClientBuilder` without `Credentials` looks up the host of its base URL when it is built.
+//!
+//! # Example
+//!
+//! ```rust
+//! use deployment_changelog::api::netrc::Netrc;
+//!
+//! let netrc = Netrc::parse("machine bitbucket.example.com login deployer password http-access-token");
+//! let credentials = netrc.credentials("bitbucket.example.com");
+//! ```
+use std::{collections::HashMap, env, fs, io::ErrorKind, path::PathBuf};
+
+use anyhow::{Context, Result};
+use reqwest::Url;
+
+use super::{rest::Credentials, secret::SecretString};
+
+/// The `NetrcEntry` struct holds the `login` and `password` of a `machine` or `default` entry of a `.netrc` file.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NetrcEntry {
+    pub login: Option<String>,
+    pub password: Option<SecretString>
+}
+
+impl NetrcEntry {
+    /// Returns the credentials of the entry: basic authentication with a login and password, or a bearer token
+    /// with a password alone.
+    fn credentials(&self) -> Option<Credentials> {
+        let password = self.password.clone()?;
+
+        Some(match &self.login {
+            Some(login) => Credentials::Basic {
+                username: login.clone(),
+                password
+            },
+            None => Credentials::Bearer(password)
+        })
+    }
+}
+
+/// The `Netrc` struct holds the entries of a `.netrc` file, keyed by host.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Netrc {
+    machines: HashMap<String, NetrcEntry>,
+    default: Option<NetrcEntry>
+}
+
+impl Netrc {
+    /// Parses the contents of a `.netrc` file. The `account` of entries and the `macdef` macros are ignored, and
+    /// the first entry of a host wins, like with curl.
+    ///
+    /// # Arguments
+    ///
+    /// * `contents` - The contents of the file.
+    ///
+    /// # Returns
+    ///
+    /// The parsed `Netrc`.
+    pub fn parse(contents: &str) -> Self {
+        let mut netrc = Netrc::default();
+        let mut entry: Option<(Option<String>, NetrcEntry)> = None;
+
+        let mut lines = contents.lines();
+        while let Some(line) = lines.next() {
+            let mut tokens = line.split_whitespace()
+                .take_while(|token| !token.starts_with('#'));
+
+            while let Some(token) = tokens.next() {
+                match token {
+                    "machine" | "default" => {
+                        netrc.insert(entry.take());
+
+                        let machine = if token == "machine" { tokens.next().map(str::to_lowercase) } else { None };
+                        entry = Some((machine, NetrcEntry::default()));
+                    },
+                    "login" => if let Some((_, entry)) = &mut entry {
+                        entry.login = tokens.next().map(str::to_string);
+                    },
+                    "password" => if let Some((_, entry)) = &mut entry {
+                        entry.password = tokens.next().map(SecretString::from);
+                    },
+                    "account" => {
+                        tokens.next();
+                    },
+                    "macdef" => {
+                        // A macro runs until the next empty line.
+                        lines.by_ref()
+                            .find(|line| line.trim().is_empty());
+
+                        break;
+                    },
+                    _ => ()
+                }
+            }
+        }
+
+        netrc.insert(entry);
+        netrc
+    }
+
+    /// Loads the `.netrc` file of the user, from the path in the `NETRC` environment variable or from their home
+    /// directory.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the `Netrc`, `None` if there is no file, or an error if the file can't be read.
+    pub fn load() -> Result<Option<Self>> {
+        let path = match netrc_path() {
+            Some(path) => path,
+            None => return Ok(None)
+        };
+
+        match fs::read_to_string(&path) {
+            Ok(contents) => Ok(Some(Netrc::parse(&contents))),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error).with_context(|| format!("Error reading {}", path.display()))
+        }
+    }
+
+    /// Returns the credentials of a host, from its `machine` entry or else from the `default` entry.
+    ///
+    /// # Arguments
+    ///
+    /// * `host` - The host name, such as `bitbucket.example.com`.
+    pub fn credentials(&self, host: &str) -> Option<Credentials> {
+        self.machines.get(&host.to_lowercase())
+            .or(self.default.as_ref())
+            .and_then(NetrcEntry::credentials)
+    }
+
+    fn insert(&mut self, entry: Option<(Option<String>, NetrcEntry)>) {
+        match entry {
+            Some((Some(machine), entry)) => {
+                self.machines.entry(machine).or_insert(entry);
+            },
+            Some((None, entry)) => {
+                self.default.get_or_insert(entry);
+            },
+            None => ()
+        }
+    }
+}
+
+/// Looks up the credentials of the host of a URL in the `.netrc` file of the user. A file that can't be read is
+/// logged and skipped, so that requests are sent without credentials as they would be without the file.
+pub(crate) fn netrc_credentials(url: &Url) -> Option<Credentials> {
+    let host = url.host_str()?;
+
+    let netrc = match Netrc::load() {
+        Ok(netrc) => netrc?,
+        Err(error) => {
+            log::warn!("Skipping the .netrc file: {error:#}");
+            return None;
+        }
+    };
+
+    let credentials = netrc.credentials(host)?;
+    log::debug!("Authenticating the requests to {host} with the credentials of the .netrc file");
+
+    Some(credentials)
+}
+
+fn netrc_path() -> Option<PathBuf> {
+    if let Some(path) = env::var_os("NETRC") {
+        return Some(PathBuf::from(path));
+    }
+
+    if cfg!(windows) {
+        env::var_os("USERPROFILE").map(|home| PathBuf::from(home).join("_netrc"))
+    } else {
+        env::var_os("HOME").map(|home| PathBuf::from(home).join(".netrc"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn basic(username: &str, password: &str) -> Option<Credentials> {
+        Some(Credentials::Basic {
+            username: username.to_string(),
+            password: SecretString::from(password)
+        })
+    }
+
+    #[test]
+    fn default_entry_applies_to_hosts_without_a_machine_entry() {
+        let netrc = Netrc::parse("machine bitbucket.example.com login deployer password token\ndefault login anonymous password guest");
+
+        assert_eq!(netrc.credentials("bitbucket.example.com"), basic("deployer", "token"));
+        assert_eq!(netrc.credentials("jira.example.com"), basic("anonymous", "guest"));
+    }
+
+    #[test]
+    fn macdef_runs_until_the_next_blank_line() {
+        let netrc = Netrc::parse("macdef init\nmachine ignored.example.com login macro password secret\n\nmachine bitbucket.example.com login deployer password token");
+
+        assert_eq!(netrc.credentials("ignored.example.com"), None);
+        assert_eq!(netrc.credentials("bitbucket.example.com"), basic("deployer", "token"));
+    }
+
+    #[test]
+    fn comments_are_skipped() {
+        let netrc = Netrc::parse("# machine commented.example.com login nobody password nothing\nmachine bitbucket.example.com login deployer password token # password other");
+
+        assert_eq!(netrc.credentials("commented.example.com"), None);
+        assert_eq!(netrc.credentials("bitbucket.example.com"), basic("deployer", "token"));
+    }
+
+    #[test]
+    fn account_is_skipped() {
+        let netrc = Netrc::parse("machine bitbucket.example.com account login login deployer password token");
+
+        assert_eq!(netrc.credentials("bitbucket.example.com"), basic("deployer", "token"));
+    }
+
+    #[test]
+    fn first_entry_of_a_host_wins() {
+        let netrc = Netrc::parse("machine Bitbucket.example.com login first password one\nmachine bitbucket.example.com login second password two\ndefault password three\ndefault password four");
+
+        assert_eq!(netrc.credentials("bitbucket.example.com"), basic("first", "one"));
+        assert_eq!(netrc.credentials("jira.example.com"), Some(Credentials::Bearer(SecretString::from("three"))));
+    }
+
+    #[test]
+    fn password_without_login_is_a_bearer_token() {
+        let netrc = Netrc::parse("machine jira.example.com password personal-access-token\nmachine confluence.example.com login nobody");
+
+        assert_eq!(netrc.credentials("jira.example.com"), Some(Credentials::Bearer(SecretString::from("personal-access-token"))));
+        assert_eq!(netrc.credentials("confluence.example.com"), None);
+    }
+}
@@ -0,0 +1,63 @@
+//! The `deployment_changelog::api::priority` module classifies the requests of a `RestClient` by
+//! `RequestPriority`, so that the requests a changelog can't be generated without, such as resolving its commit
+//! range, can be sent with a shorter timeout and more retries than the bulk of the requests enriching it, and a
+//! slow Jira server doesn't stall the resolution of the range.
+//!
+//! Requests are tagged by the future they are sent from, with `with_priority`, instead of by every client method.
+//! A `RestClient` sends the requests of a priority with the `PriorityPolicy` of that priority, if it has one, and
+//! the other requests with its own timeout and `RetryPolicy`.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use std::time::Duration;
+//! use deployment_changelog::api::{jira::JiraClient, priority::{with_priority, PriorityPolicy, RequestPriority}, rest::RestClient, retry::RetryPolicy};
+//!
+//! let mut rest_client_builder = RestClient::builder("https://jira.example.com").unwrap();
+//! rest_client_builder.priority_policies.insert(RequestPriority::Bulk, PriorityPolicy {
+//!     timeout: Some(Duration::from_secs(30)),
+//!     retry_policy: RetryPolicy { max_retries: 0, ..RetryPolicy::default() }
+//! });
+//!
+//! let jira_client = JiraClient::from_client(rest_client_builder.build().unwrap());
+//! let issue = with_priority(RequestPriority::Bulk, jira_client.get_issue("DEMO-123")).await.unwrap();
+//! ```
+use std::{future::Future, time::Duration};
+
+use super::retry::RetryPolicy;
+
+tokio::task_local! {
+    static REQUEST_PRIORITY: RequestPriority;
+}
+
+/// The `RequestPriority` enum lists the classes of requests of a changelog.
+///
+/// - `Critical`: The requests the changelog can't be generated without, such as resolving its commit range.
+/// - `Bulk`: The many requests enriching the changelog, such as fetching its Jira issues.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RequestPriority {
+    Critical,
+    Bulk
+}
+
+/// The `PriorityPolicy` struct overrides how a `RestClient` sends the requests of a `RequestPriority`. It
+/// contains the following fields:
+///
+/// - `timeout`: The timeout of every attempt of a request, or `None` for the timeout of the client.
+/// - `retry_policy`: The `RetryPolicy` the requests are retried with, within the retry budget of the client.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PriorityPolicy {
+    pub timeout: Option<Duration>,
+    pub retry_policy: RetryPolicy
+}
+
+/// Runs a future, sending the requests it makes with the given priority, unless a future it awaits tags them with
+/// another one.
+pub async fn with_priority<F: Future>(priority: RequestPriority, future: F) -> F::Output {
+    REQUEST_PRIORITY.scope(priority, future).await
+}
+
+/// Returns the priority of the requests sent by the current task, if it is running a future of `with_priority`.
+pub(crate) fn current_priority() -> Option<RequestPriority> {
+    REQUEST_PRIORITY.try_with(|priority| *priority).ok()
+}
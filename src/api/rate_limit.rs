@@ -0,0 +1,98 @@
+//! The `deployment_changelog::api::rate_limit` module throttles the requests of a `RestClient` with a token bucket,
+//! so that the requests fanned out for every commit and pull request of a changelog don't trip the rate limits of
+//! a server, such as those of Bitbucket Data Center.
+//!
+//! The bucket holds up to a second's worth of requests, and is refilled continuously at the configured rate. Every
+//! attempt of a request, retries included, takes a token, waiting for the bucket to refill when it is empty. A
+//! `RateLimiter` can be shared by the clients of the same server, so that they are throttled together.
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::sync::Arc;
+//! use deployment_changelog::api::{bitbucket::BitbucketClient, rate_limit::RateLimiter, rest::RestClient};
+//!
+//! let mut rest_client_builder = RestClient::builder("https://bitbucket.example.com").unwrap();
+//! rest_client_builder.rate_limiter = Some(Arc::new(RateLimiter::new(10.0).unwrap()));
+//!
+//! let bitbucket_client = BitbucketClient::from_client(rest_client_builder.build().unwrap());
+//! ```
+use std::{sync::Mutex, time::Duration};
+
+use anyhow::{bail, Result};
+use tokio::time::Instant;
+
+/// The `RateLimiter` struct is a token bucket limiting the rate of the requests of the `RestClient`s sharing it.
+#[derive(Debug)]
+pub struct RateLimiter {
+    requests_per_second: f64,
+    capacity: f64,
+    bucket: Mutex<Bucket>
+}
+
+/// The tokens left in a `RateLimiter`, which are negative when requests are waiting for the bucket to refill, and
+/// when they were last counted.
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    refilled_at: Instant
+}
+
+impl RateLimiter {
+    /// Creates a new `RateLimiter` allowing `requests_per_second` requests per second, in bursts of up to a
+    /// second's worth of requests, and at least one.
+    ///
+    /// # Arguments
+    ///
+    /// * `requests_per_second` - The sustained rate of requests, which must be positive.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the `RateLimiter`, or an error if the rate isn't positive.
+    pub fn new(requests_per_second: f64) -> Result<Self> {
+        if !requests_per_second.is_finite() || requests_per_second <= 0.0 {
+            bail!("Invalid rate limit of {requests_per_second} requests per second, expected a positive number");
+        }
+
+        let capacity = requests_per_second.max(1.0);
+
+        Ok(Self {
+            requests_per_second,
+            capacity,
+            bucket: Mutex::new(Bucket {
+                tokens: capacity,
+                refilled_at: Instant::now()
+            })
+        })
+    }
+
+    /// Returns the sustained rate of requests of the limiter.
+    pub fn requests_per_second(&self) -> f64 {
+        self.requests_per_second
+    }
+
+    /// Takes a token from the bucket, waiting until the bucket has refilled enough if it is empty. Tokens are
+    /// handed out in the order they are asked for, since every request waits behind the ones before it.
+    pub async fn acquire(&self) {
+        let wait = {
+            let mut bucket = self.bucket.lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+            let now = Instant::now();
+            let elapsed = now.duration_since(bucket.refilled_at).as_secs_f64();
+
+            bucket.tokens = (bucket.tokens + elapsed * self.requests_per_second).min(self.capacity) - 1.0;
+            bucket.refilled_at = now;
+
+            match bucket.tokens < 0.0 {
+                true => Duration::from_secs_f64(-bucket.tokens / self.requests_per_second),
+                false => Duration::ZERO
+            }
+        };
+
+        if !wait.is_zero() {
+            log::debug!("Waiting {} ms for the rate limit", wait.as_millis());
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
@@ -0,0 +1,192 @@
+//! The `deployment_changelog::api::retry` module provides the retry settings of a `RestClient`: a `RetryPolicy`
+//! deciding how many times a request failing with a transient error is retried, and a `RetryBudget` capping the
+//! retries of every client sharing it, so that a degraded server isn't flooded with retries.
+//!
+//! The `RetryBudget` also records the requests that were retried or failed with a transient error, so that they can
+//! be summarized at the end of a run instead of being interleaved with the logs.
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::sync::Arc;
+//! use deployment_changelog::api::{jira::JiraClient, rest::RestClient, retry::RetryBudget};
+//!
+//! let retry_budget = Arc::new(RetryBudget::new(50));
+//!
+//! let mut rest_client_builder = RestClient::builder("https://jira.example.com").unwrap();
+//! rest_client_builder.retry_budget = Some(retry_budget.clone());
+//!
+//! let jira_client = JiraClient::from_client(rest_client_builder.build().unwrap());
+//!
+//! // After using the client
+//! for retried_request in retry_budget.retried_requests() {
+//!     eprintln!("{retried_request}");
+//! }
+//! ```
+use std::{collections::hash_map::RandomState, fmt::Display, hash::BuildHasher, sync::{atomic::{AtomicUsize, Ordering}, Mutex}, time::Duration};
+
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+
+/// The HTTP statuses of responses that are retried by default, because the server may answer the same request
+/// successfully later.
+pub const RETRIABLE_STATUSES: [StatusCode; 5] = [
+    StatusCode::TOO_MANY_REQUESTS,
+    StatusCode::INTERNAL_SERVER_ERROR,
+    StatusCode::BAD_GATEWAY,
+    StatusCode::SERVICE_UNAVAILABLE,
+    StatusCode::GATEWAY_TIMEOUT
+];
+
+/// The `RetryPolicy` struct decides how a `RestClient` retries requests failing with a timeout, a connection
+/// error, or a retriable status. It contains the following fields:
+///
+/// - `max_retries`: The maximum number of times a request is retried.
+/// - `initial_backoff`: The delay before the first retry, which doubles with every retry.
+/// - `max_backoff`: The longest delay between two retries, however many retries came before.
+/// - `jitter`: Whether every delay is drawn at random between half of it and all of it, so that the requests
+///   failing together, such as when a server restarts, aren't all retried at the same time.
+/// - `retriable_statuses`: The statuses of the responses that are retried, `RETRIABLE_STATUSES` by default.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+/// use deployment_changelog::api::{rest::RestClient, retry::RetryPolicy};
+/// use reqwest::StatusCode;
+///
+/// let mut rest_client_builder = RestClient::builder("https://bitbucket.example.com").unwrap();
+/// rest_client_builder.retry_policy = RetryPolicy {
+///     max_retries: 5,
+///     initial_backoff: Duration::from_secs(1),
+///     retriable_statuses: vec![StatusCode::BAD_GATEWAY, StatusCode::SERVICE_UNAVAILABLE],
+///     ..RetryPolicy::default()
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub jitter: bool,
+    pub retriable_statuses: Vec<StatusCode>
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 2,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            jitter: true,
+            retriable_statuses: RETRIABLE_STATUSES.to_vec()
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Returns whether a response with the given status is worth retrying.
+    pub fn is_retriable_status(&self, status: StatusCode) -> bool {
+        self.retriable_statuses.contains(&status)
+    }
+
+    /// Returns the delay before the given retry, counted from 1: the initial backoff doubled for every earlier
+    /// retry, capped by the maximum backoff, and drawn between half of it and all of it with jitter.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let backoff = self.initial_backoff
+            .checked_mul(2u32.saturating_pow(retry.saturating_sub(1)))
+            .unwrap_or(self.max_backoff)
+            .min(self.max_backoff);
+
+        match self.jitter {
+            // The hasher of a new `RandomState` is seeded at random, which is enough to spread retries out.
+            true => backoff.mul_f64(0.5 + RandomState::new().hash_one(retry) as f64 / u64::MAX as f64 / 2.0),
+            false => backoff
+        }
+    }
+}
+
+/// The `RetriedRequest` struct is a request that was retried or failed. It contains the following fields:
+///
+/// - `method`: The HTTP method of the request.
+/// - `url`: The URL of the request.
+/// - `attempts`: The number of times the request was sent.
+/// - `error`: The error of the last attempt, or `None` if the request eventually succeeded.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RetriedRequest {
+    pub method: String,
+    pub url: String,
+    pub attempts: u32,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>
+}
+
+impl Display for RetriedRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let attempts = match self.attempts {
+            1 => String::from("1 attempt"),
+            attempts => format!("{attempts} attempts")
+        };
+
+        match &self.error {
+            Some(error) => write!(f, "{} {}: failed after {attempts}: {error}", self.method, self.url),
+            None => write!(f, "{} {}: succeeded after {attempts}", self.method, self.url)
+        }
+    }
+}
+
+/// The `RetryBudget` struct caps the number of retries of the `RestClient`s sharing it, and records the requests
+/// they retried or that failed with a transient error.
+#[derive(Debug)]
+pub struct RetryBudget {
+    max_retries: usize,
+    retries: AtomicUsize,
+    retried_requests: Mutex<Vec<RetriedRequest>>
+}
+
+impl RetryBudget {
+    /// Creates a new `RetryBudget` allowing `max_retries` retries in total.
+    pub fn new(max_retries: usize) -> Self {
+        Self {
+            max_retries,
+            retries: AtomicUsize::new(0),
+            retried_requests: Mutex::new(Vec::new())
+        }
+    }
+
+    /// Spends a retry from the budget, returning `false` if the budget is exhausted.
+    pub(crate) fn try_spend(&self) -> bool {
+        self.retries.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |retries| (retries < self.max_retries).then_some(retries + 1))
+            .is_ok()
+    }
+
+    pub(crate) fn record(&self, retried_request: RetriedRequest) {
+        self.lock().push(retried_request);
+    }
+
+    /// Returns the requests that were retried or failed, in the order they completed.
+    pub fn retried_requests(&self) -> Vec<RetriedRequest> {
+        self.lock().clone()
+    }
+
+    /// Returns whether every retry of the budget was spent.
+    pub fn is_exhausted(&self) -> bool {
+        self.retries.load(Ordering::SeqCst) >= self.max_retries
+    }
+
+    /// Refills the budget and forgets the recorded requests, such as before generating another changelog in
+    /// the same process.
+    pub fn reset(&self) {
+        self.retries.store(0, Ordering::SeqCst);
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<RetriedRequest>> {
+        match self.retried_requests.lock() {
+            Ok(retried_requests) => retried_requests,
+            Err(poisoned) => poisoned.into_inner()
+        }
+    }
+}
@@ -0,0 +1,531 @@
+//! The `deployment_changelog::api::scm` module defines the `ScmProvider` trait, which abstracts over the
+//! source control systems a changelog can be generated from.
+//!
+//! The changelog pipeline only needs three operations from a source control system: listing the commits
+//! in a range, finding the reviews (pull requests, changes, ...) a commit belongs to, and finding the issues
+//! linked to a review. Each backend implements these operations and maps its native representations onto the
+//! commit, pull request and issue link models used by `Changelog`, which are shaped after the Bitbucket API.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use deployment_changelog::api::{bitbucket::BitbucketClient, gerrit::GerritClient, scm::ScmProvider};
+//!
+//! let scm_provider: Box<dyn ScmProvider> = if use_gerrit {
+//!     Box::new(GerritClient::new("https://gerrit.example.com").unwrap())
+//! } else {
+//!     Box::new(BitbucketClient::new("https://bitbucket.example.com").unwrap())
+//! };
+//!
+//! let commits = scm_provider.commits_between("PROJECT", "my-repo", "abcdef", "123456").await.unwrap();
+//! ```
+use std::collections::HashMap;
+
+use anyhow::{bail, Result};
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+
+use super::bitbucket::{BitbucketCommit, BitbucketPullRequest, BitbucketPullRequestIssue, BitbucketPullRequestSize};
+
+/// The `ScmProvider` trait provides the source control operations needed to generate a changelog.
+///
+/// Implementations map their native commit, review and issue link representations onto the
+/// `BitbucketCommit`, `BitbucketPullRequest` and `BitbucketPullRequestIssue` models, so the rest of the
+/// pipeline and the serialized changelog stay identical regardless of the backend.
+///
+/// # Example
+///
+/// ```rust
+/// use deployment_changelog::api::{bitbucket::BitbucketClient, scm::ScmProvider};
+///
+/// async fn print_pull_requests(scm_provider: &dyn ScmProvider) {
+///     let commits = scm_provider.commits_between("PROJECT", "my-repo", "abcdef", "123456").await.unwrap();
+///
+///     for commit in commits {
+///         for pull_request in scm_provider.pull_requests_for_commit("PROJECT", "my-repo", &commit.id).await.unwrap() {
+///             println!("{} belongs to #{}", commit.display_id, pull_request.id);
+///         }
+///     }
+/// }
+/// ```
+#[async_trait::async_trait]
+pub trait ScmProvider: Send + Sync {
+    /// Returns the base URL of the source control server.
+    fn base_url(&self) -> &Url;
+
+    /// Fetches the commits reachable from `start_commit` but not from `end_commit` in the given repository.
+    ///
+    /// # Arguments
+    ///
+    /// * `project` - The project the repository belongs to.
+    /// * `repo` - The repository name.
+    /// * `start_commit` - The most recent commit of the range.
+    /// * `end_commit` - The oldest commit of the range, which is excluded from the result.
+    async fn commits_between(&self, project: &str, repo: &str, start_commit: &str, end_commit: &str) -> Result<Vec<BitbucketCommit>>;
+
+    /// Fetches the pull requests (or equivalent reviews) that contain the given commit.
+    ///
+    /// # Arguments
+    ///
+    /// * `project` - The project the repository belongs to.
+    /// * `repo` - The repository name.
+    /// * `commit` - The commit ID to fetch the pull requests for.
+    async fn pull_requests_for_commit(&self, project: &str, repo: &str, commit: &str) -> Result<Vec<BitbucketPullRequest>>;
+
+    /// Fetches the issues linked to the given pull request (or equivalent review).
+    ///
+    /// # Arguments
+    ///
+    /// * `project` - The project the repository belongs to.
+    /// * `repo` - The repository name.
+    /// * `pull_request_id` - The ID of the pull request to fetch the issues for.
+    async fn issues_for_pull_request(&self, project: &str, repo: &str, pull_request_id: u64) -> Result<Vec<BitbucketPullRequestIssue>>;
+
+    /// Fetches the paths of the files changed between `end_commit` and `start_commit` in the given repository.
+    ///
+    /// # Arguments
+    ///
+    /// * `project` - The project the repository belongs to.
+    /// * `repo` - The repository name.
+    /// * `start_commit` - The most recent commit of the range.
+    /// * `end_commit` - The oldest commit of the range.
+    async fn changed_files(&self, project: &str, repo: &str, start_commit: &str, end_commit: &str) -> Result<Vec<String>>;
+
+    /// Fetches the lines added and removed between `end_commit` and `start_commit` in the given repository.
+    ///
+    /// Providers that can't fetch diffs return no lines, which is the default.
+    ///
+    /// # Arguments
+    ///
+    /// * `project` - The project the repository belongs to.
+    /// * `repo` - The repository name.
+    /// * `start_commit` - The most recent commit of the range.
+    /// * `end_commit` - The oldest commit of the range.
+    async fn changed_lines(&self, _project: &str, _repo: &str, _start_commit: &str, _end_commit: &str) -> Result<Vec<DiffLine>> {
+        Ok(Vec::new())
+    }
+
+    /// Resolves a tag to the ID of the commit it points to, peeling annotated tags.
+    ///
+    /// # Arguments
+    ///
+    /// * `project` - The project the repository belongs to.
+    /// * `repo` - The repository name.
+    /// * `tag` - The name of the tag, without the `refs/tags/` prefix.
+    async fn resolve_tag(&self, project: &str, repo: &str, tag: &str) -> Result<String>;
+
+    /// Resolves a branch, a tag or an abbreviated commit ID to the full ID of the commit it points to, or `None`
+    /// if it doesn't exist in the repository.
+    ///
+    /// Providers that can't resolve references return them unchanged, which is the default.
+    ///
+    /// # Arguments
+    ///
+    /// * `project` - The project the repository belongs to.
+    /// * `repo` - The repository name.
+    /// * `reference` - The branch, tag or commit ID to resolve.
+    async fn resolve_ref(&self, _project: &str, _repo: &str, reference: &str) -> Result<Option<String>> {
+        Ok(Some(reference.to_string()))
+    }
+
+    /// Computes the merge base of two commits, the best common ancestor that a range is compared against when
+    /// comparing two diverged branches.
+    ///
+    /// Providers that can't compute merge bases return an error, which is the default.
+    ///
+    /// # Arguments
+    ///
+    /// * `project` - The project the repository belongs to.
+    /// * `repo` - The repository name.
+    /// * `first_commit` and `second_commit` - The IDs of the commits.
+    async fn merge_base(&self, _project: &str, _repo: &str, _first_commit: &str, _second_commit: &str) -> Result<String> {
+        bail!("The source control server at {} can't compute merge bases", self.base_url())
+    }
+
+    /// Finds the most recent commit of a branch authored before a time, which ends the commits of the branch within
+    /// a time window. The commits are walked from the tip of the branch, so commits authored out of order, such as
+    /// rebased ones, are counted by their position in the branch.
+    ///
+    /// Providers that can't list the commits of a branch return an error, which is the default.
+    ///
+    /// # Arguments
+    ///
+    /// * `project` - The project the repository belongs to.
+    /// * `repo` - The repository name.
+    /// * `branch` - The branch to walk, or `None` for the default branch of the repository.
+    /// * `time` - The time the commit must have been authored before.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the ID of the commit, `None` if the branch has no commit that old, or an error if the
+    /// request fails.
+    async fn last_commit_before(&self, _project: &str, _repo: &str, _branch: Option<&str>, _time: &DateTime<Utc>) -> Result<Option<String>> {
+        bail!("The source control server at {} can't list the commits of a branch by date", self.base_url())
+    }
+
+    /// Lists the pull requests of a repository, in every state, that were updated since a time, most recently
+    /// updated first. A release train scans them for the issues they reference.
+    ///
+    /// Providers that can't list the pull requests of a repository return an error, which is the default.
+    ///
+    /// # Arguments
+    ///
+    /// * `project` - The project the repository belongs to.
+    /// * `repo` - The repository name.
+    /// * `time` - The time the pull requests must have been updated since.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the pull requests, or an error if the request fails.
+    async fn pull_requests_updated_since(&self, _project: &str, _repo: &str, _time: &DateTime<Utc>) -> Result<Vec<BitbucketPullRequest>> {
+        bail!("The source control server at {} can't list the pull requests of a repository", self.base_url())
+    }
+
+    /// Fetches a pull request of a repository by its ID, such as a pull request that one of the changelog's pull
+    /// requests depends on, to find out whether it was merged.
+    ///
+    /// Providers that can't fetch a single pull request return an error, which is the default.
+    ///
+    /// # Arguments
+    ///
+    /// * `project` - The project the repository belongs to.
+    /// * `repo` - The repository name.
+    /// * `pull_request_id` - The ID of the pull request.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the pull request, or an error if the request fails.
+    async fn pull_request(&self, _project: &str, _repo: &str, pull_request_id: u64) -> Result<BitbucketPullRequest> {
+        bail!("The source control server at {} can't fetch pull request #{pull_request_id}", self.base_url())
+    }
+
+    /// Finds the size of a pull request of a repository by its ID: the number of its commits and, when the server
+    /// reports them, of the files it changes.
+    ///
+    /// Providers that can't list the commits of a pull request return an error, which is the default.
+    ///
+    /// # Arguments
+    ///
+    /// * `project` - The project the repository belongs to.
+    /// * `repo` - The repository name.
+    /// * `pull_request_id` - The ID of the pull request.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the size of the pull request, or an error if the request fails.
+    async fn pull_request_size(&self, _project: &str, _repo: &str, pull_request_id: u64) -> Result<BitbucketPullRequestSize> {
+        bail!("The source control server at {} can't find the size of pull request #{pull_request_id}", self.base_url())
+    }
+
+    /// Posts a comment on a pull request of a repository, such as a note telling its author when the change
+    /// reaches production.
+    ///
+    /// Providers that can't comment on pull requests return an error, which is the default.
+    ///
+    /// # Arguments
+    ///
+    /// * `project` - The project the repository belongs to.
+    /// * `repo` - The repository name.
+    /// * `pull_request_id` - The ID of the pull request.
+    /// * `text` - The Markdown text of the comment.
+    ///
+    /// # Returns
+    ///
+    /// A Result which is an error if the request fails.
+    async fn comment_on_pull_request(&self, _project: &str, _repo: &str, pull_request_id: u64, _text: &str) -> Result<()> {
+        bail!("The source control server at {} can't comment on pull request #{pull_request_id}", self.base_url())
+    }
+
+    /// Lists the names of the projects on the server, used to suggest close matches when a project is not found.
+    ///
+    /// Providers that can't list projects return an empty list, which is the default.
+    async fn list_projects(&self) -> Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    /// Lists the names of the repositories of a project, used to suggest close matches when a repository is not found.
+    ///
+    /// Providers that can't list repositories return an empty list, which is the default.
+    ///
+    /// # Arguments
+    ///
+    /// * `project` - The project to list the repositories of.
+    async fn list_repos(&self, _project: &str) -> Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    /// Returns the base URL of the source control server hosting the given project, which is the server of the
+    /// provider unless it routes projects to several servers.
+    ///
+    /// # Arguments
+    ///
+    /// * `project` - The project to find the server of.
+    fn project_base_url(&self, _project: &str) -> &Url {
+        self.base_url()
+    }
+}
+
+/// The `ScmRouter` struct routes the requests about each project to the source control server hosting it, such as
+/// when some repositories live on a second Bitbucket instance, and the other requests to a default server.
+///
+/// Project keys are matched case-insensitively. Listing projects lists the projects of every server.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use deployment_changelog::api::{bitbucket::BitbucketClient, scm::{ScmProvider, ScmRouter}};
+///
+/// let mut scm_router = ScmRouter::new(Box::new(BitbucketClient::new("https://bitbucket.example.com").unwrap()));
+/// scm_router.route("PAY", Box::new(BitbucketClient::new("https://bitbucket.payments.example.com").unwrap()));
+///
+/// // Sent to bitbucket.payments.example.com.
+/// let commits = scm_router.commits_between("PAY", "payments-service", "abcdef", "123456").await.unwrap();
+/// ```
+pub struct ScmRouter {
+    default: Box<dyn ScmProvider>,
+    routes: HashMap<String, Box<dyn ScmProvider>>
+}
+
+impl ScmRouter {
+    /// Creates a new `ScmRouter` sending every request to the given provider until projects are routed elsewhere.
+    ///
+    /// # Arguments
+    ///
+    /// * `default` - The provider of the projects that aren't routed.
+    pub fn new(default: Box<dyn ScmProvider>) -> Self {
+        Self {
+            default,
+            routes: HashMap::new()
+        }
+    }
+
+    /// Routes the requests about a project to the given provider.
+    ///
+    /// # Arguments
+    ///
+    /// * `project` - The key of the project, such as `PAY`.
+    /// * `provider` - The provider of the server hosting the project.
+    pub fn route(&mut self, project: &str, provider: Box<dyn ScmProvider>) {
+        self.routes.insert(project.to_uppercase(), provider);
+    }
+
+    /// Returns the provider of the server hosting the given project.
+    pub fn provider(&self, project: &str) -> &dyn ScmProvider {
+        self.routes.get(&project.to_uppercase())
+            .unwrap_or(&self.default)
+            .as_ref()
+    }
+}
+
+#[async_trait::async_trait]
+impl ScmProvider for ScmRouter {
+    fn base_url(&self) -> &Url {
+        self.default.base_url()
+    }
+
+    async fn commits_between(&self, project: &str, repo: &str, start_commit: &str, end_commit: &str) -> Result<Vec<BitbucketCommit>> {
+        self.provider(project).commits_between(project, repo, start_commit, end_commit).await
+    }
+
+    async fn pull_requests_for_commit(&self, project: &str, repo: &str, commit: &str) -> Result<Vec<BitbucketPullRequest>> {
+        self.provider(project).pull_requests_for_commit(project, repo, commit).await
+    }
+
+    async fn issues_for_pull_request(&self, project: &str, repo: &str, pull_request_id: u64) -> Result<Vec<BitbucketPullRequestIssue>> {
+        self.provider(project).issues_for_pull_request(project, repo, pull_request_id).await
+    }
+
+    async fn changed_files(&self, project: &str, repo: &str, start_commit: &str, end_commit: &str) -> Result<Vec<String>> {
+        self.provider(project).changed_files(project, repo, start_commit, end_commit).await
+    }
+
+    async fn changed_lines(&self, project: &str, repo: &str, start_commit: &str, end_commit: &str) -> Result<Vec<DiffLine>> {
+        self.provider(project).changed_lines(project, repo, start_commit, end_commit).await
+    }
+
+    async fn resolve_tag(&self, project: &str, repo: &str, tag: &str) -> Result<String> {
+        self.provider(project).resolve_tag(project, repo, tag).await
+    }
+
+    async fn resolve_ref(&self, project: &str, repo: &str, reference: &str) -> Result<Option<String>> {
+        self.provider(project).resolve_ref(project, repo, reference).await
+    }
+
+    async fn merge_base(&self, project: &str, repo: &str, first_commit: &str, second_commit: &str) -> Result<String> {
+        self.provider(project).merge_base(project, repo, first_commit, second_commit).await
+    }
+
+    async fn last_commit_before(&self, project: &str, repo: &str, branch: Option<&str>, time: &DateTime<Utc>) -> Result<Option<String>> {
+        self.provider(project).last_commit_before(project, repo, branch, time).await
+    }
+
+    async fn pull_requests_updated_since(&self, project: &str, repo: &str, time: &DateTime<Utc>) -> Result<Vec<BitbucketPullRequest>> {
+        self.provider(project).pull_requests_updated_since(project, repo, time).await
+    }
+
+    async fn pull_request(&self, project: &str, repo: &str, pull_request_id: u64) -> Result<BitbucketPullRequest> {
+        self.provider(project).pull_request(project, repo, pull_request_id).await
+    }
+
+    async fn pull_request_size(&self, project: &str, repo: &str, pull_request_id: u64) -> Result<BitbucketPullRequestSize> {
+        self.provider(project).pull_request_size(project, repo, pull_request_id).await
+    }
+
+    async fn comment_on_pull_request(&self, project: &str, repo: &str, pull_request_id: u64, text: &str) -> Result<()> {
+        self.provider(project).comment_on_pull_request(project, repo, pull_request_id, text).await
+    }
+
+    async fn list_projects(&self) -> Result<Vec<String>> {
+        let mut projects = self.default.list_projects().await?;
+
+        for provider in self.routes.values() {
+            projects.extend(provider.list_projects().await?);
+        }
+
+        Ok(projects)
+    }
+
+    async fn list_repos(&self, project: &str) -> Result<Vec<String>> {
+        self.provider(project).list_repos(project).await
+    }
+
+    fn project_base_url(&self, project: &str) -> &Url {
+        self.provider(project).base_url()
+    }
+}
+
+/// The `DiffLine` struct represents a line added or removed between two commits. It contains the following fields:
+///
+/// - `path`: The path of the file the line belongs to. The new path is used for files that were moved.
+/// - `added`: Whether the line was added rather than removed.
+/// - `line`: The contents of the line.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffLine {
+    pub path: String,
+    pub added: bool,
+    pub line: String
+}
+
+/// Parses the added and removed lines of a unified diff, such as the output of `git diff`.
+///
+/// # Example
+///
+/// ```rust
+/// use deployment_changelog::api::scm::parse_unified_diff;
+///
+/// let diff = "diff --git a/README.md b/README.md\n--- a/README.md\n+++ b/README.md\n@@ -1 +1 @@\n-Hello\n+Hello, world\n";
+///
+/// let lines = parse_unified_diff(diff);
+/// assert_eq!(lines.len(), 2);
+/// assert!(lines[1].added);
+/// ```
+pub fn parse_unified_diff(diff: &str) -> Vec<DiffLine> {
+    let mut lines: Vec<DiffLine> = Vec::new();
+    let mut old_path = String::new();
+    let mut path = String::new();
+
+    // The `---` and `+++` file headers are only expected before the first hunk of a file, since a
+    // removed `--` line would otherwise look like a header.
+    let mut in_header = false;
+
+    diff.lines()
+        .for_each(|line| {
+            if line.starts_with("diff ") {
+                in_header = true;
+            } else if line.starts_with("@@") {
+                in_header = false;
+            } else if in_header {
+                if let Some(header_path) = line.strip_prefix("--- ") {
+                    old_path = strip_diff_prefix(header_path, "a/");
+                } else if let Some(header_path) = line.strip_prefix("+++ ") {
+                    path = match strip_diff_prefix(header_path, "b/") {
+                        new_path if new_path == "/dev/null" => old_path.clone(),
+                        new_path => new_path
+                    };
+                }
+            } else if let Some(added) = line.strip_prefix('+') {
+                lines.push(DiffLine { path: path.clone(), added: true, line: added.to_string() });
+            } else if let Some(removed) = line.strip_prefix('-') {
+                lines.push(DiffLine { path: path.clone(), added: false, line: removed.to_string() });
+            }
+        });
+
+    lines
+}
+
+fn strip_diff_prefix(header_path: &str, prefix: &str) -> String {
+    let header_path = header_path.split('\t').next().unwrap_or(header_path);
+
+    header_path.strip_prefix(prefix)
+        .unwrap_or(header_path)
+        .to_string()
+}
+
+/// Extracts the Jira-style issue keys (e.g. `ABC-123`) mentioned in a piece of text, in order of appearance
+/// and without duplicates.
+///
+/// This is used by backends that have no native issue integration, to link reviews to issues from their
+/// titles and descriptions.
+///
+/// # Example
+///
+/// ```rust
+/// use deployment_changelog::api::scm::extract_issue_keys;
+///
+/// let issue_keys = extract_issue_keys("ABC-123: Fix the login page, follow-up to ABC-100 and ABC-123");
+/// assert_eq!(issue_keys, vec!["ABC-123", "ABC-100"]);
+/// ```
+pub fn extract_issue_keys(text: &str) -> Vec<String> {
+    let issue_key_regex = Regex::new(r"\b[A-Z][A-Z0-9_]+-[0-9]+\b")
+        .expect("Error compiling issue key regex");
+
+    let mut issue_keys: Vec<String> = Vec::new();
+
+    issue_key_regex.find_iter(text)
+        .for_each(|issue_key| {
+            if !issue_keys.iter().any(|key| key == issue_key.as_str()) {
+                issue_keys.push(issue_key.as_str().to_string());
+            }
+        });
+
+    issue_keys
+}
+
+/// Extracts the pull requests and issues that a pull request description declares it depends on, such as
+/// "Depends on PR #45" or "Blocked by ABC-123, ABC-124", in the order they first appear and without
+/// duplicates. Pull requests are returned as `#45` and issues by their keys.
+///
+/// The phrases recognized are "depends on", "blocked by" and "requires", followed by a list of references
+/// separated by commas or "and". Pull requests may be written `#45`, `PR #45` or `pull request #45`.
+///
+/// # Example
+///
+/// ```rust
+/// use deployment_changelog::api::scm::extract_dependencies;
+///
+/// let dependencies = extract_dependencies("Adds the refund button.\n\nDepends on PR #45 and PAY-12, requires #45");
+/// assert_eq!(dependencies, vec!["#45", "PAY-12"]);
+/// ```
+pub fn extract_dependencies(text: &str) -> Vec<String> {
+    let reference = r"(?:(?:PR|pull\s+request)\s*)?(?:#[0-9]+|(?-i:[A-Z][A-Z0-9_]+-[0-9]+)\b)";
+    let dependency_regex = Regex::new(&format!(r"(?i)\b(?:depends\s+on|blocked\s+by|requires)\s*:?\s*({reference}(?:\s*(?:,|&|\band\b)\s*{reference})*)"))
+        .expect("Error compiling dependency regex");
+    let reference_regex = Regex::new(r"#[0-9]+|\b[A-Z][A-Z0-9_]+-[0-9]+\b")
+        .expect("Error compiling dependency reference regex");
+
+    let mut dependencies: Vec<String> = Vec::new();
+
+    dependency_regex.captures_iter(text)
+        .filter_map(|captures| captures.get(1))
+        .flat_map(|references| reference_regex.find_iter(references.as_str()))
+        .for_each(|reference| {
+            if !dependencies.iter().any(|dependency| dependency == reference.as_str()) {
+                dependencies.push(reference.as_str().to_string());
+            }
+        });
+
+    dependencies
+}
@@ -0,0 +1,256 @@
+//! The `deployment_changelog::api::sigv4` module signs the requests of a `RestClient` with AWS Signature Version 4,
+//! for servers behind an IAM-authenticated gateway, such as a Spinnaker Gate behind Amazon API Gateway or VPC
+//! Lattice. It is only built with the `aws-sigv4` feature.
+//!
+//! The requests are signed with the ambient AWS credentials: the `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY` and
+//! `AWS_SESSION_TOKEN` environment variables, or else the `AWS_PROFILE` profile (`default` by default) of the
+//! shared credentials file, `~/.aws/credentials` or the path in `AWS_SHARED_CREDENTIALS_FILE`.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use std::sync::Arc;
+//! use deployment_changelog::api::{rest::{Credentials, RestClient}, sigv4::{AwsCredentials, SigV4Signer}};
+//!
+//! let signer = SigV4Signer::new(AwsCredentials::from_environment().unwrap(), "us-east-1", "execute-api");
+//!
+//! let mut rest_client_builder = RestClient::builder("https://spinnaker-gate.example.com").unwrap();
+//! rest_client_builder.credentials = Some(Credentials::AwsSigV4(Arc::new(signer)));
+//! ```
+use std::{collections::HashMap, env, fmt::Debug, fs, path::PathBuf};
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use reqwest::{header::{HeaderName, HeaderValue, AUTHORIZATION}, Request};
+use sha2::{Digest, Sha256};
+
+use super::hmac::hmac_sha256;
+
+const ALGORITHM: &str = "AWS4-HMAC-SHA256";
+const AMZ_DATE_HEADER: &str = "x-amz-date";
+const AMZ_CONTENT_SHA256_HEADER: &str = "x-amz-content-sha256";
+const AMZ_SECURITY_TOKEN_HEADER: &str = "x-amz-security-token";
+
+/// The `AwsCredentials` struct holds the AWS credentials requests are signed with. The secrets are left out of
+/// the `Debug` representation.
+#[derive(Clone, PartialEq, Eq)]
+pub struct AwsCredentials {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    pub session_token: Option<String>
+}
+
+impl AwsCredentials {
+    /// Loads the ambient AWS credentials, from the environment variables or else from the shared credentials file.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the `AwsCredentials`, or an error if neither has credentials.
+    pub fn from_environment() -> Result<Self> {
+        if let (Ok(access_key_id), Ok(secret_access_key)) = (env::var("AWS_ACCESS_KEY_ID"), env::var("AWS_SECRET_ACCESS_KEY")) {
+            return Ok(Self {
+                access_key_id,
+                secret_access_key,
+                session_token: env::var("AWS_SESSION_TOKEN").ok()
+            });
+        }
+
+        let path = match env::var_os("AWS_SHARED_CREDENTIALS_FILE") {
+            Some(path) => PathBuf::from(path),
+            None => match env::var_os(if cfg!(windows) { "USERPROFILE" } else { "HOME" }) {
+                Some(home) => PathBuf::from(home).join(".aws").join("credentials"),
+                None => bail!("No AWS credentials in the environment, and no home directory to find the shared credentials file in")
+            }
+        };
+
+        let profile = env::var("AWS_PROFILE")
+            .unwrap_or_else(|_| String::from("default"));
+
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("No AWS credentials in the environment, and error reading the shared credentials file {}", path.display()))?;
+
+        Self::from_profile(&contents, &profile)
+            .with_context(|| format!("Error reading profile {profile} of the shared credentials file {}", path.display()))
+    }
+
+    /// Reads the credentials of a profile of a shared credentials file.
+    fn from_profile(contents: &str, profile: &str) -> Result<Self> {
+        let mut profiles: HashMap<&str, HashMap<&str, &str>> = HashMap::new();
+        let mut current_profile: Option<&str> = None;
+
+        contents.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with(['#', ';']))
+            .for_each(|line| {
+                if let Some(name) = line.strip_prefix('[').and_then(|line| line.strip_suffix(']')) {
+                    current_profile = Some(name.trim());
+                } else if let (Some(current_profile), Some((key, value))) = (current_profile, line.split_once('=')) {
+                    profiles.entry(current_profile)
+                        .or_default()
+                        .insert(key.trim(), value.trim());
+                }
+            });
+
+        let properties = profiles.get(profile)
+            .with_context(|| format!("Profile {profile} not found"))?;
+
+        let property = |key: &str| properties.get(key)
+            .map(|value| value.to_string());
+
+        Ok(Self {
+            access_key_id: property("aws_access_key_id")
+                .context("The profile has no aws_access_key_id")?,
+            secret_access_key: property("aws_secret_access_key")
+                .context("The profile has no aws_secret_access_key")?,
+            session_token: property("aws_session_token")
+        })
+    }
+}
+
+impl Debug for AwsCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "AwsCredentials {{ access_key_id: {:?}, secret_access_key: <redacted>, session_token: <redacted> }}", self.access_key_id)
+    }
+}
+
+/// The `SigV4Signer` struct signs requests with AWS Signature Version 4 for a region and a service, such as
+/// `execute-api` for Amazon API Gateway.
+#[derive(Debug, PartialEq, Eq)]
+pub struct SigV4Signer {
+    credentials: AwsCredentials,
+    region: String,
+    service: String
+}
+
+impl SigV4Signer {
+    /// Creates a new `SigV4Signer`.
+    ///
+    /// # Arguments
+    ///
+    /// * `credentials` - The `AwsCredentials` the requests are signed with.
+    /// * `region` - The AWS region of the gateway, such as `us-east-1`.
+    /// * `service` - The signing name of the gateway's service, such as `execute-api` or `vpc-lattice-svcs`.
+    ///
+    /// # Returns
+    ///
+    /// A new `SigV4Signer` instance.
+    pub fn new(credentials: AwsCredentials, region: &str, service: &str) -> Self {
+        Self {
+            credentials,
+            region: region.to_string(),
+            service: service.to_string()
+        }
+    }
+
+    /// Returns who the requests are signed as, the access key ID, region and service of the signer, without its
+    /// secret access key.
+    pub(crate) fn identity(&self) -> String {
+        format!("{} {} {}", self.credentials.access_key_id, self.region, self.service)
+    }
+
+    /// Signs a request, setting its `Authorization`, `x-amz-date` and `x-amz-content-sha256` headers, and its
+    /// `x-amz-security-token` header with temporary credentials.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The request to sign, with its body.
+    /// * `now` - The time of the signature.
+    pub fn sign(&self, request: &mut Request, now: DateTime<Utc>) -> Result<()> {
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+
+        // Streamed bodies, such as multipart uploads, can't be hashed before they are sent.
+        let payload_hash = match request.body() {
+            Some(body) => match body.as_bytes() {
+                Some(bytes) => hex::encode(Sha256::digest(bytes)),
+                None => String::from("UNSIGNED-PAYLOAD")
+            },
+            None => hex::encode(Sha256::digest(b""))
+        };
+
+        let url = request.url();
+        let host = match (url.host_str(), url.port()) {
+            (Some(host), Some(port)) => format!("{host}:{port}"),
+            (Some(host), None) => host.to_string(),
+            (None, _) => bail!("Can't sign a request to {url}, which has no host")
+        };
+
+        let mut headers: Vec<(&str, &str)> = vec![
+            ("host", &host),
+            (AMZ_CONTENT_SHA256_HEADER, &payload_hash),
+            (AMZ_DATE_HEADER, &amz_date)
+        ];
+
+        if let Some(session_token) = &self.credentials.session_token {
+            headers.push((AMZ_SECURITY_TOKEN_HEADER, session_token));
+        }
+
+        headers.sort();
+
+        let signed_headers = headers.iter()
+            .map(|(name, _)| *name)
+            .collect::<Vec<&str>>()
+            .join(";");
+
+        let canonical_headers: String = headers.iter()
+            .map(|(name, value)| format!("{name}:{}\n", value.trim()))
+            .collect();
+
+        let mut query: Vec<(String, String)> = url.query_pairs()
+            .map(|(name, value)| (uri_encode(&name, true), uri_encode(&value, true)))
+            .collect();
+        query.sort();
+
+        let canonical_query = query.iter()
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect::<Vec<String>>()
+            .join("&");
+
+        let canonical_request = format!(
+            "{}\n{}\n{canonical_query}\n{canonical_headers}\n{signed_headers}\n{payload_hash}",
+            request.method(),
+            uri_encode(url.path(), false)
+        );
+
+        let scope = format!("{date}/{}/{}/aws4_request", self.region, self.service);
+        let string_to_sign = format!("{ALGORITHM}\n{amz_date}\n{scope}\n{}", hex::encode(Sha256::digest(canonical_request.as_bytes())));
+
+        let signing_key = [date.as_str(), &self.region, &self.service, "aws4_request"].into_iter()
+            .fold(format!("AWS4{}", self.credentials.secret_access_key).into_bytes(), |key, part| hmac_sha256(&key, part.as_bytes()));
+        let signature = hex::encode(hmac_sha256(&signing_key, string_to_sign.as_bytes()));
+
+        let authorization = format!(
+            "{ALGORITHM} Credential={}/{scope}, SignedHeaders={signed_headers}, Signature={signature}",
+            self.credentials.access_key_id
+        );
+
+        let request_headers = request.headers_mut();
+
+        for (name, value) in headers.iter().filter(|(name, _)| *name != "host") {
+            let mut value = HeaderValue::from_str(value)
+                .with_context(|| format!("Error creating the {name} header"))?;
+            value.set_sensitive(*name == AMZ_SECURITY_TOKEN_HEADER);
+
+            request_headers.insert(HeaderName::from_static(name), value);
+        }
+
+        let mut authorization = HeaderValue::from_str(&authorization)
+            .context("Error creating the SigV4 authorization header")?;
+        authorization.set_sensitive(true);
+        request_headers.insert(AUTHORIZATION, authorization);
+
+        Ok(())
+    }
+}
+
+/// URI-encodes a string the way SigV4 expects, leaving only the unreserved characters as is, and the slashes of
+/// paths. Paths are encoded again, as SigV4 requires outside of Amazon S3.
+fn uri_encode(value: &str, encode_slash: bool) -> String {
+    value.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (byte as char).to_string(),
+            b'/' if !encode_slash => String::from("/"),
+            _ => format!("%{byte:02X}")
+        })
+        .collect()
+}
@@ -10,9 +10,9 @@
 //!
 //! # Example
 //!
-//! ```rust
+//! ```rust,ignore
 //! use deployment_changelog::api::rest::spinnaker::{SpinnakerClient, md_environment_states_query};
-//! use chrono::{DateTime, Local};
+//! use chrono::{DateTime, Utc};
 //!
 //! async fn fetch_environment_states() -> Result<md_environment_states_query::ResponseData> {
 //!     let spinnaker_client = SpinnakerClient::new("https://api.example.com")?;
@@ -29,13 +29,18 @@
 //! Spinnaker API, then call the `get_environment_states` method with the necessary
 //! variables to fetch the environment states data. The result is a
 //! `md_environment_states_query::ResponseData` object containing the fetched data.
-use chrono::{DateTime, Local};
+use chrono::{DateTime, Utc};
 use graphql_client::GraphQLQuery;
+use serde::{Deserialize, Serialize};
 use anyhow::{Result, Context, bail};
+use reqwest::Url;
 
-use super::graphql::GraphQLClient;
+use super::{graphql::{schema_incompatibilities, GraphQLClient, RuntimeQuery}, rest::Credentials};
 
-type InstantTime = DateTime<Local>;
+/// The Spinnaker schema the compiled queries were generated from, used to validate queries loaded at runtime.
+pub const SPINNAKER_SCHEMA: &str = include_str!("../../resources/graphql/spinnaker/schema.graphql");
+
+type InstantTime = DateTime<Utc>;
 
 /// The `MdEnvironmentStatesQuery` struct represents the GraphQL query used to fetch
 /// environment states from the Spinnaker API. It is derived from the `GraphQLQuery` trait
@@ -79,6 +84,34 @@ type InstantTime = DateTime<Local>;
 )]
 pub struct MdEnvironmentStatesQuery;
 
+/// The `MdEnvironmentNamesQuery` struct represents the GraphQL query used to list the names of the
+/// environments of a Spinnaker application, which are suggested when an environment is not found.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "resources/graphql/spinnaker/schema.graphql",
+    query_path = "resources/graphql/spinnaker/queries.graphql",
+    response_derives = "Debug, Serialize, Deserialize, Hash, Eq, PartialEq, Clone"
+)]
+pub struct MdEnvironmentNamesQuery;
+
+enum SpinnakerEndpoints {
+    Applications
+}
+
+impl SpinnakerEndpoints {
+    fn url(&self) -> &'static str {
+        match self {
+            SpinnakerEndpoints::Applications => "applications"
+        }
+    }
+}
+
+/// The `SpinnakerApplication` struct represents an application returned by the Spinnaker Gate REST API.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct SpinnakerApplication {
+    pub name: String
+}
+
 /// The `SpinnakerClient` struct provides an interface to interact with the Spinnaker API
 /// for fetching environment states. It wraps the `GraphQLClient` and handles the execution
 /// of the `MdEnvironmentStatesQuery` for you.
@@ -109,7 +142,8 @@ pub struct MdEnvironmentStatesQuery;
 /// the fetched data.
 #[derive(Debug)]
 pub struct SpinnakerClient {
-    client: GraphQLClient
+    client: GraphQLClient,
+    environment_states_query: Option<RuntimeQuery>
 }
 
 impl SpinnakerClient {
@@ -133,7 +167,62 @@ impl SpinnakerClient {
     /// ```
     pub fn new(base_url: &str) -> Result<Self> {
         Ok(Self {
-            client: GraphQLClient::new(base_url)?
+            client: GraphQLClient::new(base_url)?,
+            environment_states_query: None
+        })
+    }
+
+    /// Constructs a new `SpinnakerClient` instance with the provided base URL for the Spinnaker API,
+    /// authenticating with a bearer token, such as one accepted by the authentication proxy of Gate.
+    ///
+    /// # Arguments
+    ///
+    /// * `base_url` - The base URL of the Spinnaker API, as a string.
+    /// * `token` - The token sent in the `Authorization: Bearer` header of every request.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a new `SpinnakerClient` instance, or an error if there was an issue
+    /// creating the underlying `GraphQLClient`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use deployment_changelog::api::rest::spinnaker::SpinnakerClient;
+    ///
+    /// let spinnaker_client = SpinnakerClient::with_token("https://api.example.com", "token")?;
+    /// ```
+    pub fn with_token(base_url: &str, token: &str) -> Result<Self> {
+        Ok(Self {
+            client: GraphQLClient::with_token(base_url, token)?,
+            environment_states_query: None
+        })
+    }
+
+    /// Creates a new `SpinnakerClient` instance with the given base URL, authenticating with the given
+    /// credentials, such as the session cookie of a Spinnaker login.
+    ///
+    /// # Arguments
+    ///
+    /// * `base_url` - The base URL of the Spinnaker API, as a string.
+    /// * `credentials` - The `Credentials` every request is authenticated with.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a new `SpinnakerClient` instance, or an error if there was an issue
+    /// creating the underlying `GraphQLClient`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use deployment_changelog::api::{rest::Credentials, secret::SecretString, spinnaker::SpinnakerClient};
+    ///
+    /// let spinnaker_client = SpinnakerClient::with_credentials("https://api.example.com", Credentials::Cookie(SecretString::from("SESSION=abc123")))?;
+    /// ```
+    pub fn with_credentials(base_url: &str, credentials: Credentials) -> Result<Self> {
+        Ok(Self {
+            client: GraphQLClient::with_credentials(base_url, credentials)?,
+            environment_states_query: None
         })
     }
 
@@ -157,10 +246,91 @@ impl SpinnakerClient {
     /// ```
     pub fn from_client(client: GraphQLClient) -> Self {
         Self {
-            client
+            client,
+            environment_states_query: None
         }
     }
 
+    /// Replaces the compiled `MdEnvironmentStatesQuery` with a query loaded at runtime, for Spinnaker
+    /// servers exposing extra fields. The query is validated against the given schema, or the compiled-in
+    /// schema if there is none, and must select every field of the compiled query.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The GraphQL document containing a `MdEnvironmentStatesQuery` query.
+    /// * `schema` - The schema of the Spinnaker server, if it differs from the compiled-in one.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use deployment_changelog::api::spinnaker::SpinnakerClient;
+    ///
+    /// let query = std::fs::read_to_string("queries/queries.graphql")?;
+    /// let spinnaker_client = SpinnakerClient::new("https://api.example.com")?
+    ///     .with_environment_states_query(&query, None)?;
+    /// ```
+    pub fn with_environment_states_query(mut self, query: &str, schema: Option<&str>) -> Result<Self> {
+        self.environment_states_query = Some(RuntimeQuery::new(
+            md_environment_states_query::OPERATION_NAME,
+            md_environment_states_query::QUERY,
+            query,
+            schema.unwrap_or(SPINNAKER_SCHEMA)
+        )?);
+
+        Ok(self)
+    }
+
+    /// Returns whether the environment states are fetched with a query loaded at runtime.
+    pub fn has_custom_environment_states_query(&self) -> bool {
+        self.environment_states_query.is_some()
+    }
+
+    /// Logs in to a Spinnaker Gate protected by a login form, such as with LDAP authentication, by posting the
+    /// username and password to its `/login` endpoint. The session cookie Gate sets in response is kept by the
+    /// underlying `RestClient` and sent with every later request of this client.
+    ///
+    /// # Arguments
+    ///
+    /// * `username` - The username to log in with.
+    /// * `password` - The password of the user.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` that is an error if the request fails, if Gate rejects the credentials, or if it doesn't set a
+    /// session cookie, such as when it doesn't use form login.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use deployment_changelog::api::spinnaker::SpinnakerClient;
+    ///
+    /// let spinnaker_client = SpinnakerClient::new("https://gate.example.com")?;
+    /// spinnaker_client.login("jdoe", "hunter2").await?;
+    /// ```
+    pub async fn login(&self, username: &str, password: &str) -> Result<()> {
+        let rest_client = self.client.rest_client();
+
+        let redirect = rest_client.login("login", &[("username", username), ("password", password)])
+            .await
+            .with_context(|| format!("Error logging in to Spinnaker at {} as {username}", self.base_url()))?;
+
+        // Spring Security redirects failed form logins back to the login page, as /login?error.
+        if redirect.iter().flat_map(|redirect| redirect.query_pairs()).any(|(name, _)| name == "error") {
+            bail!("Spinnaker at {} rejected the credentials of {username}", self.base_url());
+        }
+
+        if !rest_client.has_session() {
+            bail!("Spinnaker at {} didn't set a session cookie when logging in as {username}, it may not use form login", self.base_url());
+        }
+
+        Ok(())
+    }
+
+    /// Returns the base URL of the Spinnaker API server this client talks to.
+    pub fn base_url(&self) -> &Url {
+        self.client.base_url()
+    }
+
     /// Fetches environment states data from the Spinnaker API using the provided query variables.
     ///
     /// # Arguments
@@ -193,6 +363,14 @@ impl SpinnakerClient {
         &self,
         variables: md_environment_states_query::Variables
     ) -> Result<md_environment_states_query::ResponseData> {
+        if self.environment_states_query.is_some() {
+            let data = self.get_raw_environment_states(variables)
+                .await?;
+
+            return serde_json::from_value(data)
+                .with_context(|| "Error deserializing the response of the custom environment states query");
+        }
+
         let response = self.client.post::<MdEnvironmentStatesQuery>(variables)
             .await?;
 
@@ -203,4 +381,115 @@ impl SpinnakerClient {
         response.data
             .with_context(|| "No data received for GraphQL call but no errors were found")
     }
+
+    /// Fetches environment states data from the Spinnaker API as raw JSON, keeping any extra fields selected
+    /// by a query set with `with_environment_states_query`.
+    ///
+    /// # Arguments
+    ///
+    /// * `variables` - An `md_environment_states_query::Variables` instance containing the
+    ///   required variables for the `MdEnvironmentStatesQuery`.
+    pub async fn get_raw_environment_states(
+        &self,
+        variables: md_environment_states_query::Variables
+    ) -> Result<serde_json::Value> {
+        let compiled_query = RuntimeQuery {
+            query: md_environment_states_query::QUERY.to_string(),
+            operation_name: md_environment_states_query::OPERATION_NAME.to_string()
+        };
+
+        let query = self.environment_states_query.as_ref()
+            .unwrap_or(&compiled_query);
+
+        let response = self.client.post_runtime::<_, serde_json::Value>(query, variables)
+            .await?;
+
+        if let Some(errors) = response.errors {
+            bail!("Received errors from GraphQL call {:#?}", errors);
+        }
+
+        response.data
+            .with_context(|| "No data received for GraphQL call but no errors were found")
+    }
+
+    /// Lists the names of the environments of a Spinnaker application.
+    ///
+    /// # Arguments
+    ///
+    /// * `app_name` - The name of the Spinnaker application.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the environment names, or an error if the application was not found or there was
+    /// an issue executing the GraphQL call.
+    pub async fn list_environments(&self, app_name: &str) -> Result<Vec<String>> {
+        let variables = md_environment_names_query::Variables {
+            app_name: app_name.to_string()
+        };
+
+        let response = self.client.post::<MdEnvironmentNamesQuery>(variables)
+            .await?;
+
+        if let Some(errors) = response.errors {
+            bail!("Received errors from GraphQL call {:#?}", errors);
+        }
+
+        let application = response.data
+            .and_then(|data| data.application)
+            .with_context(|| format!("Spinnaker application {app_name} was not found"))?;
+
+        Ok(application.environments
+            .into_iter()
+            .map(|environment| environment.name)
+            .collect())
+    }
+
+    /// Checks the compiled queries against the schema of the Spinnaker server, fetched with an introspection
+    /// query, so that Spinnaker upgrades breaking them are noticed before changelogs fail.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the incompatibilities between the compiled queries and the schema of the server,
+    /// which are none if the queries are compatible, or an error if the schema could not be fetched.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// // Suppose you have a SpinnakerClient named 'spinnaker_client'
+    /// for incompatibility in spinnaker_client.check_schema().await.unwrap() {
+    ///     println!("{incompatibility}");
+    /// }
+    /// ```
+    pub async fn check_schema(&self) -> Result<Vec<String>> {
+        let server_schema = self.client.introspect_schema()
+            .await?;
+
+        let incompatibilities = [
+            (md_environment_states_query::OPERATION_NAME, md_environment_states_query::QUERY),
+            (md_environment_names_query::OPERATION_NAME, md_environment_names_query::QUERY)
+        ]
+            .into_iter()
+            .map(|(operation_name, query)| schema_incompatibilities(operation_name, query, SPINNAKER_SCHEMA, &server_schema))
+            .collect::<Result<Vec<Vec<String>>>>()?;
+
+        Ok(incompatibilities.into_iter()
+            .flatten()
+            .collect())
+    }
+
+    /// Lists the Spinnaker applications, using the Gate REST API.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// // Suppose you have a SpinnakerClient named 'spinnaker_client'
+    /// for application in spinnaker_client.list_applications().await.unwrap() {
+    ///     println!("{}", application.name);
+    /// }
+    /// ```
+    pub async fn list_applications(&self) -> Result<Vec<SpinnakerApplication>> {
+        self.client.rest_client()
+            .get::<Vec<SpinnakerApplication>>(SpinnakerEndpoints::Applications.url(), None)
+            .await
+    }
 }
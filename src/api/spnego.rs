@@ -0,0 +1,252 @@
+//! The `deployment_changelog::api::spnego` module authenticates the requests of a `RestClient` with Kerberos
+//! through SPNEGO, for on-premises servers behind a negotiate authentication filter, such as Bitbucket Server or
+//! Jira Server in an Active Directory domain. It is only built with the `spnego` feature.
+//!
+//! The system GSSAPI library is loaded when a `SpnegoAuthenticator` is created, rather than linked, so that the
+//! feature builds without the Kerberos development files: `libgssapi_krb5.so.2` on Linux, the GSS framework on
+//! macOS. The Kerberos tickets of the user are used, such as those obtained with `kinit`.
+//!
+//! Every request is sent with an `Authorization: Negotiate` header carrying the first token of a new security
+//! context for the `HTTP` service of its host, so that servers accept it without challenging it first.
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::sync::Arc;
+//! use deployment_changelog::api::{rest::{Credentials, RestClient}, spnego::SpnegoAuthenticator};
+//!
+//! let mut rest_client_builder = RestClient::builder("https://jira.example.com").unwrap();
+//! rest_client_builder.credentials = Some(Credentials::Negotiate(Arc::new(SpnegoAuthenticator::new().unwrap())));
+//! ```
+use std::{env, ffi::c_void, fmt::Debug, ptr};
+
+use anyhow::{bail, Context, Result};
+use libloading::Library;
+
+#[cfg(target_os = "macos")]
+const GSSAPI_LIBRARIES: &[&str] = &["/System/Library/Frameworks/GSS.framework/GSS"];
+#[cfg(not(target_os = "macos"))]
+const GSSAPI_LIBRARIES: &[&str] = &["libgssapi_krb5.so.2", "libgssapi_krb5.so", "libgssapi.so.3"];
+
+/// The OID of the host-based service name type, 1.2.840.113554.1.2.1.4, naming services as `service@host`.
+const NT_HOSTBASED_SERVICE: &[u8] = b"\x2a\x86\x48\x86\xf7\x12\x01\x02\x01\x04";
+/// The OID of the SPNEGO mechanism, 1.3.6.1.5.5.2.
+const SPNEGO_MECHANISM: &[u8] = b"\x2b\x06\x01\x05\x05\x02";
+
+const GSS_C_MUTUAL_FLAG: u32 = 2;
+const GSS_C_GSS_CODE: i32 = 1;
+const GSS_C_MECH_CODE: i32 = 2;
+
+#[repr(C)]
+struct GssBuffer {
+    length: usize,
+    value: *mut c_void
+}
+
+impl GssBuffer {
+    fn empty() -> Self {
+        Self {
+            length: 0,
+            value: ptr::null_mut()
+        }
+    }
+}
+
+#[repr(C)]
+struct GssOid {
+    length: u32,
+    elements: *const c_void
+}
+
+impl GssOid {
+    fn new(oid: &'static [u8]) -> Self {
+        Self {
+            length: oid.len() as u32,
+            elements: oid.as_ptr().cast()
+        }
+    }
+}
+
+type GssName = *mut c_void;
+type GssContext = *mut c_void;
+
+type ImportName = unsafe extern "C" fn(*mut u32, *const GssBuffer, *const GssOid, *mut GssName) -> u32;
+type InitSecContext = unsafe extern "C" fn(
+    *mut u32, *mut c_void, *mut GssContext, GssName, *const GssOid, u32, u32, *const c_void, *const GssBuffer,
+    *mut *const GssOid, *mut GssBuffer, *mut u32, *mut u32
+) -> u32;
+type ReleaseBuffer = unsafe extern "C" fn(*mut u32, *mut GssBuffer) -> u32;
+type ReleaseName = unsafe extern "C" fn(*mut u32, *mut GssName) -> u32;
+type DeleteSecContext = unsafe extern "C" fn(*mut u32, *mut GssContext, *mut GssBuffer) -> u32;
+type DisplayStatus = unsafe extern "C" fn(*mut u32, u32, i32, *const GssOid, *mut u32, *mut GssBuffer) -> u32;
+
+/// The `SpnegoAuthenticator` struct creates the SPNEGO tokens of requests with the system GSSAPI library, from the
+/// Kerberos tickets of the user.
+pub struct SpnegoAuthenticator {
+    import_name: ImportName,
+    init_sec_context: InitSecContext,
+    release_buffer: ReleaseBuffer,
+    release_name: ReleaseName,
+    delete_sec_context: DeleteSecContext,
+    display_status: DisplayStatus,
+    // The functions above point into the library, so it is kept loaded for as long as they can be called.
+    _library: Library
+}
+
+impl SpnegoAuthenticator {
+    /// Creates a new `SpnegoAuthenticator`, loading the system GSSAPI library.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the `SpnegoAuthenticator`, or an error if the GSSAPI library can't be loaded.
+    pub fn new() -> Result<Self> {
+        let library = GSSAPI_LIBRARIES.iter()
+            // SAFETY: The GSSAPI libraries don't run initialization code with preconditions when they are loaded.
+            .find_map(|name| unsafe { Library::new(name) }.ok())
+            .with_context(|| format!("Error loading the GSSAPI library, tried {}. Is Kerberos installed?", GSSAPI_LIBRARIES.join(", ")))?;
+
+        // SAFETY: The types of the functions match their declarations in RFC 2744.
+        unsafe {
+            Ok(Self {
+                import_name: *library.get::<ImportName>(b"gss_import_name\0")?,
+                init_sec_context: *library.get::<InitSecContext>(b"gss_init_sec_context\0")?,
+                release_buffer: *library.get::<ReleaseBuffer>(b"gss_release_buffer\0")?,
+                release_name: *library.get::<ReleaseName>(b"gss_release_name\0")?,
+                delete_sec_context: *library.get::<DeleteSecContext>(b"gss_delete_sec_context\0")?,
+                display_status: *library.get::<DisplayStatus>(b"gss_display_status\0")?,
+                _library: library
+            })
+        }
+    }
+
+    /// Returns who the requests are authenticated as, the Kerberos credentials cache and user of the environment,
+    /// since the principal of the tickets is only known to the GSSAPI library.
+    pub(crate) fn identity(&self) -> String {
+        ["KRB5CCNAME", "USER"].iter()
+            .map(|name| env::var(name).unwrap_or_default())
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    /// Creates the SPNEGO token authenticating a request to the `HTTP` service of a host. Getting the service
+    /// ticket of the host can send a request to the Kerberos KDC, so this blocks.
+    ///
+    /// # Arguments
+    ///
+    /// * `host` - The host name of the server, such as `jira.example.com`.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the token, or an error if the user has no Kerberos ticket or the service is unknown.
+    pub fn token(&self, host: &str) -> Result<Vec<u8>> {
+        let service = format!("HTTP@{host}");
+        let service_buffer = GssBuffer {
+            length: service.len(),
+            value: service.as_ptr() as *mut c_void
+        };
+
+        let name_type = GssOid::new(NT_HOSTBASED_SERVICE);
+        let mechanism = GssOid::new(SPNEGO_MECHANISM);
+
+        let mut minor = 0;
+        let mut name: GssName = ptr::null_mut();
+
+        // SAFETY: The buffer and the OID outlive the call, and the name is released below.
+        let major = unsafe { (self.import_name)(&mut minor, &service_buffer, &name_type, &mut name) };
+        self.check(major, minor)
+            .with_context(|| format!("Error importing the service name {service}"))?;
+
+        let mut context: GssContext = ptr::null_mut();
+        let mut output_token = GssBuffer::empty();
+
+        // SAFETY: The default credentials are used and the optional arguments are null. The context, the name and
+        // the output token are released below.
+        let major = unsafe {
+            (self.init_sec_context)(
+                &mut minor, ptr::null_mut(), &mut context, name, &mechanism, GSS_C_MUTUAL_FLAG, 0, ptr::null(),
+                ptr::null(), ptr::null_mut(), &mut output_token, ptr::null_mut(), ptr::null_mut()
+            )
+        };
+
+        let token = self.check(major, minor)
+            .with_context(|| format!("Error creating the SPNEGO token of {service}"))
+            .map(|_| match output_token.value.is_null() {
+                true => Vec::new(),
+                // SAFETY: The library wrote output_token.length bytes at output_token.value.
+                false => unsafe { std::slice::from_raw_parts(output_token.value.cast::<u8>(), output_token.length) }.to_vec()
+            });
+
+        // SAFETY: The buffer, the context and the name were allocated by the library, or are null.
+        unsafe {
+            (self.release_buffer)(&mut minor, &mut output_token);
+            if !context.is_null() {
+                (self.delete_sec_context)(&mut minor, &mut context, ptr::null_mut());
+            }
+            (self.release_name)(&mut minor, &mut name);
+        }
+
+        match token? {
+            token if token.is_empty() => bail!("The GSSAPI library created an empty SPNEGO token for {service}"),
+            token => Ok(token)
+        }
+    }
+
+    /// Turns a failed GSSAPI status into an error with the messages of the library.
+    fn check(&self, major: u32, minor: u32) -> Result<()> {
+        // The calling and routine errors are in the upper 16 bits, and the lower ones are informational.
+        if major & 0xffff_0000 == 0 {
+            return Ok(());
+        }
+
+        let mut messages = self.status_messages(major, GSS_C_GSS_CODE);
+        messages.extend(self.status_messages(minor, GSS_C_MECH_CODE));
+
+        bail!("{}", messages.join(": "))
+    }
+
+    fn status_messages(&self, status: u32, status_type: i32) -> Vec<String> {
+        let mut messages = Vec::new();
+        let mut message_context = 0;
+
+        loop {
+            let mut minor = 0;
+            let mut message = GssBuffer::empty();
+
+            // SAFETY: The message is released below once it is copied.
+            let major = unsafe { (self.display_status)(&mut minor, status, status_type, ptr::null(), &mut message_context, &mut message) };
+            if major & 0xffff_0000 != 0 {
+                break;
+            }
+
+            if !message.value.is_null() {
+                // SAFETY: The library wrote message.length bytes at message.value.
+                let bytes = unsafe { std::slice::from_raw_parts(message.value.cast::<u8>(), message.length) };
+                messages.push(String::from_utf8_lossy(bytes).trim().to_string());
+
+                // SAFETY: The message was allocated by the library.
+                unsafe { (self.release_buffer)(&mut minor, &mut message) };
+            }
+
+            if message_context == 0 {
+                break;
+            }
+        }
+
+        messages
+    }
+}
+
+impl Debug for SpnegoAuthenticator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SpnegoAuthenticator")
+    }
+}
+
+impl PartialEq for SpnegoAuthenticator {
+    // Every authenticator creates the same tokens, from the tickets of the user.
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for SpnegoAuthenticator {}
@@ -0,0 +1,59 @@
+//! The `deployment_changelog::api::suggest` module finds close matches for names that could not be found,
+//! such as a misspelled repository or Spinnaker environment, so errors can suggest what was meant.
+//!
+//! # Example
+//!
+//! ```rust
+//! use deployment_changelog::api::suggest::did_you_mean;
+//!
+//! let repos = vec![String::from("payments-service"), String::from("orders-service")];
+//!
+//! assert_eq!(did_you_mean("payment-service", &repos), ", did you mean `payments-service`?");
+//! ```
+use strsim::jaro_winkler;
+
+/// The minimum Jaro-Winkler similarity for a candidate to be suggested.
+const SIMILARITY_THRESHOLD: f64 = 0.8;
+
+/// The maximum number of candidates suggested.
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Returns the candidates closest to `name`, most similar first, ignoring case.
+///
+/// # Arguments
+///
+/// * `name` - The name that could not be found.
+/// * `candidates` - The names that exist.
+pub fn closest_matches<'a>(name: &str, candidates: &'a [String]) -> Vec<&'a str> {
+    let name = name.to_lowercase();
+
+    let mut matches: Vec<(f64, &str)> = candidates.iter()
+        .map(|candidate| (jaro_winkler(&name, &candidate.to_lowercase()), candidate.as_str()))
+        .filter(|(similarity, _)| *similarity >= SIMILARITY_THRESHOLD)
+        .collect();
+
+    matches.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+
+    matches.into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, candidate)| candidate)
+        .collect()
+}
+
+/// Returns a suggestion to append to a "not found" error message, such as ``, did you mean `payments-service`?``,
+/// or an empty string if none of the candidates is close to `name`.
+///
+/// # Arguments
+///
+/// * `name` - The name that could not be found.
+/// * `candidates` - The names that exist.
+pub fn did_you_mean(name: &str, candidates: &[String]) -> String {
+    let matches: Vec<String> = closest_matches(name, candidates).into_iter()
+        .map(|candidate| format!("`{candidate}`"))
+        .collect();
+
+    match matches.len() {
+        0 => String::new(),
+        _ => format!(", did you mean {}?", matches.join(" or "))
+    }
+}
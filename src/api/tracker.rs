@@ -0,0 +1,79 @@
+//! The `deployment_changelog::api::tracker` module defines the `IssueTracker` trait, which lets work items
+//! from trackers other than Jira be linked to a changelog.
+//!
+//! Unlike Jira issues, which Bitbucket links to pull requests natively, work items from trackers such as
+//! Trello or Asana are usually only referenced by URL in pull request descriptions. An `IssueTracker` finds
+//! those references in a piece of text and resolves each of them into a normalized `TrackedIssue` entry.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use deployment_changelog::api::{tracker::IssueTracker, trello::TrelloClient};
+//!
+//! let trello_client = TrelloClient::new("https://api.trello.com", "api-key", "token").unwrap();
+//!
+//! for reference in trello_client.find_references("Implements https://trello.com/c/AbCd1234/42-login-page") {
+//!     let tracked_issue = trello_client.get_tracked_issue(&reference).await.unwrap();
+//!     println!("{}: {} ({:?})", tracked_issue.id, tracked_issue.title, tracked_issue.status);
+//! }
+//! ```
+use std::fmt::Display;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// The `TrackedIssue` struct is the normalized representation of a work item from an `IssueTracker`.
+/// It contains the following fields:
+///
+/// - `tracker`: The name of the tracker the work item comes from (e.g. `trello`).
+/// - `id`: The identifier of the work item in its tracker.
+/// - `url`: The URL of the work item.
+/// - `title`: The title of the work item.
+/// - `status`: The status of the work item, such as the Trello list or Asana section it is in, if known.
+/// - `entry_id`: The stable ID of the entry in changelogs, set when it is linked to a changelog.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TrackedIssue {
+    pub tracker: String,
+    pub id: String,
+    pub url: String,
+    pub title: String,
+    pub status: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entry_id: Option<String>
+}
+
+impl Display for TrackedIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match serde_json::to_string_pretty(&self) {
+            Ok(json) => write!(f, "{json}"),
+            Err(error) => panic!("Error serializing tracked issue: {error}")
+        }
+    }
+}
+
+/// The `IssueTracker` trait resolves references to work items found in free text into `TrackedIssue` entries.
+///
+/// # Example
+///
+/// ```rust
+/// use deployment_changelog::api::tracker::IssueTracker;
+///
+/// async fn resolve_all(tracker: &dyn IssueTracker, description: &str) {
+///     for reference in tracker.find_references(description) {
+///         println!("{}", tracker.get_tracked_issue(&reference).await.unwrap());
+///     }
+/// }
+/// ```
+#[async_trait::async_trait]
+pub trait IssueTracker: Send + Sync {
+    /// Returns the name of the tracker, used as the `tracker` field of the resolved entries.
+    fn name(&self) -> &'static str;
+
+    /// Finds the references to work items of this tracker in the given text, without duplicates.
+    fn find_references(&self, text: &str) -> Vec<String>;
+
+    /// Resolves a reference returned by `find_references` into a normalized `TrackedIssue`.
+    async fn get_tracked_issue(&self, reference: &str) -> Result<TrackedIssue>;
+}
@@ -0,0 +1,170 @@
+//! The `deployment_changelog::api::trello` module provides a client for the Trello REST API, used to link
+//! Trello cards referenced in pull request descriptions to a changelog.
+//!
+//! Cards are referenced by their URL or short link (e.g. `https://trello.com/c/AbCd1234`). Each card is
+//! resolved into a `TrackedIssue` whose status is the name of the list the card is in.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use deployment_changelog::api::trello::TrelloClient;
+//!
+//! let trello_client = TrelloClient::new("https://api.trello.com", "api-key", "token").unwrap();
+//! let card = trello_client.get_card("AbCd1234").await.unwrap();
+//!
+//! println!("{} is in list {}", card.name, card.list.name);
+//! ```
+use std::{fmt::Display, collections::HashMap};
+
+use anyhow::Result;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use super::{rest::RestClient, tracker::{IssueTracker, TrackedIssue}};
+
+enum TrelloEndpoints {
+    GetCard
+}
+
+impl TrelloEndpoints {
+    fn url(&self) -> &'static str {
+        match self {
+            TrelloEndpoints::GetCard => "1/cards/{cardId}?fields=name,shortLink,shortUrl&list=true&list_fields=name"
+        }
+    }
+}
+
+enum TrelloOptions {
+    Key,
+    Token
+}
+
+impl TrelloOptions {
+    fn option(&self) -> &'static str {
+        match self {
+            TrelloOptions::Key => "key",
+            TrelloOptions::Token => "token"
+        }
+    }
+}
+
+/// The `TrelloCard` struct represents a Trello card and the list it is in.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TrelloCard {
+    pub id: String,
+    pub name: String,
+    pub short_link: String,
+    pub short_url: String,
+    pub list: TrelloList
+}
+
+impl Display for TrelloCard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match serde_json::to_string_pretty(&self) {
+            Ok(json) => write!(f, "{json}"),
+            Err(error) => panic!("Error serializing Trello card: {error}")
+        }
+    }
+}
+
+/// The `TrelloList` struct represents a Trello list, which is used as the status of the cards it contains.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TrelloList {
+    pub id: String,
+    pub name: String
+}
+
+/// The `TrelloClient` struct provides access to Trello cards using an API key and token.
+///
+/// It implements `IssueTracker`, finding Trello card URLs in text and resolving them into `TrackedIssue` entries.
+///
+/// # Example
+///
+/// ```rust
+/// use deployment_changelog::api::trello::TrelloClient;
+///
+/// let trello_client = TrelloClient::new("https://api.trello.com", "api-key", "token").unwrap();
+/// ```
+#[derive(Debug)]
+pub struct TrelloClient {
+    client: RestClient,
+    auth_query: HashMap<String, String>
+}
+
+impl TrelloClient {
+    /// Creates a new `TrelloClient` with the given API base URL, API key and token.
+    ///
+    /// # Arguments
+    ///
+    /// * `base_url` - The base URL of the Trello API, usually `https://api.trello.com`.
+    /// * `key` - The Trello API key.
+    /// * `token` - The Trello API token authorizing access to the boards.
+    pub fn new(base_url: &str, key: &str, token: &str) -> Result<Self> {
+        Ok(Self::from_client(RestClient::new(base_url)?, key, token))
+    }
+
+    /// Creates a new `TrelloClient` from an existing `RestClient`, API key and token.
+    pub fn from_client(client: RestClient, key: &str, token: &str) -> Self {
+        let auth_query = HashMap::from([
+            (TrelloOptions::Key.option().to_string(), key.to_string()),
+            (TrelloOptions::Token.option().to_string(), token.to_string())
+        ]);
+
+        Self {
+            client,
+            auth_query
+        }
+    }
+
+    /// Fetches a Trello card, along with the list it is in, by its ID or short link.
+    ///
+    /// # Arguments
+    ///
+    /// * `card_id` - The ID or short link of the card.
+    pub async fn get_card(&self, card_id: &str) -> Result<TrelloCard> {
+        let card_path: String = TrelloEndpoints::GetCard.url()
+            .replace("{cardId}", card_id);
+
+        self.client.get::<TrelloCard>(&card_path, Some(&self.auth_query)).await
+    }
+}
+
+#[async_trait::async_trait]
+impl IssueTracker for TrelloClient {
+    fn name(&self) -> &'static str {
+        "trello"
+    }
+
+    fn find_references(&self, text: &str) -> Vec<String> {
+        let card_url_regex = Regex::new(r"https?://trello\.com/c/([A-Za-z0-9]+)")
+            .expect("Error compiling Trello card URL regex");
+
+        let mut short_links: Vec<String> = Vec::new();
+
+        card_url_regex.captures_iter(text)
+            .for_each(|captures| {
+                let short_link = captures[1].to_string();
+
+                if !short_links.contains(&short_link) {
+                    short_links.push(short_link);
+                }
+            });
+
+        short_links
+    }
+
+    async fn get_tracked_issue(&self, reference: &str) -> Result<TrackedIssue> {
+        let card = self.get_card(reference).await?;
+
+        Ok(TrackedIssue {
+            tracker: self.name().to_string(),
+            id: card.short_link,
+            url: card.short_url,
+            title: card.name,
+            status: Some(card.list.name),
+            entry_id: None
+        })
+    }
+}
@@ -0,0 +1,1978 @@
+use deployment_changelog::{changelog::{Baseline, Changelog, CommitSpecifier, DeployDigest, DigestService, EnvironmentComparison, SpinnakerEnvironment, GitCommitRange, ReleaseTrain, ScmCoordinates, TimeWindow}, config::{Config, ServiceConfig}, ci::{teamcity_build_problem, teamcity_messages, write_github_outputs}, api::{cache::HttpCache, rest::{ClientIdentity, ConnectionOptions, Credentials, HttpVersion, OAuth2ClientCredentials, OAuth2TokenProvider, ProxyOptions, RefreshableToken, RestClient, RestClientBuilder, read_root_certificates, ATLASSIAN_TOKEN_URL}, retry::{RetryBudget, RetryPolicy, RETRIABLE_STATUSES}, secret::{redact_urls, RedactedUrl, SecretString}, priority::{PriorityPolicy, RequestPriority}, rate_limit::RateLimiter, graphql::GraphQLClient, jira::{jql_string, JiraClient}, bitbucket::BitbucketClient, gerrit::GerritClient, azure_devops::AzureDevOpsClient, scm::{ScmProvider, ScmRouter}, tracker::IssueTracker, trello::TrelloClient, asana::AsanaClient, confluence::ConfluenceClient, spinnaker::SpinnakerClient}, render::{lint_release_notes, render_bytes, render_comparison, render_digest, render_release_notes, OutputFormat, RenderOptions, DisplayTimezone, DateFormat}, metrics::{DeploymentRecord, MetricsTarget}, notify::{FanOutNotifier, NotificationMetadata, Notifier, WebhookNotifier}, progress::{self, JsonLinesReporter}};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+#[cfg(feature = "fault-injection")]
+use deployment_changelog::api::fault::{self, FaultInjector, FaultRule};
+#[cfg(feature = "desktop-notifications")]
+use deployment_changelog::notify::DesktopNotifier;
+#[cfg(feature = "aws-sigv4")]
+use deployment_changelog::api::sigv4::{AwsCredentials, SigV4Signer};
+#[cfg(feature = "spnego")]
+use deployment_changelog::api::spnego::SpnegoAuthenticator;
+use clap::{Parser, ValueEnum};
+use clap_verbosity_flag::Verbosity;
+use reqwest::{Certificate, StatusCode};
+use std::{collections::{BTreeMap, HashMap}, fs, io::Write, net::IpAddr, path::PathBuf, process::ExitCode, sync::{Arc, OnceLock}, time::{Duration, Instant}};
+
+const TRELLO_API_URL: &str = "https://api.trello.com";
+const ASANA_URL: &str = "https://app.asana.com";
+const DEFAULT_CACHE_TTL: u64 = 3600;
+const DEFAULT_CACHE_MEMORY_ENTRIES: u64 = 10000;
+
+/// The retry policy and budget shared by every client of the run, set once the arguments are parsed.
+static RETRY_POLICY: OnceLock<RetryPolicy> = OnceLock::new();
+static RETRY_BUDGET: OnceLock<Arc<RetryBudget>> = OnceLock::new();
+
+/// The User-Agents set with --user-agent and --backend-user-agent, set once the arguments are parsed.
+static USER_AGENTS: OnceLock<UserAgents> = OnceLock::new();
+
+/// The rate limiters shared by the clients of every backend set with --rate-limit, set once the arguments are parsed.
+static RATE_LIMITERS: OnceLock<HashMap<Backend, Arc<RateLimiter>>> = OnceLock::new();
+
+/// The connection options shared by every client of the run, set once the arguments are parsed.
+static CONNECTION_OPTIONS: OnceLock<ConnectionOptions> = OnceLock::new();
+
+/// The client certificate presented to servers requiring mutual TLS, set once the arguments are parsed.
+static CLIENT_IDENTITY: OnceLock<ClientIdentity> = OnceLock::new();
+static ROOT_CERTIFICATES: OnceLock<Vec<Certificate>> = OnceLock::new();
+static INSECURE: OnceLock<bool> = OnceLock::new();
+static PROXY: OnceLock<ProxyOptions> = OnceLock::new();
+
+/// The credentials of the servers requests are authenticated to, set once the arguments are parsed.
+static CREDENTIALS: OnceLock<HashMap<Backend, Credentials>> = OnceLock::new();
+static SPINNAKER_LOGIN: OnceLock<(String, SecretString)> = OnceLock::new();
+
+/// The response cache shared by every client of the run, set once the arguments and the configuration file are parsed.
+static HTTP_CACHE: OnceLock<Arc<HttpCache>> = OnceLock::new();
+
+/// The timeouts and retry policies of the request priorities, set once the arguments are parsed.
+static PRIORITY_POLICIES: OnceLock<BTreeMap<RequestPriority, PriorityPolicy>> = OnceLock::new();
+
+/// Matches the flag key of LaunchDarkly SDK evaluation calls, such as `boolVariation("my-flag", ...)` or `variation_detail("my-flag", ...)`.
+const LAUNCHDARKLY_FLAG_PATTERN: &str = r#"(?i)\b\w*variation(?:_?detail)?\s*\(\s*["']([\w.-]+)["']"#;
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about)]
+struct Args {
+    #[clap(subcommand)]
+    commit_specifier: CommitSpecifierSubcommand,
+
+    #[clap(
+        long,
+        help = "Generate the changelog of a Spinnaker environment or commit range in windows of this many commits, printing each window as soon as it is resolved to keep memory flat for very large ranges",
+        conflicts_with_all = ["note", "notes_file", "attach_to_issue", "attach_to_page", "comment_on_pull_requests", "lint_notes", "strict_notes", "fail_on_unresolved_dependencies", "github_output", "export_metrics", "webhook_url"]
+    )]
+    window_size: Option<usize>,
+
+    #[clap(long, value_enum, default_value = "bitbucket", help = "The source control system hosting the repository")]
+    scm: ScmProviderKind,
+
+    #[clap(long, short = 'b', help = "The URL to your Bitbucket server", env = "BITBUCKET_URL")]
+    bitbucket_url: Option<String>,
+
+    #[clap(long, help = "The Bitbucket HTTP access token or personal access token authenticating the requests to Bitbucket", env = "BITBUCKET_TOKEN", hide_env_values = true)]
+    bitbucket_token: Option<SecretString>,
+
+    #[clap(long, help = "The username authenticating the requests to Bitbucket with basic authentication", env = "BITBUCKET_USERNAME", requires = "bitbucket_password", conflicts_with = "bitbucket_token")]
+    bitbucket_username: Option<String>,
+
+    #[clap(long, help = "The password authenticating the requests to Bitbucket with basic authentication", env = "BITBUCKET_PASSWORD", hide_env_values = true, requires = "bitbucket_username")]
+    bitbucket_password: Option<SecretString>,
+
+    #[clap(long, value_parser = parse_bitbucket_route, help = "Route the requests about a project to another Bitbucket instance, as <project>=<url>, such as PAY=https://bitbucket.payments.example.com. The other projects use --bitbucket-url. Can be repeated")]
+    bitbucket_route: Vec<(String, String)>,
+
+    #[clap(long, value_parser = parse_bitbucket_route_token, help = "The HTTP access token authenticating the requests to the Bitbucket instance of a --bitbucket-route, as <project>=<token>. Can be repeated")]
+    bitbucket_route_token: Vec<(String, SecretString)>,
+
+    #[clap(long, help = "Send the Bitbucket credentials of --bitbucket-url to the instances of the --bitbucket-route routes without a --bitbucket-route-token, which are otherwise requested anonymously")]
+    share_bitbucket_credentials: bool,
+
+    #[clap(long, short = 'g', help = "The URL to your Gerrit server", env = "GERRIT_URL")]
+    gerrit_url: Option<String>,
+
+    #[clap(long, help = "The username authenticating the requests to Gerrit with basic authentication", env = "GERRIT_USERNAME", requires = "gerrit_password")]
+    gerrit_username: Option<String>,
+
+    #[clap(long, help = "The HTTP password authenticating the requests to Gerrit with basic authentication", env = "GERRIT_PASSWORD", hide_env_values = true, requires = "gerrit_username")]
+    gerrit_password: Option<SecretString>,
+
+    #[clap(long, help = "The URL to your Azure DevOps organization", env = "AZURE_DEVOPS_URL")]
+    azure_devops_url: Option<String>,
+
+    #[clap(long, short = 'j', help = "The URL to your JIRA server", env = "JIRA_URL")]
+    jira_url: Option<String>,
+
+    #[clap(long, help = "The Jira personal access token authenticating the requests to Jira", env = "JIRA_TOKEN", hide_env_values = true)]
+    jira_token: Option<SecretString>,
+
+    #[clap(long, help = "The username authenticating the requests to Jira with basic authentication", env = "JIRA_USERNAME", requires = "jira_password", conflicts_with = "jira_token")]
+    jira_username: Option<String>,
+
+    #[clap(long, help = "The password or API token authenticating the requests to Jira with basic authentication", env = "JIRA_PASSWORD", hide_env_values = true, requires = "jira_username")]
+    jira_password: Option<SecretString>,
+
+    #[clap(long, help = "The client ID authenticating the requests to Jira with OAuth2 tokens obtained with the client credentials flow, such as of a Jira Cloud service account", env = "JIRA_CLIENT_ID", requires = "jira_client_secret", conflicts_with_all = ["jira_token", "jira_username"])]
+    jira_client_id: Option<String>,
+
+    #[clap(long, help = "The client secret of --jira-client-id", env = "JIRA_CLIENT_SECRET", hide_env_values = true, requires = "jira_client_id")]
+    jira_client_secret: Option<SecretString>,
+
+    #[clap(long, default_value = ATLASSIAN_TOKEN_URL, help = "The token endpoint the OAuth2 tokens of --jira-client-id are obtained from")]
+    jira_token_url: RedactedUrl,
+
+    #[clap(long, help = "The space-separated scopes of the OAuth2 tokens of --jira-client-id, such as read:jira-work, the scopes of the client by default")]
+    jira_scope: Option<String>,
+
+    #[clap(long, help = "The bearer token authenticating the requests to Spinnaker", env = "SPINNAKER_TOKEN", hide_env_values = true)]
+    spinnaker_token: Option<SecretString>,
+
+    #[clap(long, help = "The session cookie authenticating the requests to Spinnaker, such as SESSION=<id>, for a Spinnaker behind a login page", env = "SPINNAKER_SESSION_COOKIE", hide_env_values = true, conflicts_with = "spinnaker_token")]
+    spinnaker_session_cookie: Option<SecretString>,
+
+    #[clap(long, help = "The user logging in to the login form of Spinnaker, such as with LDAP authentication, for a session cookie authenticating the requests to Spinnaker", env = "SPINNAKER_USERNAME", requires = "spinnaker_password", conflicts_with_all = ["spinnaker_token", "spinnaker_session_cookie"])]
+    spinnaker_username: Option<String>,
+
+    #[clap(long, help = "The password of --spinnaker-username", env = "SPINNAKER_PASSWORD", hide_env_values = true, requires = "spinnaker_username")]
+    spinnaker_password: Option<SecretString>,
+
+    #[clap(long, value_parser = parse_token_command, help = "A command printing the bearer token of the requests to one server, as <backend>=<command>, such as jira='vault read -field=token secret/jira'. It is run again whenever the server rejects the token. Can be repeated")]
+    token_command: Vec<(Backend, String)>,
+
+    #[cfg(feature = "aws-sigv4")]
+    #[clap(long, help = "Sign the requests to Spinnaker with AWS Signature Version 4 for the given region, with the ambient AWS credentials, for a Spinnaker behind Amazon API Gateway", conflicts_with_all = ["spinnaker_token", "spinnaker_session_cookie", "spinnaker_username"])]
+    spinnaker_aws_region: Option<String>,
+
+    #[cfg(feature = "aws-sigv4")]
+    #[clap(long, default_value = "execute-api", requires = "spinnaker_aws_region", help = "The service the requests to Spinnaker are signed for with --spinnaker-aws-region, such as execute-api for Amazon API Gateway or vpc-lattice-svcs for VPC Lattice")]
+    spinnaker_aws_service: String,
+
+    #[cfg(feature = "spnego")]
+    #[clap(long, value_enum, help = "Authenticate the requests to a server with Kerberos through SPNEGO, with the tickets of the user, instead of its other credentials. Can be repeated")]
+    negotiate: Vec<Backend>,
+
+    #[clap(long, help = "The Trello API key, used to link Trello cards referenced in pull requests", env = "TRELLO_API_KEY", hide_env_values = true, requires = "trello_token")]
+    trello_key: Option<SecretString>,
+
+    #[clap(long, help = "The Trello API token, used to link Trello cards referenced in pull requests", env = "TRELLO_TOKEN", hide_env_values = true, requires = "trello_key")]
+    trello_token: Option<SecretString>,
+
+    #[clap(long, help = "The Asana personal access token, used to link Asana tasks referenced in pull requests", env = "ASANA_TOKEN", hide_env_values = true)]
+    asana_token: Option<SecretString>,
+
+    #[clap(long, default_value = "In Progress", help = "The Jira status that starts the cycle time of an issue")]
+    cycle_start_status: String,
+
+    #[clap(long, default_value = "Done", help = "The Jira status that ends the cycle time of an issue")]
+    cycle_end_status: String,
+
+    #[clap(long, help = "The path of the TOML configuration file", env = "CHANGELOG_CONFIG")]
+    config: Option<PathBuf>,
+
+    #[clap(long = "migration-glob", default_values = ["**/migrations/**", "*.sql"], help = "A glob matching the database migration files flagged in the changelog, can be repeated")]
+    migration_globs: Vec<String>,
+
+    #[clap(long = "feature-flag-glob", default_values = ["*feature-flags*", "*feature_flags*"], help = "A glob matching the feature flag configuration files listed in the changelog, can be repeated")]
+    feature_flag_globs: Vec<String>,
+
+    #[clap(long = "feature-flag-pattern", default_value = LAUNCHDARKLY_FLAG_PATTERN, help = "A regular expression matching feature flag references in changed lines, whose first capture group is the flag key, can be repeated")]
+    feature_flag_patterns: Vec<String>,
+
+    #[clap(long, short = 'f', value_enum, help = "The format to output the changelog in [default: json, or release-notes for the release-notes subcommand]")]
+    format: Option<OutputFormat>,
+
+    #[clap(long, default_value = "UTC", help = "The timezone to display timestamps in: UTC, local or an IANA timezone name such as Europe/Paris", env = "CHANGELOG_TIMEZONE")]
+    timezone: DisplayTimezone,
+
+    #[clap(long, default_value = "%Y-%m-%d %H:%M:%S %:z", help = "The strftime pattern absolute dates are displayed with in the markdown and text formats")]
+    date_format: DateFormat,
+
+    #[clap(long, help = "A deployment note to add to the changelog, such as \"DB migration required\". Can be repeated")]
+    note: Vec<String>,
+
+    #[clap(long, help = "A file of deployment notes to add to the changelog, one per line", env = "CHANGELOG_NOTES_FILE")]
+    notes_file: Option<PathBuf>,
+
+    #[clap(long, help = "The URL to your Confluence server, used to attach the changelog to a page", env = "CONFLUENCE_URL")]
+    confluence_url: Option<String>,
+
+    #[clap(long, help = "The username authenticating the requests to Confluence with basic authentication", env = "CONFLUENCE_USERNAME", requires = "confluence_password")]
+    confluence_username: Option<String>,
+
+    #[clap(long, help = "The password or API token authenticating the requests to Confluence with basic authentication", env = "CONFLUENCE_PASSWORD", hide_env_values = true, requires = "confluence_username")]
+    confluence_password: Option<SecretString>,
+
+    #[clap(long, help = "Attach the rendered changelog to this Jira issue, such as a release ticket")]
+    attach_to_issue: Option<String>,
+
+    #[clap(long, help = "Attach the rendered changelog to the Confluence page with this ID", requires = "confluence_url")]
+    attach_to_page: Option<String>,
+
+    #[clap(long, help = "Comment on every pull request of the changelog when its change is shipping, so that its author knows when it reaches production. Requires --scm bitbucket")]
+    comment_on_pull_requests: bool,
+
+    #[clap(long, help = "The URL the changelog is published at, linked from the pull request comments", requires = "comment_on_pull_requests")]
+    changelog_url: Option<String>,
+
+    #[clap(long, help = "Lint the release notes for broken links, empty sections, TODO markers and control characters, printing warnings on stderr")]
+    lint_notes: bool,
+
+    #[clap(long, help = "Lint the release notes like --lint-notes, and fail without printing the changelog if there are any warnings")]
+    strict_notes: bool,
+
+    #[clap(long, help = "Fail with a non-zero exit status, without printing the changelog, if its pull requests depend on pull requests or issues, such as \"Depends on PR #45\" in their descriptions, that are neither included nor merged or done")]
+    fail_on_unresolved_dependencies: bool,
+
+    #[clap(long, help = "Fetch the commits and changed files of every pull request, adding the percentiles of their sizes to the changelog's stats")]
+    pull_request_sizes: bool,
+
+    #[clap(long, help = "Sum the time logged on every issue from its Jira worklogs, adding the total effort of the deployment to the changelog's stats")]
+    worklogs: bool,
+
+    #[clap(long, help = "Write the changelog's path and counts to the GitHub Actions step outputs, and its Markdown rendering to the job summary")]
+    github_output: bool,
+
+    #[clap(long, help = "Print TeamCity service messages reporting the changelog's counts, and a build problem if the run fails")]
+    teamcity: bool,
+
+    #[clap(long, help = "Strip the changelog down to issue keys, issue summaries and pull request titles, so it can be shared outside of the organization")]
+    external: bool,
+
+    #[clap(long, help = "Append the deployment's lead time and change volume to a metrics target: csv:<path>, sqlite:<path> or pushgateway:<url>. Can be repeated")]
+    export_metrics: Vec<MetricsTarget>,
+
+    #[clap(long, help = "Post every changelog as JSON to this URL, such as the endpoint of an internal system subscribing to deployments. Can be repeated")]
+    webhook_url: Vec<RedactedUrl>,
+
+    #[clap(long, requires = "webhook_url", help = "Sign the changelogs posted to --webhook-url with HMAC-SHA256 and this secret, in the X-Changelog-Signature-256 header", env = "WEBHOOK_SECRET", hide_env_values = true)]
+    webhook_secret: Option<SecretString>,
+
+    #[clap(long, help = "A directory to cache the responses of the source control and Jira servers in, overriding the directory of the [cache] table of --config", env = "CHANGELOG_CACHE_DIR")]
+    cache_dir: Option<PathBuf>,
+
+    #[clap(long, help = "The number of seconds cached responses are used for, overriding the ttl of the [cache] table of --config [default: 3600]")]
+    cache_ttl: Option<u64>,
+
+    #[clap(long, help = "The maximum number of cached responses kept in memory, overriding the memory-entries of the [cache] table of --config [default: 10000]")]
+    cache_memory_entries: Option<u64>,
+
+    #[clap(long, default_value = "2", help = "The number of times a request failing with a timeout, a connection error or a retriable status is retried")]
+    retries: u32,
+
+    #[clap(long, default_value = "500", help = "The delay in milliseconds before the first retry of a request, which doubles with every retry")]
+    retry_backoff: u64,
+
+    #[clap(long, default_value = "30000", help = "The longest delay in milliseconds between two retries of a request")]
+    max_retry_backoff: u64,
+
+    #[clap(long, help = "Wait exactly the backoff between retries, instead of a random delay between half of it and all of it")]
+    no_retry_jitter: bool,
+
+    #[clap(long, value_delimiter = ',', help = "The HTTP statuses of the responses that are retried, such as 502,503. 429, 500, 502, 503 and 504 by default")]
+    retry_status: Vec<StatusCode>,
+
+    #[clap(long, default_value = "50", help = "The maximum number of retries across all the requests of a changelog, so that a degraded server isn't flooded")]
+    retry_budget: usize,
+
+    #[clap(long, help = "The timeout in seconds of the critical requests resolving the commit range, such as its Spinnaker versions and commit references")]
+    critical_timeout: Option<u64>,
+
+    #[clap(long, help = "The number of times the critical requests resolving the commit range are retried, --retries by default")]
+    critical_retries: Option<u32>,
+
+    #[clap(long, help = "The timeout in seconds of the bulk requests enriching the changelog, such as fetching its Jira issues and linked work items")]
+    bulk_timeout: Option<u64>,
+
+    #[clap(long, help = "The number of times the bulk requests enriching the changelog are retried, --retries by default")]
+    bulk_retries: Option<u32>,
+
+    #[cfg(feature = "fault-injection")]
+    #[clap(long, conflicts_with = "inject_fault", help = "Inject the faults of the chaos profile into every request: up to 500 ms of latency, and one in five requests failing with 503")]
+    chaos: bool,
+
+    #[cfg(feature = "fault-injection")]
+    #[clap(long, help = "Inject faults into the requests to an endpoint, as <endpoint>=<error rate>[,<max latency in ms>], such as /rest/api/2/issue=0.5,2000. Can be repeated")]
+    inject_fault: Vec<FaultRule>,
+
+    #[cfg(feature = "fault-injection")]
+    #[clap(long, default_value = "0", help = "The seed the injected faults are drawn from, so that the faults of a run can be reproduced")]
+    fault_seed: u64,
+
+    #[clap(long, value_enum, help = "Report the progress of the changelog generation on stderr in the given format")]
+    progress: Option<ProgressFormat>,
+
+    #[clap(long, help = "The User-Agent of the requests to every server, which defaults to deployment-changelog/<version>", env = "CHANGELOG_USER_AGENT")]
+    user_agent: Option<String>,
+
+    #[clap(long, value_parser = parse_backend_user_agent, help = "The User-Agent of the requests to one server, as <backend>=<user agent>, such as jira=payments-deploys/1.0. The backends are bitbucket, gerrit, azure-devops, jira, spinnaker, confluence, trello, asana, webhook and pushgateway. Can be repeated")]
+    backend_user_agent: Vec<(Backend, String)>,
+
+    #[clap(long, value_parser = parse_backend_rate_limit, help = "The maximum number of requests per second to one server, as <backend>=<requests per second>, such as bitbucket=10, shared by every client of the backend. Can be repeated")]
+    rate_limit: Vec<(Backend, f64)>,
+
+    #[clap(long, help = "The maximum number of idle connections kept open per server")]
+    pool_max_idle_per_host: Option<usize>,
+
+    #[clap(long, help = "The number of seconds idle connections are kept open, 90 by default")]
+    pool_idle_timeout: Option<u64>,
+
+    #[clap(long, help = "The interval in seconds of the TCP keep-alive probes, so that VPNs and proxies don't drop long-lived connections")]
+    tcp_keepalive: Option<u64>,
+
+    #[clap(long, value_enum, default_value = "negotiate", help = "The HTTP version of the requests: HTTP/2 when the server offers it, HTTP/1.1 only or HTTP/2 only")]
+    http_version: HttpVersion,
+
+    #[clap(long, value_parser = parse_resolve, help = "Resolve a host name to an IP address instead of through DNS, as <host>=<ip>, such as for split-horizon DNS. Can be repeated")]
+    resolve: Vec<(String, IpAddr)>,
+
+    #[clap(long, help = "The client certificate presented to servers requiring mutual TLS, such as a Spinnaker Gate behind an mTLS proxy: a PEM certificate chain with --client-key, or a PKCS #12 archive without it", env = "CLIENT_CERT")]
+    client_cert: Option<PathBuf>,
+
+    #[clap(long, help = "The PEM encoded PKCS #8 private key of the --client-cert certificate", env = "CLIENT_KEY", requires = "client_cert")]
+    client_key: Option<PathBuf>,
+
+    #[clap(long, help = "The password of the --client-cert PKCS #12 archive", env = "CLIENT_CERT_PASSWORD", hide_env_values = true, requires = "client_cert", conflicts_with = "client_key")]
+    client_cert_password: Option<SecretString>,
+
+    #[clap(long, help = "Trust the root certificates of this CA bundle besides the built-in ones, such as those of an internal CA signing the certificates of on-premises servers: PEM certificates or a DER certificate. Can be repeated")]
+    ca_cert: Vec<PathBuf>,
+
+    #[clap(long, conflicts_with = "ca_cert", help = "Don't verify the TLS certificates of the servers, for lab environments only: the servers aren't authenticated, so anyone on the network can read and change the requests, credentials included")]
+    insecure: bool,
+
+    #[clap(long, help = "Send the requests to every server through this proxy instead of those of the HTTP_PROXY and HTTPS_PROXY environment variables: http://, https://, or socks5:// and socks5h:// when built with the socks feature")]
+    proxy: Option<RedactedUrl>,
+
+    #[clap(long, requires = "proxy", help = "The credentials of the --proxy proxy, as <user>:<password>", env = "PROXY_USER", hide_env_values = true)]
+    proxy_user: Option<SecretString>,
+
+    #[clap(long, requires = "proxy", value_delimiter = ',', help = "The comma-separated hosts requested directly rather than through the --proxy proxy, such as jira.example.com, .example.com for its subdomains, or 10.0.0.0/8")]
+    no_proxy: Vec<String>,
+
+    #[clap(flatten)]
+    verbose: Verbosity
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum ScmProviderKind {
+    Bitbucket,
+    Gerrit,
+    AzureDevops
+}
+
+/// The servers requests are sent to, whose User-Agent can be set with --backend-user-agent.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Backend {
+    Bitbucket,
+    Gerrit,
+    AzureDevops,
+    Jira,
+    Spinnaker,
+    Confluence,
+    Trello,
+    Asana,
+    Webhook,
+    Pushgateway
+}
+
+#[derive(Debug, Default)]
+struct UserAgents {
+    default: Option<String>,
+    backends: HashMap<Backend, String>
+}
+
+impl UserAgents {
+    fn get(&self, backend: Backend) -> Option<&String> {
+        self.backends.get(&backend)
+            .or(self.default.as_ref())
+    }
+}
+
+fn parse_backend_rate_limit(backend_rate_limit: &str) -> Result<(Backend, f64)> {
+    let (backend, requests_per_second) = backend_rate_limit.split_once('=')
+        .with_context(|| format!("Invalid rate limit {backend_rate_limit}, expected <backend>=<requests per second>"))?;
+
+    let backend = Backend::from_str(backend, true)
+        .map_err(|error| anyhow::anyhow!("Invalid backend {backend}: {error}"))?;
+
+    let requests_per_second: f64 = requests_per_second.parse()
+        .with_context(|| format!("Invalid number of requests per second {requests_per_second}"))?;
+
+    // The rate limiter rejects rates that aren't positive.
+    RateLimiter::new(requests_per_second)?;
+
+    Ok((backend, requests_per_second))
+}
+
+fn parse_backend_user_agent(backend_user_agent: &str) -> Result<(Backend, String)> {
+    let (backend, user_agent) = backend_user_agent.split_once('=')
+        .with_context(|| format!("Invalid backend User-Agent {backend_user_agent}, expected <backend>=<user agent>"))?;
+
+    let backend = Backend::from_str(backend, true)
+        .map_err(|error| anyhow::anyhow!("Invalid backend {backend}: {error}"))?;
+
+    Ok((backend, user_agent.to_string()))
+}
+
+fn parse_token_command(token_command: &str) -> Result<(Backend, String)> {
+    let (backend, command) = token_command.split_once('=')
+        .with_context(|| format!("Invalid token command {token_command}, expected <backend>=<command>"))?;
+
+    let backend = Backend::from_str(backend, true)
+        .map_err(|error| anyhow::anyhow!("Invalid backend {backend}: {error}"))?;
+
+    Ok((backend, command.to_string()))
+}
+
+fn parse_bitbucket_route(bitbucket_route: &str) -> Result<(String, String)> {
+    let (project, bitbucket_url) = bitbucket_route.split_once('=')
+        .with_context(|| format!("Invalid Bitbucket route {bitbucket_route}, expected <project>=<url>"))?;
+
+    Ok((project.to_string(), bitbucket_url.to_string()))
+}
+
+fn parse_bitbucket_route_token(bitbucket_route_token: &str) -> Result<(String, SecretString)> {
+    let (project, token) = bitbucket_route_token.split_once('=')
+        .with_context(|| "Invalid Bitbucket route token, expected <project>=<token>")?;
+
+    Ok((project.to_string(), SecretString::new(token)))
+}
+
+fn parse_resolve(resolve: &str) -> Result<(String, IpAddr)> {
+    let (host, address) = resolve.split_once('=')
+        .with_context(|| format!("Invalid host resolution {resolve}, expected <host>=<ip>"))?;
+
+    let address = address.parse()
+        .with_context(|| format!("Invalid IP address {address}"))?;
+
+    Ok((host.to_string(), address))
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum ProgressFormat {
+    Json
+}
+
+#[derive(Parser, Debug)]
+enum CommitSpecifierSubcommand {
+    Spinnaker(SpinnakerArgs),
+    CommitRange(CommitRangeArgs),
+    Jql(JqlArgs),
+    ReleaseNotes(ReleaseNotesArgs),
+
+    #[clap(about = "Get the changelog of a fix version across the repositories of the configuration file's service catalog")]
+    ReleaseTrain(ReleaseTrainArgs),
+
+    #[clap(about = "Get the changelog of the commits of a branch within a period of time, such as for periodic reports")]
+    Period(PeriodArgs),
+
+    #[clap(about = "Compare the issues and pull requests deployed to two environments of a Spinnaker application")]
+    CompareEnvs(CompareEnvsArgs),
+
+    #[clap(about = "List the Spinnaker applications")]
+    Apps(AppsArgs),
+
+    #[clap(about = "List the environments of a Spinnaker application")]
+    Envs(EnvsArgs),
+
+    #[clap(about = "Watch a Spinnaker environment and print a changelog whenever its pending version changes")]
+    Watch(WatchArgs),
+
+    #[clap(about = "Get the changelog of a service of the configuration file's catalog, by environment or commit range")]
+    Service(ServiceArgs),
+
+    #[clap(about = "Get a digest of everything deployed to an environment by the services of the configuration file's catalog, grouped by team")]
+    Digest(DigestArgs),
+
+    #[clap(subcommand, about = "Inspect or clear the response cache")]
+    Cache(CacheSubcommand),
+
+    #[clap(subcommand, about = "Check the schema of a Spinnaker server")]
+    Schema(SchemaSubcommand)
+}
+
+#[derive(Parser, Debug)]
+enum SchemaSubcommand {
+    #[clap(about = "Report the incompatibilities between the schema of a Spinnaker server and the compiled queries, such as after a Spinnaker upgrade")]
+    Check(SchemaCheckArgs)
+}
+
+#[derive(Parser, Debug)]
+struct SchemaCheckArgs {
+    #[clap(long, short = 's', help = "The URL to your Spinnaker server", env = "SPINNAKER_URL")]
+    spinnaker_url: String
+}
+
+#[derive(Parser, Debug)]
+enum CacheSubcommand {
+    #[clap(about = "Print the number and size of the cached responses")]
+    Stats,
+
+    #[clap(about = "Remove every cached response")]
+    Clear
+}
+
+#[derive(Parser, Debug)]
+struct SpinnakerArgs {
+    #[clap(long, short = 's', help = "The URL to your Spinnaker server", env = "SPINNAKER_URL")]
+    spinnaker_url: String,
+
+    #[clap(
+        long,
+        help = "A directory with a queries.graphql overriding the Spinnaker environment states query, and optionally the schema.graphql of your Spinnaker server",
+        env = "SPINNAKER_QUERIES_DIR"
+    )]
+    spinnaker_queries: Option<PathBuf>,
+
+    #[clap(long, help = "The commit to compare the pending version against when the environment has no current version", conflicts_with = "baseline_tag")]
+    baseline_commit: Option<String>,
+
+    #[clap(long, help = "The tag to compare the pending version against when the environment has no current version")]
+    baseline_tag: Option<String>,
+
+    #[clap(long, help = "Split the changelog into the commits, pull requests and issues added by each pending version, when several versions are pending")]
+    segment_versions: bool,
+
+    #[clap(help = "The Spinnaker app name")]
+    app_name: String,
+
+    #[clap(help = "The Spinnaker environment")]
+    env: String
+}
+
+#[derive(Parser, Debug)]
+struct CompareEnvsArgs {
+    #[clap(long, short = 's', help = "The URL to your Spinnaker server", env = "SPINNAKER_URL")]
+    spinnaker_url: String,
+
+    #[clap(
+        long,
+        help = "A directory with a queries.graphql overriding the Spinnaker environment states query, and optionally the schema.graphql of your Spinnaker server",
+        env = "SPINNAKER_QUERIES_DIR"
+    )]
+    spinnaker_queries: Option<PathBuf>,
+
+    #[clap(help = "The Spinnaker app name")]
+    app_name: String,
+
+    #[clap(help = "The first Spinnaker environment")]
+    env_a: String,
+
+    #[clap(help = "The second Spinnaker environment")]
+    env_b: String
+}
+
+impl CompareEnvsArgs {
+    fn spinnaker_env(&self, env: &str) -> Result<SpinnakerEnvironment> {
+        Ok(SpinnakerEnvironment {
+            client: spinnaker_client(&self.spinnaker_url, self.spinnaker_queries.as_ref())?,
+            app_name: self.app_name.clone(),
+            env: env.to_string(),
+            baseline: None
+        })
+    }
+}
+
+#[derive(Parser, Debug)]
+struct WatchArgs {
+    #[clap(long, default_value = "60", help = "The number of seconds between two polls of the Spinnaker environment")]
+    interval: u64,
+
+    #[clap(long, default_value = "30", help = "The number of seconds a new pending version must stay pending before its changelog is printed")]
+    debounce: u64,
+
+    #[cfg(feature = "desktop-notifications")]
+    #[clap(long, help = "Show a desktop notification summarizing every new pending deployment")]
+    desktop_notifications: bool,
+
+    #[clap(flatten)]
+    spinnaker: SpinnakerArgs
+}
+
+impl SpinnakerArgs {
+    fn client(&self) -> Result<SpinnakerClient> {
+        spinnaker_client(&self.spinnaker_url, self.spinnaker_queries.as_ref())
+    }
+
+    fn spinnaker_env(&self) -> Result<SpinnakerEnvironment> {
+        Ok(SpinnakerEnvironment {
+            client: self.client()?,
+            app_name: self.app_name.clone(),
+            env: self.env.clone(),
+            baseline: self.baseline()
+        })
+    }
+
+    fn baseline(&self) -> Option<Baseline> {
+        match (&self.baseline_commit, &self.baseline_tag) {
+            (Some(commit), _) => Some(Baseline::Commit(commit.clone())),
+            (None, Some(tag)) => Some(Baseline::Tag(tag.clone())),
+            (None, None) => None
+        }
+    }
+}
+
+fn spinnaker_client(spinnaker_url: &str, spinnaker_queries: Option<&PathBuf>) -> Result<SpinnakerClient> {
+    let spinnaker_client = SpinnakerClient::from_client(GraphQLClient::from_client(rest_client_builder(spinnaker_url, Backend::Spinnaker)?.build()?));
+
+    if let Some((username, password)) = SPINNAKER_LOGIN.get() {
+        // The clients are created while the arguments are converted, outside of any async context.
+        tokio::task::block_in_place(|| tokio::runtime::Handle::current().block_on(spinnaker_client.login(username, password.expose_secret())))?;
+    }
+
+    let queries_dir = match spinnaker_queries {
+        Some(queries_dir) => queries_dir,
+        None => return Ok(spinnaker_client)
+    };
+
+    let query_path = queries_dir.join("queries.graphql");
+    let query = fs::read_to_string(&query_path)
+        .with_context(|| format!("Error reading the Spinnaker query file {}", query_path.display()))?;
+
+    let schema_path = queries_dir.join("schema.graphql");
+    let schema = if schema_path.exists() {
+        Some(
+            fs::read_to_string(&schema_path)
+                .with_context(|| format!("Error reading the Spinnaker schema file {}", schema_path.display()))?
+        )
+    } else {
+        None
+    };
+
+    spinnaker_client.with_environment_states_query(&query, schema.as_deref())
+        .with_context(|| format!("Invalid Spinnaker query file {}", query_path.display()))
+}
+
+#[derive(Parser, Debug)]
+struct CommitRangeArgs {
+    #[clap(help = "The Bitbucket project")]
+    project: String,
+
+    #[clap(help = "The Bitbucket repository")]
+    repo: String,
+
+    #[clap(help = "The start commit, branch or tag to get the changelog for, this commit should be more recent than the end commit")]
+    start_commit: String,
+
+    #[clap(help = "The end commit, branch or tag to get the changelog for, this commit should be older than the start commit")]
+    end_commit: String,
+
+    #[clap(long, help = "Compare the start commit against its merge base with the end commit, so that comparing two diverged branches only yields the changes unique to the start commit")]
+    merge_base: bool
+}
+
+#[derive(Parser, Debug)]
+struct PeriodArgs {
+    #[clap(help = "The Bitbucket project")]
+    project: String,
+
+    #[clap(help = "The Bitbucket repository")]
+    repo: String,
+
+    #[clap(long, help = "The branch to get the changelog of, defaults to the default branch of the repository")]
+    branch: Option<String>,
+
+    #[clap(long, value_parser = parse_time, help = "The start of the period, inclusive, as a date such as 2024-05-01 (midnight UTC) or an RFC 3339 time")]
+    since: DateTime<Utc>,
+
+    #[clap(long, value_parser = parse_time, help = "The end of the period, exclusive, as a date such as 2024-05-15 (midnight UTC) or an RFC 3339 time, defaults to now")]
+    until: Option<DateTime<Utc>>
+}
+
+fn parse_time(time: &str) -> Result<DateTime<Utc>> {
+    if let Ok(date) = NaiveDate::parse_from_str(time, "%Y-%m-%d") {
+        return Ok(DateTime::from_utc(date.and_time(NaiveTime::MIN), Utc));
+    }
+
+    DateTime::parse_from_rfc3339(time)
+        .map(|time| time.with_timezone(&Utc))
+        .with_context(|| format!("Invalid time {time}, expected a date such as 2024-05-01 or an RFC 3339 time such as 2024-05-01T09:00:00Z"))
+}
+
+#[derive(Parser, Debug)]
+struct ServiceArgs {
+    #[clap(long, short = 's', help = "The URL to your Spinnaker server, required with --env", env = "SPINNAKER_URL")]
+    spinnaker_url: Option<String>,
+
+    #[clap(
+        long,
+        help = "A directory with a queries.graphql overriding the Spinnaker environment states query, and optionally the schema.graphql of your Spinnaker server",
+        env = "SPINNAKER_QUERIES_DIR"
+    )]
+    spinnaker_queries: Option<PathBuf>,
+
+    #[clap(long, help = "The Spinnaker environment of the service's application to get the changelog for", required_unless_present = "start_commit", conflicts_with = "start_commit")]
+    env: Option<String>,
+
+    #[clap(long, help = "The start commit, branch or tag to get the changelog for, this commit should be more recent than the end commit", requires = "end_commit")]
+    start_commit: Option<String>,
+
+    #[clap(long, help = "The end commit, branch or tag to get the changelog for, this commit should be older than the start commit", requires = "start_commit")]
+    end_commit: Option<String>,
+
+    #[clap(long, help = "Compare the start commit against its merge base with the end commit, so that comparing two diverged branches only yields the changes unique to the start commit", requires = "start_commit")]
+    merge_base: bool,
+
+    #[clap(help = "The name of the service in the configuration file")]
+    name: String
+}
+
+impl ServiceArgs {
+    fn commit_specifier(&self, service: &ServiceConfig) -> Result<CommitSpecifier> {
+        if let Some(env) = &self.env {
+            let spinnaker_url = self.spinnaker_url.as_ref()
+                .with_context(|| "A Spinnaker URL is required to get the changelog of an environment")?;
+            let app_name = service.spinnaker_app.as_ref()
+                .with_context(|| format!("Service {} has no spinnaker-app in the configuration file", self.name))?;
+
+            return Ok(CommitSpecifier::Spinnaker(Box::new(SpinnakerEnvironment {
+                client: spinnaker_client(spinnaker_url, self.spinnaker_queries.as_ref())?,
+                app_name: app_name.clone(),
+                env: env.clone(),
+                baseline: None
+            })));
+        }
+
+        let (project, repo) = service.project.as_ref()
+            .zip(service.repo.as_ref())
+            .with_context(|| format!("Service {} needs a project and a repo in the configuration file to get the changelog of a commit range", self.name))?;
+
+        Ok(CommitSpecifier::CommitRange(GitCommitRange {
+            project: project.clone(),
+            repo: repo.clone(),
+            start_commit: self.start_commit.clone().unwrap_or_default(),
+            end_commit: self.end_commit.clone().unwrap_or_default(),
+            merge_base: self.merge_base
+        }))
+    }
+}
+
+#[derive(Parser, Debug)]
+struct DigestArgs {
+    #[clap(long, short = 's', help = "The URL to your Spinnaker server", env = "SPINNAKER_URL")]
+    spinnaker_url: String,
+
+    #[clap(
+        long,
+        help = "A directory with a queries.graphql overriding the Spinnaker environment states query, and optionally the schema.graphql of your Spinnaker server",
+        env = "SPINNAKER_QUERIES_DIR"
+    )]
+    spinnaker_queries: Option<PathBuf>,
+
+    #[clap(long, help = "The Spinnaker environment of the services' applications to get the deployments to")]
+    env: String,
+
+    #[clap(long, value_parser = parse_since, help = "The start of the digest, as a time ago such as 24h, 7d or 30m, a date such as 2024-05-01 (midnight UTC) or an RFC 3339 time")]
+    since: DateTime<Utc>,
+
+    #[clap(long, help = "A service of the configuration file's catalog to get the deployments of, defaults to every service with a spinnaker-app. Can be repeated")]
+    service: Vec<String>
+}
+
+impl DigestArgs {
+    fn digest_services(&self, config: &Config) -> Result<Vec<DigestService>> {
+        let services: Vec<(&String, &ServiceConfig)> = if self.service.is_empty() {
+            config.services.iter()
+                .filter(|(_, service)| service.spinnaker_app.is_some())
+                .collect()
+        } else {
+            self.service.iter()
+                .map(|name| Ok((name, config.service(name)?)))
+                .collect::<Result<_>>()?
+        };
+
+        if services.is_empty() {
+            bail!("The configuration file has no services with a spinnaker-app to get the deployments of");
+        }
+
+        services.into_iter()
+            .map(|(name, service)| {
+                let app_name = service.spinnaker_app.as_ref()
+                    .with_context(|| format!("Service {name} has no spinnaker-app in the configuration file"))?;
+
+                Ok(DigestService {
+                    name: name.clone(),
+                    team: service.team.clone(),
+                    spinnaker_env: SpinnakerEnvironment {
+                        client: spinnaker_client(&self.spinnaker_url, self.spinnaker_queries.as_ref())?,
+                        app_name: app_name.clone(),
+                        env: self.env.clone(),
+                        baseline: None
+                    }
+                })
+            })
+            .collect()
+    }
+}
+
+/// Parses the start of a period given as a time ago, such as `24h`, `7d` or `30m`, or else as a time like `parse_time`.
+fn parse_since(since: &str) -> Result<DateTime<Utc>> {
+    let time_ago = since.strip_suffix('m').and_then(|minutes| minutes.parse().ok()).map(chrono::Duration::minutes)
+        .or_else(|| since.strip_suffix('h').and_then(|hours| hours.parse().ok()).map(chrono::Duration::hours))
+        .or_else(|| since.strip_suffix('d').and_then(|days| days.parse().ok()).map(chrono::Duration::days));
+
+    match time_ago {
+        Some(time_ago) => Ok(Utc::now() - time_ago),
+        None => parse_time(since)
+            .with_context(|| format!("Invalid start {since}, expected a time ago such as 24h, 7d or 30m, a date such as 2024-05-01 or an RFC 3339 time"))
+    }
+}
+
+#[derive(Parser, Debug)]
+struct JqlArgs {
+    #[clap(help = "The JQL query matching the Jira issues to get the changelog for, e.g. \"fixVersion = 2024.06\"")]
+    query: String
+}
+
+#[derive(Parser, Debug)]
+struct AppsArgs {
+    #[clap(long, short = 's', help = "The URL to your Spinnaker server", env = "SPINNAKER_URL")]
+    spinnaker_url: String
+}
+
+#[derive(Parser, Debug)]
+struct EnvsArgs {
+    #[clap(long, short = 's', help = "The URL to your Spinnaker server", env = "SPINNAKER_URL")]
+    spinnaker_url: String,
+
+    #[clap(help = "The Spinnaker app name")]
+    app_name: String
+}
+
+#[derive(Parser, Debug)]
+struct ReleaseNotesArgs {
+    #[clap(long, help = "The Jira fix version to get the release notes for")]
+    fix_version: String,
+
+    #[clap(long, help = "The Jira project key to restrict the release notes to")]
+    project: Option<String>
+}
+
+impl ReleaseNotesArgs {
+    fn jql(&self) -> String {
+        match &self.project {
+            Some(project) => format!("project = {} AND fixVersion = {} ORDER BY key", jql_string(project), jql_string(&self.fix_version)),
+            None => format!("fixVersion = {} ORDER BY key", jql_string(&self.fix_version))
+        }
+    }
+}
+
+#[derive(Parser, Debug)]
+struct ReleaseTrainArgs {
+    #[clap(long, help = "The Jira fix version of the release train")]
+    fix_version: String,
+
+    #[clap(long, help = "A service of the configuration file's catalog taking part in the release train, defaults to every service with a project and a repo. Can be repeated")]
+    service: Vec<String>
+}
+
+impl ReleaseTrainArgs {
+    fn commit_specifier(&self, config: &Config) -> Result<CommitSpecifier> {
+        let services: Vec<(&String, &ServiceConfig)> = if self.service.is_empty() {
+            config.services.iter()
+                .filter(|(_, service)| service.project.is_some() && service.repo.is_some())
+                .collect()
+        } else {
+            self.service.iter()
+                .map(|name| Ok((name, config.service(name)?)))
+                .collect::<Result<_>>()?
+        };
+
+        let repos: Vec<ScmCoordinates> = services.into_iter()
+            .map(|(name, service)| service.project.as_ref()
+                .zip(service.repo.as_ref())
+                .map(|(project, repo)| ScmCoordinates {
+                    project: project.clone(),
+                    repo: repo.clone()
+                })
+                .with_context(|| format!("Service {name} needs a project and a repo in the configuration file to take part in a release train")))
+            .collect::<Result<_>>()?;
+
+        if repos.is_empty() {
+            bail!("A release train needs services with a project and a repo in the configuration file");
+        }
+
+        Ok(CommitSpecifier::ReleaseTrain(ReleaseTrain {
+            fix_version: self.fix_version.clone(),
+            repos
+        }))
+    }
+}
+
+impl TryFrom<&CommitSpecifierSubcommand> for CommitSpecifier {
+    type Error = anyhow::Error;
+
+    fn try_from(commit_specifier_subcommand: &CommitSpecifierSubcommand) -> Result<Self> {
+        match commit_specifier_subcommand {
+            CommitSpecifierSubcommand::Spinnaker(spinnaker_args) => Ok(CommitSpecifier::Spinnaker(Box::new(spinnaker_args.spinnaker_env()?))),
+            CommitSpecifierSubcommand::Watch(watch_args) => Ok(CommitSpecifier::Spinnaker(Box::new(watch_args.spinnaker.spinnaker_env()?))),
+            CommitSpecifierSubcommand::CommitRange(commit_range) => Ok(CommitSpecifier::CommitRange(GitCommitRange {
+                project: commit_range.project.clone(),
+                repo: commit_range.repo.clone(),
+                start_commit: commit_range.start_commit.clone(),
+                end_commit: commit_range.end_commit.clone(),
+                merge_base: commit_range.merge_base
+            })),
+            CommitSpecifierSubcommand::Jql(jql_args) => Ok(CommitSpecifier::Jql(jql_args.query.clone())),
+            CommitSpecifierSubcommand::Period(period_args) => Ok(CommitSpecifier::TimeWindow(TimeWindow {
+                project: period_args.project.clone(),
+                repo: period_args.repo.clone(),
+                branch: period_args.branch.clone(),
+                since: period_args.since,
+                until: period_args.until.unwrap_or_else(Utc::now)
+            })),
+            CommitSpecifierSubcommand::ReleaseNotes(release_notes_args) => Ok(CommitSpecifier::Jql(release_notes_args.jql())),
+            CommitSpecifierSubcommand::Service(_) => Err(anyhow::anyhow!("The commits of a service are specified by the configuration file")),
+            CommitSpecifierSubcommand::Digest(_) => Err(anyhow::anyhow!("A deploy digest has the commit ranges of several services")),
+            CommitSpecifierSubcommand::ReleaseTrain(_) => Err(anyhow::anyhow!("The repositories of a release train are specified by the configuration file")),
+            CommitSpecifierSubcommand::CompareEnvs(_) => Err(anyhow::anyhow!("Comparing Spinnaker environments does not specify a single commit range")),
+            CommitSpecifierSubcommand::Cache(_) => Err(anyhow::anyhow!("Managing the response cache does not specify commits")),
+            CommitSpecifierSubcommand::Schema(_) => Err(anyhow::anyhow!("Checking the Spinnaker schema does not specify commits")),
+            CommitSpecifierSubcommand::Apps(_) | CommitSpecifierSubcommand::Envs(_) => Err(anyhow::anyhow!("Listing Spinnaker applications or environments does not specify commits"))
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    env_logger::init();
+    log::info!("Parsing arguments");
+
+    let args = Args::parse();
+
+    if let Some(ProgressFormat::Json) = args.progress {
+        progress::set_reporter(Box::new(JsonLinesReporter))
+            .expect("Error installing the progress reporter");
+    }
+
+    RETRY_POLICY.get_or_init(|| retry_policy(&args, args.retries));
+    RETRY_BUDGET.get_or_init(|| Arc::new(RetryBudget::new(args.retry_budget)));
+    USER_AGENTS.get_or_init(|| UserAgents {
+        default: args.user_agent.clone(),
+        backends: args.backend_user_agent.iter().cloned().collect()
+    });
+    RATE_LIMITERS.get_or_init(|| args.rate_limit.iter()
+        .filter_map(|(backend, requests_per_second)| RateLimiter::new(*requests_per_second).ok()
+            .map(|rate_limiter| (*backend, Arc::new(rate_limiter))))
+        .collect());
+    CONNECTION_OPTIONS.get_or_init(|| ConnectionOptions {
+        pool_max_idle_per_host: args.pool_max_idle_per_host,
+        pool_idle_timeout: args.pool_idle_timeout.map(Duration::from_secs),
+        tcp_keepalive: args.tcp_keepalive.map(Duration::from_secs),
+        http_version: args.http_version,
+        resolve: args.resolve.clone()
+    });
+
+    // Invalid credentials, such as missing AWS keys, fail the run like any other error rather than panicking.
+    match credentials(&args) {
+        Ok(credentials) => CREDENTIALS.get_or_init(|| credentials),
+        Err(error) => return report_error(&args, error)
+    };
+
+    if let Some(spinnaker_login) = args.spinnaker_username.clone().zip(args.spinnaker_password.clone()) {
+        SPINNAKER_LOGIN.get_or_init(|| spinnaker_login);
+    }
+
+    match client_identity(&args) {
+        Ok(Some(client_identity)) => { CLIENT_IDENTITY.get_or_init(|| client_identity); },
+        Ok(None) => (),
+        Err(error) => return report_error(&args, error)
+    }
+
+    match root_certificates(&args) {
+        Ok(root_certificates) => ROOT_CERTIFICATES.get_or_init(|| root_certificates),
+        Err(error) => return report_error(&args, error)
+    };
+
+    if let Some(proxy) = proxy(&args) {
+        PROXY.get_or_init(|| proxy);
+    }
+
+    match http_cache(&args) {
+        Ok(Some(http_cache)) => { HTTP_CACHE.get_or_init(|| http_cache); },
+        Ok(None) => (),
+        Err(error) => return report_error(&args, error)
+    }
+
+    if args.insecure {
+        eprintln!("WARNING: --insecure disables TLS certificate verification. The servers aren't authenticated, so anyone on the network can read and change the requests, credentials included. Only use it in lab environments.");
+        INSECURE.get_or_init(|| true);
+    }
+    PRIORITY_POLICIES.get_or_init(|| priority_policies(&args));
+
+    #[cfg(feature = "fault-injection")]
+    if args.chaos {
+        fault::set_injector(FaultInjector::chaos(args.fault_seed))
+            .expect("Error installing the fault injector");
+    } else if !args.inject_fault.is_empty() {
+        fault::set_injector(FaultInjector::new(args.fault_seed, args.inject_fault.clone()))
+            .expect("Error installing the fault injector");
+    }
+
+    let result = match &args.commit_specifier {
+        CommitSpecifierSubcommand::CompareEnvs(compare_envs_args) => print_environment_comparison(&args, compare_envs_args).await,
+        CommitSpecifierSubcommand::Digest(digest_args) => print_deploy_digest(&args, digest_args).await,
+        CommitSpecifierSubcommand::Apps(apps_args) => print_spinnaker_apps(apps_args).await,
+        CommitSpecifierSubcommand::Envs(envs_args) => print_spinnaker_envs(envs_args).await,
+        CommitSpecifierSubcommand::Watch(watch_args) => watch_spinnaker_env(&args, watch_args).await,
+        CommitSpecifierSubcommand::Cache(cache_subcommand) => manage_cache(cache_subcommand),
+        CommitSpecifierSubcommand::Schema(SchemaSubcommand::Check(schema_check_args)) => check_spinnaker_schema(schema_check_args).await,
+        _ => match args.window_size {
+            Some(window_size) => print_changelog_windows(&args, window_size).await,
+            None => print_changelog(&args).await.map(|_| ())
+        }
+    };
+
+    let exit_code = match result {
+        Ok(_) => ExitCode::SUCCESS,
+        Err(error) => report_error(&args, error)
+    };
+
+    print_retried_requests();
+    exit_code
+}
+
+/// Prints the error failing the run, as a TeamCity build problem too with --teamcity, returning the exit code
+/// of a failed run.
+fn report_error(args: &Args, error: anyhow::Error) -> ExitCode {
+    eprintln!("Error: {error}");
+
+    if args.teamcity {
+        println!("{}", teamcity_build_problem(&format!("{error:#}")));
+    }
+
+    ExitCode::FAILURE
+}
+
+async fn print_changelog(args: &Args) -> Result<Changelog> {
+    log::info!("Getting changelog for args: {}", redact_urls(&format!("{args:?}")));
+
+    if let Some(retry_budget) = RETRY_BUDGET.get() {
+        retry_budget.reset();
+    }
+
+    let scm_provider = scm_provider(args)?;
+    let jira_url = args.jira_url.as_ref()
+        .with_context(|| "A JIRA URL is required to generate a changelog")?;
+    let jira_client = JiraClient::from_client(rest_client(jira_url, Backend::Jira)?);
+
+    let config = config(args)?;
+    let commit_specifier = commit_specifier(args, &config)?;
+
+    let mut changelog: Changelog = Changelog::new(
+        scm_provider.as_ref(),
+        &jira_client,
+        &commit_specifier
+    ).await?;
+
+    if let Some(jira_project) = service_jira_project(args, &config)? {
+        changelog.retain_jira_project(jira_project);
+    }
+
+    changelog.compute_cycle_times(&args.cycle_start_status, &args.cycle_end_status);
+    changelog.extract_release_notes();
+    changelog.notes = deployment_notes(args)?;
+    changelog.detect_migrations(scm_provider.as_ref(), &args.migration_globs).await?;
+    changelog.detect_feature_flags(scm_provider.as_ref(), &args.feature_flag_globs, &args.feature_flag_patterns).await?;
+    changelog.check_dependencies(scm_provider.as_ref(), &jira_client).await?;
+
+    // The pull requests lose their repositories when the changelog is redacted, so their sizes are found first.
+    if args.pull_request_sizes {
+        changelog.compute_pull_request_sizes(scm_provider.as_ref()).await?;
+    }
+
+    if args.worklogs {
+        changelog.aggregate_worklogs(&jira_client).await?;
+    }
+
+    // The dependencies are removed when the changelog is redacted, so they are checked first.
+    if args.fail_on_unresolved_dependencies {
+        fail_on_unresolved_dependencies(&changelog)?;
+    }
+
+    if let CommitSpecifier::Spinnaker(spinnaker_env) = &commit_specifier {
+        if segment_versions(args) {
+            let pending_versions = spinnaker_env.pending_versions().await?;
+            changelog.segment(scm_provider.as_ref(), &pending_versions).await?;
+        }
+    }
+
+    // The pull requests lose their repositories when the changelog is redacted, so they are commented on first.
+    if args.comment_on_pull_requests {
+        changelog.comment_on_pull_requests(scm_provider.as_ref(), &pull_request_comment(&changelog, args))
+            .await?;
+    }
+
+    if let Some(retry_budget) = RETRY_BUDGET.get() {
+        changelog.metadata.retried_requests = retry_budget.retried_requests();
+    }
+
+    // The metrics are internal, so they are computed before the changelog is redacted.
+    let deployment_record = DeploymentRecord::from(&changelog);
+
+    // The tracked issues are removed when the changelog is redacted, and are in every output, so they are linked first.
+    let issue_trackers = issue_trackers(args)?;
+    if !issue_trackers.is_empty() {
+        changelog.link_tracked_issues(&issue_trackers).await?;
+    }
+
+    // Every output redacts its own copy of the changelog, so they are written before it is redacted.
+    write_outputs(&changelog, &config, args)?;
+
+    if args.external {
+        changelog.redact_for_external();
+    }
+
+    let (format, title) = match &args.commit_specifier {
+        CommitSpecifierSubcommand::ReleaseNotes(release_notes_args) => (
+            args.format.unwrap_or(OutputFormat::ReleaseNotes),
+            Some(format!("Release notes {}", release_notes_args.fix_version))
+        ),
+        CommitSpecifierSubcommand::ReleaseTrain(release_train_args) => (
+            args.format.unwrap_or(OutputFormat::Json),
+            Some(format!("Release train {}", release_train_args.fix_version))
+        ),
+        _ => (args.format.unwrap_or(OutputFormat::Json), None)
+    };
+
+    let render_options = RenderOptions {
+        timezone: args.timezone,
+        date_format: args.date_format.clone(),
+        title,
+        rewrite_rules: config.rewrite_rules,
+        statuses: config.statuses
+    };
+
+    if args.lint_notes || args.strict_notes {
+        lint_notes(&changelog, &render_options, args.strict_notes)?;
+    }
+
+    let rendered_changelog = render_bytes(&changelog, format, &render_options);
+
+    let mut stdout = std::io::stdout().lock();
+    stdout.write_all(&rendered_changelog)?;
+    if !format.is_binary() {
+        writeln!(stdout)?;
+    }
+
+    if args.teamcity {
+        teamcity_messages(&changelog).iter()
+            .try_for_each(|message| writeln!(stdout, "{message}"))?;
+    }
+
+    let file_name = format!(
+        "changelog-{}.{}",
+        changelog.metadata.generated_at.format("%Y%m%dT%H%M%SZ"),
+        format.extension()
+    );
+
+    if args.github_output {
+        // RUNNER_TEMP is emptied at the end of every GitHub Actions job.
+        let changelog_path = std::env::var_os("RUNNER_TEMP")
+            .map(PathBuf::from)
+            .unwrap_or_else(std::env::temp_dir)
+            .join(&file_name);
+
+        fs::write(&changelog_path, &rendered_changelog)
+            .with_context(|| format!("Error writing the changelog to {}", changelog_path.display()))?;
+
+        write_github_outputs(&changelog, &changelog_path, &render_options)?;
+    }
+
+    if let Some(issue_key) = &args.attach_to_issue {
+        jira_client.add_attachment(issue_key, &file_name, format.mime_type(), rendered_changelog.clone())
+            .await?;
+    }
+
+    if let (Some(confluence_url), Some(page_id)) = (&args.confluence_url, &args.attach_to_page) {
+        ConfluenceClient::from_client(rest_client(confluence_url, Backend::Confluence)?)
+            .add_attachment(page_id, &file_name, format.mime_type(), rendered_changelog)
+            .await?;
+    }
+
+    for metrics_target in &args.export_metrics {
+        let pushgateway_client = match metrics_target {
+            MetricsTarget::Pushgateway(url) => Some(rest_client_builder(url.as_str(), Backend::Pushgateway)?.build()?),
+            _ => None
+        };
+
+        metrics_target.export_with_client(&deployment_record, pushgateway_client.as_ref()).await?;
+    }
+
+    let webhooks = webhook_notifier(args)?;
+    if !webhooks.is_empty() {
+        webhooks.notify(&changelog, &NotificationMetadata::from(&changelog)).await?;
+    }
+
+    Ok(changelog)
+}
+
+async fn print_changelog_windows(args: &Args, window_size: usize) -> Result<()> {
+    log::info!("Getting changelog windows of {window_size} commits for args: {}", redact_urls(&format!("{args:?}")));
+
+    if let Some(retry_budget) = RETRY_BUDGET.get() {
+        retry_budget.reset();
+    }
+
+    let scm_provider = scm_provider(args)?;
+    let jira_url = args.jira_url.as_ref()
+        .with_context(|| "A JIRA URL is required to generate a changelog")?;
+    let jira_client = JiraClient::from_client(rest_client(jira_url, Backend::Jira)?);
+
+    if segment_versions(args) {
+        bail!("The changelog of an environment can't be segmented by version when it is generated in windows");
+    }
+
+    let config = config(args)?;
+    if !config.outputs.is_empty() {
+        bail!("The outputs of the configuration file can't be written when the changelog is generated in windows");
+    }
+
+    let commit_specifier = commit_specifier(args, &config)?;
+    let jira_project = service_jira_project(args, &config)?;
+    let issue_trackers = issue_trackers(args)?;
+
+    let format = args.format.unwrap_or(OutputFormat::Json);
+    let render_options = RenderOptions {
+        timezone: args.timezone,
+        date_format: args.date_format.clone(),
+        title: None,
+        rewrite_rules: config.rewrite_rules.clone(),
+        statuses: config.statuses.clone()
+    };
+
+    let mut windows = Changelog::windows(
+        scm_provider.as_ref(),
+        &jira_client,
+        &commit_specifier,
+        window_size
+    ).await?;
+
+    while let Some(mut changelog) = windows.next().await? {
+        if let Some(jira_project) = jira_project {
+            changelog.retain_jira_project(jira_project);
+        }
+
+        changelog.compute_cycle_times(&args.cycle_start_status, &args.cycle_end_status);
+        changelog.extract_release_notes();
+        changelog.detect_migrations(scm_provider.as_ref(), &args.migration_globs).await?;
+        changelog.detect_feature_flags(scm_provider.as_ref(), &args.feature_flag_globs, &args.feature_flag_patterns).await?;
+        changelog.check_dependencies(scm_provider.as_ref(), &jira_client).await?;
+
+        if args.pull_request_sizes {
+            changelog.compute_pull_request_sizes(scm_provider.as_ref()).await?;
+        }
+
+        if args.worklogs {
+            changelog.aggregate_worklogs(&jira_client).await?;
+        }
+
+        if !issue_trackers.is_empty() {
+            changelog.link_tracked_issues(&issue_trackers).await?;
+        }
+
+        if args.external {
+            changelog.redact_for_external();
+        }
+
+        let mut stdout = std::io::stdout().lock();
+        stdout.write_all(&render_bytes(&changelog, format, &render_options))?;
+        if !format.is_binary() {
+            writeln!(stdout)?;
+        }
+
+        log::info!("{} commits left to generate the changelog of", windows.remaining_commits());
+    }
+
+    Ok(())
+}
+
+async fn print_environment_comparison(args: &Args, compare_envs_args: &CompareEnvsArgs) -> Result<()> {
+    log::info!("Comparing environments for args: {}", redact_urls(&format!("{args:?}")));
+
+    let scm_provider = scm_provider(args)?;
+    let jira_url = args.jira_url.as_ref()
+        .with_context(|| "A JIRA URL is required to compare environments")?;
+    let jira_client = JiraClient::from_client(rest_client(jira_url, Backend::Jira)?);
+
+    let env_a = compare_envs_args.spinnaker_env(&compare_envs_args.env_a)?;
+    let env_b = compare_envs_args.spinnaker_env(&compare_envs_args.env_b)?;
+
+    let mut comparison = EnvironmentComparison::new(
+        scm_provider.as_ref(),
+        &jira_client,
+        &env_a,
+        &env_b
+    ).await?;
+
+    comparison.only_in_a.compute_cycle_times(&args.cycle_start_status, &args.cycle_end_status);
+    comparison.only_in_b.compute_cycle_times(&args.cycle_start_status, &args.cycle_end_status);
+    comparison.only_in_a.extract_release_notes();
+    comparison.only_in_b.extract_release_notes();
+
+    if args.external {
+        comparison.only_in_a.redact_for_external();
+        comparison.only_in_b.redact_for_external();
+    }
+
+    let config = config(args)?;
+    let render_options = RenderOptions {
+        timezone: args.timezone,
+        date_format: args.date_format.clone(),
+        title: None,
+        rewrite_rules: config.rewrite_rules,
+        statuses: config.statuses
+    };
+
+    println!("{}", render_comparison(&comparison, args.format.unwrap_or(OutputFormat::Json), &render_options));
+
+    Ok(())
+}
+
+async fn print_deploy_digest(args: &Args, digest_args: &DigestArgs) -> Result<()> {
+    log::info!("Getting the deploy digest for args: {}", redact_urls(&format!("{args:?}")));
+
+    let config = config(args)?;
+    let services = digest_args.digest_services(&config)?;
+
+    let scm_provider = scm_provider(args)?;
+    let jira_url = args.jira_url.as_ref()
+        .with_context(|| "A JIRA URL is required to get a deploy digest")?;
+    let jira_client = JiraClient::from_client(rest_client(jira_url, Backend::Jira)?);
+
+    let mut digest = DeployDigest::new(
+        scm_provider.as_ref(),
+        &jira_client,
+        &digest_args.env,
+        digest_args.since,
+        &services
+    ).await?;
+
+    for deployment in digest.teams.iter_mut().flat_map(|team| team.deployments.iter_mut()) {
+        if let Some(jira_project) = &config.service(&deployment.service)?.jira_project {
+            deployment.changelog.retain_jira_project(jira_project);
+        }
+    }
+
+    for changelog in digest.changelogs_mut() {
+        changelog.compute_cycle_times(&args.cycle_start_status, &args.cycle_end_status);
+        changelog.extract_release_notes();
+
+        if args.external {
+            changelog.redact_for_external();
+        }
+    }
+
+    let render_options = RenderOptions {
+        timezone: args.timezone,
+        date_format: args.date_format.clone(),
+        title: None,
+        rewrite_rules: config.rewrite_rules,
+        statuses: config.statuses
+    };
+
+    println!("{}", render_digest(&digest, args.format.unwrap_or(OutputFormat::Json), &render_options));
+
+    Ok(())
+}
+
+async fn watch_spinnaker_env(args: &Args, watch_args: &WatchArgs) -> Result<()> {
+    let spinnaker_env = watch_args.spinnaker.spinnaker_env()?;
+    let interval = Duration::from_secs(watch_args.interval);
+    let debounce = Duration::from_secs(watch_args.debounce);
+
+    let notifier = FanOutNotifier::new();
+
+    #[cfg(feature = "desktop-notifications")]
+    let notifier = {
+        let mut notifier = notifier;
+
+        if watch_args.desktop_notifications {
+            notifier.add(Box::new(DesktopNotifier {
+                render_options: RenderOptions {
+                    timezone: args.timezone,
+                    date_format: args.date_format.clone(),
+                    statuses: config(args)?.statuses,
+                    ..RenderOptions::default()
+                }
+            }));
+        }
+
+        notifier
+    };
+
+    let mut last_pending_commit = spinnaker_env.pending_commit()
+        .await?;
+    let mut new_pending_commit: Option<(String, Instant)> = None;
+
+    log::info!(
+        "Watching environment {} of Spinnaker application {}, pending commit: {:?}",
+        spinnaker_env.env,
+        spinnaker_env.app_name,
+        last_pending_commit
+    );
+
+    loop {
+        tokio::time::sleep(interval).await;
+
+        let pending_commit = match spinnaker_env.pending_commit().await {
+            Ok(pending_commit) => pending_commit,
+            Err(error) => {
+                log::warn!("Error polling the Spinnaker environment, retrying in {} seconds: {error:#}", watch_args.interval);
+                continue;
+            }
+        };
+
+        let commit = match pending_commit {
+            Some(commit) if last_pending_commit.as_ref() != Some(&commit) => commit,
+            _ => {
+                last_pending_commit = pending_commit;
+                new_pending_commit = None;
+                continue;
+            }
+        };
+
+        // A new pending version only gets a changelog once it has stayed pending for the debounce period, so that
+        // versions superseded by a quick succession of builds are skipped.
+        let pending_since = match &new_pending_commit {
+            Some((new_commit, pending_since)) if new_commit == &commit => *pending_since,
+            _ => Instant::now()
+        };
+
+        if pending_since.elapsed() < debounce {
+            new_pending_commit = Some((commit, pending_since));
+            continue;
+        }
+
+        log::info!("Pending commit changed to {commit}, printing its changelog");
+
+        match print_changelog(args).await {
+            Ok(changelog) if !notifier.is_empty() => {
+                let metadata = NotificationMetadata {
+                    title: format!("New {} deployment pending in {}", spinnaker_env.app_name, spinnaker_env.env),
+                    app_name: Some(spinnaker_env.app_name.clone()),
+                    env: Some(spinnaker_env.env.clone())
+                };
+
+                if let Err(error) = notifier.notify(&changelog, &metadata).await {
+                    log::warn!("Error notifying the pending deployment: {error}");
+                }
+            },
+            Ok(_) => (),
+            Err(error) => eprintln!("Error: {error}")
+        }
+
+        print_retried_requests();
+
+        last_pending_commit = Some(commit);
+        new_pending_commit = None;
+    }
+}
+
+fn webhook_notifier(args: &Args) -> Result<FanOutNotifier> {
+    let mut webhook_notifier = FanOutNotifier::new();
+
+    for webhook_url in &args.webhook_url {
+        let client = rest_client_builder(webhook_url.as_str(), Backend::Webhook)?.build()?;
+        webhook_notifier.add(Box::new(WebhookNotifier::new(client, args.webhook_secret.clone())));
+    }
+
+    Ok(webhook_notifier)
+}
+
+fn deployment_notes(args: &Args) -> Result<Vec<String>> {
+    let mut notes = match &args.notes_file {
+        Some(notes_file) => fs::read_to_string(notes_file)
+            .with_context(|| format!("Error reading the notes file {}", notes_file.display()))?
+            .lines()
+            .map(|line| line.trim().trim_start_matches(['-', '*']).trim().to_string())
+            .filter(|note| !note.is_empty())
+            .collect(),
+        None => Vec::new()
+    };
+
+    notes.extend(args.note.iter().cloned());
+
+    Ok(notes)
+}
+
+async fn print_spinnaker_apps(apps_args: &AppsArgs) -> Result<()> {
+    let spinnaker_client = spinnaker_client(&apps_args.spinnaker_url, None)?;
+
+    let mut app_names: Vec<String> = spinnaker_client.list_applications()
+        .await?
+        .into_iter()
+        .map(|application| application.name)
+        .collect();
+
+    app_names.sort();
+    app_names.iter()
+        .for_each(|app_name| println!("{app_name}"));
+
+    Ok(())
+}
+
+async fn print_spinnaker_envs(envs_args: &EnvsArgs) -> Result<()> {
+    let spinnaker_client = spinnaker_client(&envs_args.spinnaker_url, None)?;
+
+    spinnaker_client.list_environments(&envs_args.app_name)
+        .await?
+        .iter()
+        .for_each(|env| println!("{env}"));
+
+    Ok(())
+}
+
+async fn check_spinnaker_schema(schema_check_args: &SchemaCheckArgs) -> Result<()> {
+    let spinnaker_client = spinnaker_client(&schema_check_args.spinnaker_url, None)?;
+
+    let incompatibilities = spinnaker_client.check_schema()
+        .await?;
+
+    if incompatibilities.is_empty() {
+        println!("The compiled queries are compatible with the schema of {}", schema_check_args.spinnaker_url);
+        return Ok(());
+    }
+
+    incompatibilities.iter()
+        .for_each(|incompatibility| println!("{incompatibility}"));
+
+    bail!("Found {} incompatibilities with the schema of {}", incompatibilities.len(), schema_check_args.spinnaker_url)
+}
+
+fn manage_cache(cache_subcommand: &CacheSubcommand) -> Result<()> {
+    let cache = HTTP_CACHE.get()
+        .with_context(|| "A cache directory is required to manage the response cache, with --cache-dir or the [cache] table of --config")?;
+
+    match cache_subcommand {
+        CacheSubcommand::Stats => println!("{}", cache.stats()?),
+        CacheSubcommand::Clear => {
+            cache.clear()?;
+            println!("Cleared the response cache in {}", cache.directory().display());
+        }
+    }
+
+    Ok(())
+}
+
+fn lint_notes(changelog: &Changelog, render_options: &RenderOptions, strict: bool) -> Result<()> {
+    let warnings = lint_release_notes(&render_release_notes(changelog, render_options));
+
+    warnings.iter()
+        .for_each(|warning| eprintln!("Warning: release notes {warning}"));
+
+    if strict && !warnings.is_empty() {
+        bail!("The release notes have {} lint warnings", warnings.len());
+    }
+
+    Ok(())
+}
+
+fn fail_on_unresolved_dependencies(changelog: &Changelog) -> Result<()> {
+    changelog.unresolved_dependencies()
+        .for_each(|dependency| eprintln!("Warning: pull request #{} depends on {} ({})", dependency.pull_request_id, dependency.reference, dependency.status));
+
+    let unresolved_count = changelog.unresolved_dependencies().count();
+    if unresolved_count > 0 {
+        bail!("The pull requests have {unresolved_count} unresolved dependencies");
+    }
+
+    Ok(())
+}
+
+fn commit_specifier(args: &Args, config: &Config) -> Result<CommitSpecifier> {
+    match &args.commit_specifier {
+        CommitSpecifierSubcommand::Service(service_args) => service_args.commit_specifier(config.service(&service_args.name)?),
+        CommitSpecifierSubcommand::ReleaseTrain(release_train_args) => release_train_args.commit_specifier(config),
+        commit_specifier_subcommand => CommitSpecifier::try_from(commit_specifier_subcommand)
+    }
+}
+
+/// Returns the Jira project the changelog is restricted to, when it is the changelog of a service of the catalog.
+fn service_jira_project<'a>(args: &Args, config: &'a Config) -> Result<Option<&'a str>> {
+    match &args.commit_specifier {
+        CommitSpecifierSubcommand::Service(service_args) => Ok(config.service(&service_args.name)?.jira_project.as_deref()),
+        _ => Ok(None)
+    }
+}
+
+/// Returns whether the changelog of a Spinnaker environment is segmented by its pending versions.
+fn segment_versions(args: &Args) -> bool {
+    match &args.commit_specifier {
+        CommitSpecifierSubcommand::Spinnaker(spinnaker_args) => spinnaker_args.segment_versions,
+        CommitSpecifierSubcommand::Watch(watch_args) => watch_args.spinnaker.segment_versions,
+        _ => false
+    }
+}
+
+fn write_outputs(changelog: &Changelog, config: &Config, args: &Args) -> Result<()> {
+    let render_options = RenderOptions {
+        timezone: args.timezone,
+        date_format: args.date_format.clone(),
+        title: None,
+        rewrite_rules: config.rewrite_rules.clone(),
+        statuses: config.statuses.clone()
+    };
+
+    config.outputs.iter()
+        .try_for_each(|(name, output)| {
+            fs::write(&output.path, output.render(changelog, &render_options))
+                .with_context(|| format!("Error writing the {name} output to {}", output.path.display()))
+        })
+}
+
+fn config(args: &Args) -> Result<Config> {
+    match &args.config {
+        Some(config_path) => Config::load(config_path),
+        None => Ok(Config::default())
+    }
+}
+
+/// Returns the response cache of the run, configured by the cache flags, or else by the [cache] table of the
+/// configuration file. Responses are only cached with a cache directory.
+fn http_cache(args: &Args) -> Result<Option<Arc<HttpCache>>> {
+    let cache_config = config(args)?.cache;
+
+    let cache_dir = match args.cache_dir.clone().or(cache_config.directory) {
+        Some(cache_dir) => cache_dir,
+        None => return Ok(None)
+    };
+
+    let memory_entries = args.cache_memory_entries
+        .or(cache_config.memory_entries)
+        .unwrap_or(DEFAULT_CACHE_MEMORY_ENTRIES);
+    let ttl = args.cache_ttl
+        .or(cache_config.ttl)
+        .unwrap_or(DEFAULT_CACHE_TTL);
+
+    Ok(Some(Arc::new(HttpCache::new(cache_dir, memory_entries, Duration::from_secs(ttl)))))
+}
+
+fn credentials(args: &Args) -> Result<HashMap<Backend, Credentials>> {
+    let tokens = [
+        (Backend::Bitbucket, &args.bitbucket_token),
+        (Backend::Jira, &args.jira_token),
+        (Backend::Spinnaker, &args.spinnaker_token)
+    ]
+        .into_iter()
+        .filter_map(|(backend, token)| token.as_ref()
+            .map(|token| (backend, Credentials::Bearer(token.clone()))));
+
+    let passwords = [
+        (Backend::Bitbucket, &args.bitbucket_username, &args.bitbucket_password),
+        (Backend::Gerrit, &args.gerrit_username, &args.gerrit_password),
+        (Backend::Jira, &args.jira_username, &args.jira_password),
+        (Backend::Confluence, &args.confluence_username, &args.confluence_password)
+    ]
+        .into_iter()
+        .filter_map(|(backend, username, password)| Some((backend, Credentials::Basic {
+            username: username.clone()?,
+            password: password.clone()?
+        })));
+
+    let oauth2 = match (&args.jira_client_id, &args.jira_client_secret) {
+        (Some(client_id), Some(client_secret)) => Some((Backend::Jira, Credentials::OAuth2(Arc::new(OAuth2TokenProvider::new(OAuth2ClientCredentials {
+            token_url: args.jira_token_url.0.clone(),
+            client_id: client_id.clone(),
+            client_secret: client_secret.clone(),
+            scope: args.jira_scope.clone()
+        })?)))),
+        _ => None
+    };
+
+    let cookie = args.spinnaker_session_cookie.as_ref()
+        .map(|cookie| (Backend::Spinnaker, Credentials::Cookie(cookie.clone())));
+
+    let token_commands = args.token_command.iter()
+        .map(|(backend, command)| {
+            let token_command = command.clone();
+            let token = RefreshableToken::new(move || run_token_command(token_command.clone()))
+                .identified_by(command);
+
+            (*backend, Credentials::Refreshable(Arc::new(token)))
+        });
+
+    let credentials = tokens.chain(passwords)
+        .chain(oauth2)
+        .chain(cookie)
+        .chain(token_commands);
+
+    #[cfg(feature = "aws-sigv4")]
+    let credentials = credentials.chain(aws_sigv4_credentials(args)?);
+
+    #[cfg(feature = "spnego")]
+    let credentials = credentials.chain(negotiate_credentials(args)?);
+
+    Ok(credentials.collect())
+}
+
+/// Runs a command of --token-command, returning the token it prints.
+async fn run_token_command(command: String) -> Result<String> {
+    log::info!("Running the token command {command}");
+
+    tokio::task::spawn_blocking(move || {
+        let output = match cfg!(windows) {
+            true => std::process::Command::new("cmd").args(["/C", &command]).output(),
+            false => std::process::Command::new("sh").args(["-c", &command]).output()
+        }
+            .with_context(|| format!("Error running the token command {command}"))?;
+
+        if !output.status.success() {
+            bail!("The token command {command} failed with {}: {}", output.status, String::from_utf8_lossy(&output.stderr).trim());
+        }
+
+        match String::from_utf8_lossy(&output.stdout).trim() {
+            "" => bail!("The token command {command} printed no token"),
+            token => Ok(token.to_string())
+        }
+    })
+        .await?
+}
+
+#[cfg(feature = "aws-sigv4")]
+fn aws_sigv4_credentials(args: &Args) -> Result<Option<(Backend, Credentials)>> {
+    let region = match &args.spinnaker_aws_region {
+        Some(region) => region,
+        None => return Ok(None)
+    };
+
+    let signer = SigV4Signer::new(AwsCredentials::from_environment()?, region, &args.spinnaker_aws_service);
+
+    Ok(Some((Backend::Spinnaker, Credentials::AwsSigV4(Arc::new(signer)))))
+}
+
+/// Returns the SPNEGO credentials of the servers of --negotiate, which replace their other credentials.
+#[cfg(feature = "spnego")]
+fn negotiate_credentials(args: &Args) -> Result<Vec<(Backend, Credentials)>> {
+    if args.negotiate.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let negotiator = Arc::new(SpnegoAuthenticator::new()?);
+
+    Ok(args.negotiate.iter()
+        .map(|backend| (*backend, Credentials::Negotiate(negotiator.clone())))
+        .collect())
+}
+
+fn client_identity(args: &Args) -> Result<Option<ClientIdentity>> {
+    let client_cert = match &args.client_cert {
+        Some(client_cert) => client_cert,
+        None => return Ok(None)
+    };
+
+    let client_identity = match &args.client_key {
+        Some(client_key) => ClientIdentity::from_pem_files(client_cert, client_key)?,
+        None => ClientIdentity::from_pkcs12_file(client_cert, args.client_cert_password.as_ref().map(SecretString::expose_secret).unwrap_or_default())?
+    };
+
+    Ok(Some(client_identity))
+}
+
+fn proxy(args: &Args) -> Option<ProxyOptions> {
+    let (username, password) = match args.proxy_user.as_ref().map(|proxy_user| proxy_user.expose_secret()) {
+        Some(proxy_user) => match proxy_user.split_once(':') {
+            Some((username, password)) => (Some(username.to_string()), Some(SecretString::from(password))),
+            None => (Some(proxy_user.to_string()), None)
+        },
+        None => (None, None)
+    };
+
+    args.proxy.as_ref().map(|url| ProxyOptions {
+        url: url.0.clone(),
+        username,
+        password,
+        no_proxy: args.no_proxy.clone()
+    })
+}
+
+fn root_certificates(args: &Args) -> Result<Vec<Certificate>> {
+    let root_certificates = args.ca_cert.iter()
+        .map(|ca_cert| read_root_certificates(ca_cert))
+        .collect::<Result<Vec<Vec<Certificate>>>>()?;
+
+    Ok(root_certificates.into_iter().flatten().collect())
+}
+
+fn priority_policies(args: &Args) -> BTreeMap<RequestPriority, PriorityPolicy> {
+    [
+        (RequestPriority::Critical, args.critical_timeout, args.critical_retries),
+        (RequestPriority::Bulk, args.bulk_timeout, args.bulk_retries)
+    ]
+        .into_iter()
+        .filter(|(_, timeout, retries)| timeout.is_some() || retries.is_some())
+        .map(|(priority, timeout, retries)| (priority, PriorityPolicy {
+            timeout: timeout.map(Duration::from_secs),
+            retry_policy: retry_policy(args, retries.unwrap_or(args.retries))
+        }))
+        .collect()
+}
+
+/// Returns the retry policy set with --retry-backoff, --max-retry-backoff, --no-retry-jitter and --retry-status,
+/// retrying requests up to `max_retries` times.
+fn retry_policy(args: &Args, max_retries: u32) -> RetryPolicy {
+    RetryPolicy {
+        max_retries,
+        initial_backoff: Duration::from_millis(args.retry_backoff),
+        max_backoff: Duration::from_millis(args.max_retry_backoff),
+        jitter: !args.no_retry_jitter,
+        retriable_statuses: match args.retry_status.is_empty() {
+            true => RETRIABLE_STATUSES.to_vec(),
+            false => args.retry_status.clone()
+        }
+    }
+}
+
+fn rest_client(base_url: &str, backend: Backend) -> Result<RestClient> {
+    let mut rest_client_builder = rest_client_builder(base_url, backend)?;
+    rest_client_builder.cache = HTTP_CACHE.get().cloned();
+
+    rest_client_builder.build()
+}
+
+fn rest_client_builder(base_url: &str, backend: Backend) -> Result<RestClientBuilder> {
+    let mut rest_client_builder = RestClient::builder(base_url)?;
+    rest_client_builder.cache_namespace = backend.to_possible_value()
+        .map(|value| value.get_name().to_string())
+        .unwrap_or_default();
+    rest_client_builder.retry_policy = RETRY_POLICY.get().cloned().unwrap_or_default();
+    rest_client_builder.retry_budget = RETRY_BUDGET.get().cloned();
+    rest_client_builder.priority_policies = PRIORITY_POLICIES.get().cloned().unwrap_or_default();
+    rest_client_builder.connection = CONNECTION_OPTIONS.get().cloned().unwrap_or_default();
+    rest_client_builder.rate_limiter = RATE_LIMITERS.get()
+        .and_then(|rate_limiters| rate_limiters.get(&backend))
+        .cloned();
+
+    if let Some(user_agent) = USER_AGENTS.get().and_then(|user_agents| user_agents.get(backend)) {
+        rest_client_builder.user_agent = user_agent.clone();
+    }
+
+    rest_client_builder.credentials = CREDENTIALS.get()
+        .and_then(|credentials| credentials.get(&backend))
+        .cloned();
+    rest_client_builder.identity = CLIENT_IDENTITY.get().cloned();
+    rest_client_builder.root_certificates = ROOT_CERTIFICATES.get().cloned().unwrap_or_default();
+    rest_client_builder.accept_invalid_certs = INSECURE.get().copied().unwrap_or_default();
+    rest_client_builder.proxy = PROXY.get().cloned();
+
+    Ok(rest_client_builder)
+}
+
+/// Prints the requests that were retried or failed since the retry budget was last reset on stderr, so that
+/// transient failures are summarized once instead of being interleaved with the logs.
+fn print_retried_requests() {
+    let retry_budget = match RETRY_BUDGET.get() {
+        Some(retry_budget) => retry_budget,
+        None => return
+    };
+
+    let retried_requests = retry_budget.retried_requests();
+    if retried_requests.is_empty() {
+        return;
+    }
+
+    eprintln!("Retried or failed requests:");
+    retried_requests.iter()
+        .for_each(|retried_request| eprintln!("  {retried_request}"));
+
+    if retry_budget.is_exhausted() {
+        eprintln!("The retry budget was exhausted, later failures were not retried");
+    }
+}
+
+fn scm_provider(args: &Args) -> Result<Box<dyn ScmProvider>> {
+    if let Some((project, _)) = args.bitbucket_route_token.iter()
+        .find(|(project, _)| !args.bitbucket_route.iter().any(|(route_project, _)| route_project.eq_ignore_ascii_case(project)))
+    {
+        bail!("The Bitbucket route token of project {project} has no --bitbucket-route");
+    }
+
+    match args.scm {
+        ScmProviderKind::Bitbucket if args.bitbucket_route.is_empty() => Ok(Box::new(bitbucket_client(args)?)),
+        ScmProviderKind::Bitbucket => {
+            let mut scm_router = ScmRouter::new(Box::new(bitbucket_client(args)?));
+
+            for (project, bitbucket_url) in &args.bitbucket_route {
+                scm_router.route(project, Box::new(bitbucket_route_client(args, project, bitbucket_url)?));
+            }
+
+            Ok(Box::new(scm_router))
+        },
+        ScmProviderKind::Gerrit => {
+            let gerrit_url = args.gerrit_url.as_ref()
+                .with_context(|| "A Gerrit URL is required when using the Gerrit SCM provider")?;
+
+            Ok(Box::new(GerritClient::from_client(rest_client(gerrit_url, Backend::Gerrit)?)))
+        },
+        ScmProviderKind::AzureDevops => {
+            let azure_devops_url = args.azure_devops_url.as_ref()
+                .with_context(|| "An Azure DevOps organization URL is required when using the Azure DevOps SCM provider")?;
+
+            Ok(Box::new(AzureDevOpsClient::from_client(rest_client(azure_devops_url, Backend::AzureDevops)?)))
+        }
+    }
+}
+
+fn bitbucket_client(args: &Args) -> Result<BitbucketClient> {
+    let bitbucket_url = args.bitbucket_url.as_ref()
+        .with_context(|| "A Bitbucket URL is required when using the Bitbucket SCM provider")?;
+
+    Ok(BitbucketClient::from_client(rest_client(bitbucket_url, Backend::Bitbucket)?))
+}
+
+/// Returns the client of the Bitbucket instance a project is routed to by --bitbucket-route. It is authenticated
+/// with the --bitbucket-route-token of the project, or with the credentials of --bitbucket-url only when they are
+/// shared with --share-bitbucket-credentials, so that they aren't sent to another server by mistake.
+fn bitbucket_route_client(args: &Args, project: &str, bitbucket_url: &str) -> Result<BitbucketClient> {
+    let mut rest_client_builder = rest_client_builder(bitbucket_url, Backend::Bitbucket)?;
+    rest_client_builder.cache = HTTP_CACHE.get().cloned();
+
+    let route_token = args.bitbucket_route_token.iter()
+        .find(|(route_project, _)| route_project.eq_ignore_ascii_case(project))
+        .map(|(_, token)| token.clone());
+
+    rest_client_builder.credentials = match route_token {
+        Some(token) => Some(Credentials::Bearer(token)),
+        None if args.share_bitbucket_credentials => rest_client_builder.credentials,
+        None => None
+    };
+
+    Ok(BitbucketClient::from_client(rest_client_builder.build()?))
+}
+
+/// Returns the comment posted on the pull requests of a changelog, such as "Shipping to prod in version 42 on
+/// 2024-06-01, see the changelog at https://wiki.example.com/changelog."
+fn pull_request_comment(changelog: &Changelog, args: &Args) -> String {
+    let environment = changelog.metadata.configuration.spinnaker.as_ref()
+        .map(|spinnaker| format!(" to {}", spinnaker.env))
+        .unwrap_or_default();
+
+    let version = match (&changelog.metadata.pending_version, &changelog.metadata.configuration.commit_range) {
+        (Some(pending_version), _) => format!(" in version {}", pending_version.version),
+        (None, Some(commit_range)) => format!(" in commit {}", commit_range.start_commit.chars().take(11).collect::<String>()),
+        (None, None) => String::new()
+    };
+
+    let date = args.timezone.convert(&changelog.metadata.generated_at)
+        .format("%Y-%m-%d");
+
+    let changelog_link = args.changelog_url.as_ref()
+        .map(|changelog_url| format!(", see the changelog at {changelog_url}"))
+        .unwrap_or_default();
+
+    format!("Shipping{environment}{version} on {date}{changelog_link}.")
+}
+
+fn issue_trackers(args: &Args) -> Result<Vec<Box<dyn IssueTracker>>> {
+    let mut issue_trackers: Vec<Box<dyn IssueTracker>> = Vec::new();
+
+    if let (Some(trello_key), Some(trello_token)) = (&args.trello_key, &args.trello_token) {
+        issue_trackers.push(Box::new(TrelloClient::from_client(rest_client(TRELLO_API_URL, Backend::Trello)?, trello_key.expose_secret(), trello_token.expose_secret())));
+    }
+
+    if let Some(asana_token) = &args.asana_token {
+        issue_trackers.push(Box::new(AsanaClient::from_builder(rest_client_builder(ASANA_URL, Backend::Asana)?, asana_token.expose_secret())?));
+    }
+
+    Ok(issue_trackers)
+}
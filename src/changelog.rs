@@ -6,7 +6,7 @@
 //!
 //! # Example
 //!
-//! ```
+//! ```ignore
 //! use deployment_changelog::changelog::{Changelog, CommitSpecifier, GitCommitRange};
 //! use deployment_changelog::api::{bitbucket::BitbucketClient, jira::JiraClient};
 //!
@@ -19,7 +19,8 @@
 //!         project: String::from("my-project"),
 //!         repo: String::from("my-repo"),
 //!         start_commit: String::from("abcdef123456"),
-//!         end_commit: String::from("ghijkl789012")
+//!         end_commit: String::from("ghijkl789012"),
+//!         merge_base: false
 //!     };
 //!
 //!     let commit_specifier = CommitSpecifier::CommitRange(commit_range);
@@ -36,15 +37,51 @@
 //!
 //! We use the `GitCommitRange` to create a `CommitSpecifier` and pass it to `Changelog::new` to create
 //! a changelog. Finally, we print the changelog.
-use crate::api::{rest::Paginated, jira::{JiraIssue, JiraClient}, bitbucket::{BitbucketCommit, BitbucketPullRequest, BitbucketPullRequestIssue, BitbucketClient, BitbucketPaginated}};
-use crate::api::spinnaker::{SpinnakerClient, md_environment_states_query::{Variables, MdArtifactStatusInEnvironment, MdEnvironmentStatesQueryApplicationEnvironmentsStateArtifactsVersions}};
+use crate::api::{jira::{jql_string, JiraIssue, JiraProvider, JiraDevStatusCommit, JiraDevStatusPullRequest, JiraWorklog}, priority::{with_priority, RequestPriority}, rest::is_not_found, retry::RetriedRequest, secret::redact_url, suggest::did_you_mean, bitbucket::{BitbucketAuthor, BitbucketCommit, BitbucketPullRequest, BitbucketPullRequestIssue, BitbucketPullRequestSize}, scm::{extract_dependencies, extract_issue_keys, ScmProvider}, tracker::{IssueTracker, TrackedIssue}};
+use crate::progress::{self, ProgressPhase};
+#[cfg(feature = "spinnaker")]
+use crate::api::spinnaker::{SpinnakerClient, md_environment_states_query::{Variables, ResponseData, MdArtifactStatusInEnvironment, MdEnvironmentStatesQueryApplicationEnvironmentsStateArtifactsVersions}};
 
-use std::{fmt::Display, collections::{HashSet, HashMap}};
+use std::{fmt::Display, collections::{BTreeMap, BTreeSet, HashMap, HashSet}, sync::Arc};
+use chrono::{DateTime, Utc};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use regex::Regex;
 use serde::{Deserialize, Serialize};
-use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
+use anyhow::{bail, Context, Result};
+
+/// The maximum number of concurrent requests of each stage of changelog generation, so that ranges of thousands of
+/// commits don't send thousands of requests to the same server at once. Jira and the other issue trackers are
+/// usually more rate limited than the source control server.
+const PULL_REQUEST_CONCURRENCY: usize = 16;
+const PULL_REQUEST_ISSUE_CONCURRENCY: usize = 16;
+const ISSUE_CONCURRENCY: usize = 8;
+const TRACKED_ISSUE_CONCURRENCY: usize = 4;
+const SEGMENT_CONCURRENCY: usize = 4;
+const PULL_REQUEST_COMMENT_CONCURRENCY: usize = 4;
+const RELEASE_TRAIN_REPO_CONCURRENCY: usize = 4;
+const DEPENDENCY_CONCURRENCY: usize = 4;
+const PULL_REQUEST_SIZE_CONCURRENCY: usize = 8;
+const WORKLOG_CONCURRENCY: usize = 8;
+
+/// The project and name of a repository.
+type Repository = (String, String);
+
+/// The ID and entry ID of a pull request.
+type PullRequestReference = (u64, String);
+
+/// The version of the JSON representation of a `Changelog`, recorded in its metadata. It is bumped whenever a
+/// field is renamed or a required field is added, along with an upgrade in `Changelog::from_json_any_version`.
+///
+/// - `1`: The changelogs stored before the version was recorded, which may lack their metadata, provenance,
+///   statistics, tracked issues and commit dates, and name the source control server `bitbucketUrl`.
+/// - `2`: The changelogs whose provenance and dependencies refer to pull requests by their IDs, which pull requests
+///   of different repositories can share.
+/// - `3`: The current representation, referring to pull requests by their entry IDs.
+pub const CHANGELOG_SCHEMA_VERSION: u32 = 3;
 
 /// The `CommitSpecifier` enum is used to specify the range of commits for which the changelog
-/// should be generated. It has two variants: `Spinnaker` and `CommitRange`.
+/// should be generated. It has three variants: `Spinnaker`, `CommitRange` and `Jql`.
 ///
 /// - `Spinnaker`: This variant uses the `SpinnakerEnvironment` struct to determine the commit range.
 ///   It fetches the latest pending and current versions from a Spinnaker environment to compute the
@@ -53,9 +90,18 @@ use anyhow::{Context, Result};
 /// - `CommitRange`: This variant uses the `GitCommitRange` struct to directly specify the range of
 ///   commits for which the changelog should be generated.
 ///
+/// - `Jql`: This variant starts from the Jira issues matching a JQL query (e.g. `fixVersion = 2024.06`) and
+///   resolves the commits and pull requests linked to them through Jira's development panel.
+///
+/// - `TimeWindow`: This variant uses the `TimeWindow` struct to find the commits of a branch within a period of
+///   time, for periodic reporting independent of deployments.
+///
+/// - `ReleaseTrain`: This variant uses the `ReleaseTrain` struct to gather the pull requests of several
+///   repositories that reference the Jira issues of a fix version, for releases spanning several services.
+///
 /// # Example
 ///
-/// ```
+/// ```ignore
 /// use deployment_changelog::changelog::{CommitSpecifier, SpinnakerEnvironment, GitCommitRange};
 /// use deployment_changelog::api::{bitbucket::BitbucketClient, jira::JiraClient, spinnaker::SpinnakerClient};
 ///
@@ -64,16 +110,18 @@ use anyhow::{Context, Result};
 /// let spinnaker_env = SpinnakerEnvironment {
 ///     client: spinnaker_client,
 ///     app_name: String::from("my-app"),
-///     env: String::from("production")
+///     env: String::from("production"),
+///     baseline: None
 /// };
-/// let commit_specifier_spinnaker = CommitSpecifier::Spinnaker(spinnaker_env);
+/// let commit_specifier_spinnaker = CommitSpecifier::Spinnaker(Box::new(spinnaker_env));
 ///
 /// // Creating a CommitSpecifier using the CommitRange variant
 /// let commit_range = GitCommitRange {
 ///     project: String::from("my-project"),
 ///     repo: String::from("my-repo"),
 ///     start_commit: String::from("abcdef123456"),
-///     end_commit: String::from("ghijkl789012")
+///     end_commit: String::from("ghijkl789012"),
+///     merge_base: false
 /// };
 /// let commit_specifier_range = CommitSpecifier::CommitRange(commit_range);
 /// ```
@@ -83,8 +131,12 @@ use anyhow::{Context, Result};
 /// `GitCommitRange` struct and use them to create `CommitSpecifier` instances.
 #[derive(Debug)]
 pub enum CommitSpecifier {
-    Spinnaker(SpinnakerEnvironment),
-    CommitRange(GitCommitRange)
+    #[cfg(feature = "spinnaker")]
+    Spinnaker(Box<SpinnakerEnvironment>),
+    CommitRange(GitCommitRange),
+    Jql(String),
+    TimeWindow(TimeWindow),
+    ReleaseTrain(ReleaseTrain)
 }
 
 /// The `SpinnakerEnvironment` struct is used to represent a Spinnaker environment for which the
@@ -93,13 +145,15 @@ pub enum CommitSpecifier {
 /// - `client`: A `SpinnakerClient` instance used to interact with the Spinnaker API.
 /// - `app_name`: A `String` representing the name of the Spinnaker application.
 /// - `env`: A `String` representing the name of the Spinnaker environment (e.g., "production").
+/// - `baseline`: An optional `Baseline` to compare the pending version against when the environment
+///   has no current version yet, such as on its first deployment.
 ///
 /// When the `CommitSpecifier::Spinnaker` variant is used, the changelog is generated based on
 /// the latest pending and current versions of the specified Spinnaker environment.
 ///
 /// # Example
 ///
-/// ```
+/// ```ignore
 /// use deployment_changelog::changelog::{CommitSpecifier, SpinnakerEnvironment};
 /// use deployment_changelog::api::{bitbucket::BitbucketClient, jira::JiraClient, spinnaker::SpinnakerClient};
 ///
@@ -107,20 +161,452 @@ pub enum CommitSpecifier {
 /// let spinnaker_env = SpinnakerEnvironment {
 ///     client: spinnaker_client,
 ///     app_name: String::from("my-app"),
-///     env: String::from("production")
+///     env: String::from("production"),
+///     baseline: None
 /// };
-/// let commit_specifier = CommitSpecifier::Spinnaker(spinnaker_env);
+/// let commit_specifier = CommitSpecifier::Spinnaker(Box::new(spinnaker_env));
 /// ```
 ///
 /// In this example, we create a `SpinnakerClient` with the Spinnaker server URL, and then create
 /// a `SpinnakerEnvironment` instance with the client, application name, and environment name.
 /// Finally, we use the `SpinnakerEnvironment` to create a `CommitSpecifier` instance with the
 /// `Spinnaker` variant.
+#[cfg(feature = "spinnaker")]
 #[derive(Debug)]
 pub struct SpinnakerEnvironment {
     pub client: SpinnakerClient,
     pub app_name: String,
-    pub env: String
+    pub env: String,
+    pub baseline: Option<Baseline>
+}
+
+/// The `Baseline` enum is the revision a pending Spinnaker version is compared against when the environment
+/// has never been deployed to, and so has no current version.
+///
+/// - `Commit`: A commit ID.
+/// - `Tag`: A tag name, resolved to the commit it points to through the `ScmProvider`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum Baseline {
+    Commit(String),
+    Tag(String)
+}
+
+#[cfg(feature = "spinnaker")]
+type SpinnakerVersion = MdEnvironmentStatesQueryApplicationEnvironmentsStateArtifactsVersions;
+
+#[cfg(feature = "spinnaker")]
+impl SpinnakerEnvironment {
+    /// Fetches the artifact versions of the environment, grouped by their status. The raw environment state
+    /// is returned alongside them when the `SpinnakerClient` uses a custom query.
+    async fn get_versions(&self) -> Result<(HashMap<MdArtifactStatusInEnvironment, Vec<SpinnakerVersion>>, Option<serde_json::Value>)> {
+        let env_state_vars = Variables {
+            app_name: self.app_name.clone(),
+            environments: vec![self.env.clone()]
+        };
+
+        let raw_env_states = self.client.get_raw_environment_states(env_state_vars)
+            .await?;
+
+        let spinnaker_state = if self.client.has_custom_environment_states_query() {
+            raw_env_states.pointer("/application/environments/0/state").cloned()
+        } else {
+            None
+        };
+
+        let env_states: ResponseData = serde_json::from_value(raw_env_states)
+            .with_context(|| "Error deserializing the Spinnaker environment states")?;
+
+        let application = match env_states.application {
+            Some(application) => application,
+            None => {
+                let app_names: Vec<String> = self.client.list_applications()
+                    .await
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|application| application.name)
+                    .collect();
+
+                bail!(
+                    "Spinnaker application {} was not found{}",
+                    self.app_name,
+                    did_you_mean(&self.app_name, &app_names)
+                );
+            }
+        };
+
+        let environment = match application.environments.into_iter().next() {
+            Some(environment) => environment,
+            None => {
+                let env_names = self.client.list_environments(&self.app_name)
+                    .await
+                    .unwrap_or_default();
+
+                bail!(
+                    "Spinnaker application {} has no environment {}{}",
+                    self.app_name,
+                    self.env,
+                    did_you_mean(&self.env, &env_names)
+                );
+            }
+        };
+
+        let artifacts = environment.state
+            .artifacts
+            .with_context(|| format!("No artifacts found for environment {} in Spinnaker application {}", self.env, self.app_name))?;
+
+        let mut version_map = HashMap::<MdArtifactStatusInEnvironment, Vec<SpinnakerVersion>>::with_capacity(1);
+
+        artifacts.into_iter()
+            .for_each(|artifact| {
+                if let Some(versions) = artifact.versions {
+                    versions.into_iter()
+                        .for_each(|version| {
+                            if let Some(status) = &version.status {
+                                version_map.entry(status.clone())
+                                    .or_default()
+                                    .push(version);
+                            }
+                        });
+                }
+            });
+
+        Ok((version_map, spinnaker_state))
+    }
+
+    /// Returns the commit of the latest pending version of the environment, or `None` when no version is pending.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// // Suppose you created a SpinnakerEnvironment named 'spinnaker_env'
+    /// if let Some(commit) = spinnaker_env.pending_commit().await.unwrap() {
+    ///     println!("Commit {commit} is pending deployment to {}", spinnaker_env.env);
+    /// }
+    /// ```
+    pub async fn pending_commit(&self) -> Result<Option<String>> {
+        let (mut version_map, _) = self.get_versions()
+            .await?;
+
+        if !version_map.contains_key(&MdArtifactStatusInEnvironment::PENDING) {
+            return Ok(None);
+        }
+
+        Ok(Some(self.latest_deployed_commit(&mut version_map, MdArtifactStatusInEnvironment::PENDING)?.commit))
+    }
+
+    /// Returns the build numbers and the commits of the pending versions of the environment, from the oldest to
+    /// the newest, which can be used to segment its changelog with `Changelog::segment`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// // Suppose you created a SpinnakerEnvironment named 'spinnaker_env' and generated its Changelog named 'changelog'
+    /// let pending_versions = spinnaker_env.pending_versions().await.unwrap();
+    /// changelog.segment(&bitbucket_client, &pending_versions).await.unwrap();
+    /// ```
+    pub async fn pending_versions(&self) -> Result<Vec<(String, DeploymentDescriptor)>> {
+        let (mut version_map, _) = self.get_versions()
+            .await?;
+
+        let mut pending_versions = version_map.remove(&MdArtifactStatusInEnvironment::PENDING)
+            .unwrap_or_default();
+        pending_versions.sort_by_key(build_number_order);
+
+        pending_versions.into_iter()
+            .map(|version| {
+                let build_number = version.build_number.clone().unwrap_or_default();
+
+                self.version_descriptor(version, "pending")
+                    .map(|descriptor| (build_number, descriptor))
+            })
+            .collect()
+    }
+
+    /// Resolves the commit range deployed by the latest pending version of the environment, from the commit of its
+    /// current version, or of its baseline when it has no current version.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// // Suppose you created a SpinnakerEnvironment named 'spinnaker_env' and a BitbucketClient named 'bitbucket_client'
+    /// let spinnaker_range = spinnaker_env.commit_range(&bitbucket_client).await.unwrap();
+    /// println!("Deploying {}..{}", spinnaker_range.commit_range.end_commit, spinnaker_range.commit_range.start_commit);
+    /// ```
+    pub async fn commit_range(&self, scm_provider: &dyn ScmProvider) -> Result<SpinnakerRange> {
+        progress::start_phase(ProgressPhase::SpinnakerVersions, None);
+
+        let (mut version_map, spinnaker_state) = self.get_versions()
+            .await?;
+
+        let pending_version = self.latest_version(&mut version_map, MdArtifactStatusInEnvironment::PENDING)?;
+        let mut pending_version_status = PendingVersionStatus::from(&pending_version);
+        let pending = self.version_descriptor(pending_version, "latest pending")?;
+
+        let deployed = match &self.baseline {
+            Some(baseline) if !version_map.contains_key(&MdArtifactStatusInEnvironment::CURRENT) => {
+                log::info!(
+                    "No current version for environment {} in Spinnaker application {}, comparing against the baseline {:?}",
+                    self.env,
+                    self.app_name,
+                    baseline
+                );
+
+                let commit = match baseline {
+                    Baseline::Commit(commit) => commit.clone(),
+                    Baseline::Tag(tag) => scm_provider.resolve_tag(&pending.scm_coords.project, &pending.scm_coords.repo, tag)
+                        .await
+                        .with_context(|| format!("Error resolving the baseline tag {tag} in {}", pending.scm_coords))?
+                };
+
+                DeploymentDescriptor {
+                    scm_coords: pending.scm_coords.clone(),
+                    commit
+                }
+            },
+            _ => {
+                let current_version = self.latest_version(&mut version_map, MdArtifactStatusInEnvironment::CURRENT)?;
+                pending_version_status.current_version = current_version.build_number.clone();
+                pending_version_status.last_deployed_at = current_version.deployed_at;
+
+                self.version_descriptor(current_version, "latest current")?
+            }
+        };
+
+        let commit_range = GitCommitRange::between(&deployed, &pending)
+            .with_context(|| format!("Error resolving the commit range of environment {} in Spinnaker application {}", self.env, self.app_name))?;
+
+        Ok(SpinnakerRange {
+            commit_range,
+            pending_version: pending_version_status,
+            spinnaker_state
+        })
+    }
+
+    /// Returns the `SpinnakerConfiguration` recorded in the metadata of the changelogs of the environment.
+    fn configuration(&self) -> SpinnakerConfiguration {
+        SpinnakerConfiguration {
+            spinnaker_url: redact_url(self.client.base_url().as_str()),
+            app_name: self.app_name.clone(),
+            env: self.env.clone(),
+            baseline: self.baseline.clone()
+        }
+    }
+
+    /// Takes the latest version with the given status out of the version map, and returns the commit it was built from.
+    fn latest_deployed_commit(
+        &self,
+        version_map: &mut HashMap<MdArtifactStatusInEnvironment, Vec<SpinnakerVersion>>,
+        status: MdArtifactStatusInEnvironment
+    ) -> Result<DeploymentDescriptor> {
+        let latest_version = self.latest_version(version_map, status.clone())?;
+        self.version_descriptor(latest_version, &format!("latest {}", status_name(&status)))
+    }
+
+    /// Takes the latest version with the given status out of the version map.
+    fn latest_version(
+        &self,
+        version_map: &mut HashMap<MdArtifactStatusInEnvironment, Vec<SpinnakerVersion>>,
+        status: MdArtifactStatusInEnvironment
+    ) -> Result<SpinnakerVersion> {
+        version_map.remove(&status)
+            .and_then(|versions| versions.into_iter().max_by_key(build_number_order))
+            .with_context(|| format!(
+                "There are no {} versions for environment {} in Spinnaker application {}",
+                status_name(&status),
+                self.env,
+                self.app_name
+            ))
+    }
+
+    /// Returns the commit range deployed to the environment since the given time, from the version that was current
+    /// then to the current version, or `None` when the current version was deployed before then.
+    async fn deployments_since(&self, since: &DateTime<Utc>) -> Result<Option<EnvironmentDeployments>> {
+        let (mut version_map, _) = self.get_versions()
+            .await?;
+
+        if !version_map.contains_key(&MdArtifactStatusInEnvironment::CURRENT) {
+            return Ok(None);
+        }
+
+        let current_version = self.latest_version(&mut version_map, MdArtifactStatusInEnvironment::CURRENT)?;
+
+        let deployed_at = match current_version.deployed_at {
+            Some(deployed_at) if deployed_at >= *since => deployed_at,
+            _ => return Ok(None)
+        };
+
+        let mut previous_versions: Vec<SpinnakerVersion> = version_map.remove(&MdArtifactStatusInEnvironment::PREVIOUS)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|version| version.deployed_at.is_some())
+            .collect();
+        previous_versions.sort_by_key(|version| version.deployed_at);
+
+        let (deployed_before, mut deployed_since): (Vec<SpinnakerVersion>, Vec<SpinnakerVersion>) = previous_versions.into_iter()
+            .partition(|version| version.deployed_at < Some(*since));
+
+        // Without a version deployed before the period, the oldest version Spinnaker still knows of is the baseline,
+        // so its own changes are left out.
+        let previous_version = match deployed_before.into_iter().last() {
+            Some(previous_version) => previous_version,
+            None if !deployed_since.is_empty() => {
+                log::warn!(
+                    "No version of Spinnaker application {} was deployed to environment {} before {since}, leaving out the changes of the oldest version deployed since",
+                    self.app_name,
+                    self.env
+                );
+
+                deployed_since.remove(0)
+            },
+            None => bail!(
+                "There are no previous versions for environment {} in Spinnaker application {} to compare the current version against",
+                self.env,
+                self.app_name
+            )
+        };
+
+        let versions = deployed_since.iter()
+            .chain([&current_version])
+            .filter_map(|version| version.build_number.clone())
+            .collect();
+
+        let previous = self.version_descriptor(previous_version, "previous")?;
+        let current = self.version_descriptor(current_version, "current")?;
+
+        let commit_range = GitCommitRange::between(&previous, &current)
+            .with_context(|| format!("Error resolving the commit range deployed to environment {} in Spinnaker application {}", self.env, self.app_name))?;
+
+        Ok(Some(EnvironmentDeployments {
+            commit_range,
+            versions,
+            deployed_at
+        }))
+    }
+
+    /// Returns the repository and the commit a version was built from, from its Git metadata.
+    fn version_descriptor(&self, version: SpinnakerVersion, status_name: &str) -> Result<DeploymentDescriptor> {
+        let git_metadata = version.git_metadata
+            .with_context(|| format!(
+                "Error getting Git metadata for the {status_name} version for Spinnaker application {}, environment {}",
+                self.app_name,
+                self.env)
+            )?;
+
+        let project = git_metadata.project
+            .with_context(|| format!(
+                "Error getting the Git project for the {status_name} version for Spinnaker application {}, environment {}",
+                self.app_name,
+                self.env)
+            )?;
+
+        let repo = git_metadata.repo_name
+            .with_context(|| format!(
+                "Error getting the Git repository name for the {status_name} version for Spinnaker application {}, environment {}",
+                self.app_name,
+                self.env)
+            )?;
+
+        let commit = git_metadata.commit
+            .with_context(|| format!(
+                "Error getting the Git commit for the {status_name} version for Spinnaker application {}, environment {}",
+                self.app_name,
+                self.env)
+            )?;
+
+        Ok(DeploymentDescriptor {
+            scm_coords: ScmCoordinates { project, repo },
+            commit
+        })
+    }
+}
+
+/// Orders versions by their build numbers, numerically so that build 10 comes after build 9. Build numbers that
+/// aren't numbers, if any, come after the numeric ones in string order.
+#[cfg(feature = "spinnaker")]
+fn build_number_order(version: &SpinnakerVersion) -> (bool, u64, Option<String>) {
+    match version.build_number.as_deref().map(str::parse::<u64>) {
+        Some(Ok(build_number)) => (false, build_number, version.build_number.clone()),
+        _ => (true, 0, version.build_number.clone())
+    }
+}
+
+/// Returns the name of a version status in error messages.
+#[cfg(feature = "spinnaker")]
+fn status_name(status: &MdArtifactStatusInEnvironment) -> &'static str {
+    match status {
+        MdArtifactStatusInEnvironment::PENDING => "pending",
+        MdArtifactStatusInEnvironment::CURRENT => "current",
+        MdArtifactStatusInEnvironment::PREVIOUS => "previous",
+        _ => "matching"
+    }
+}
+
+/// The `SpinnakerRange` struct is the commit range deployed by the latest pending version of a Spinnaker
+/// environment. It contains the following fields:
+///
+/// - `commit_range`: The `GitCommitRange` from the current version, or the baseline, to the pending version.
+/// - `pending_version`: The `PendingVersionStatus` of the pending version.
+/// - `spinnaker_state`: The raw state of the environment, only set when the `SpinnakerClient` uses a custom query.
+#[cfg(feature = "spinnaker")]
+#[derive(Debug, Clone)]
+pub struct SpinnakerRange {
+    pub commit_range: GitCommitRange,
+    pub pending_version: PendingVersionStatus,
+    pub spinnaker_state: Option<serde_json::Value>
+}
+
+/// The `EnvironmentDeployments` struct is the commit range deployed to a Spinnaker environment within a period of
+/// time, with the build numbers of the versions deployed, from the oldest to the newest, and when the last one was
+/// deployed.
+#[cfg(feature = "spinnaker")]
+#[derive(Debug, Clone)]
+struct EnvironmentDeployments {
+    commit_range: GitCommitRange,
+    versions: Vec<String>,
+    deployed_at: DateTime<Utc>
+}
+
+#[cfg(feature = "spinnaker")]
+impl From<&SpinnakerVersion> for PendingVersionStatus {
+    fn from(version: &SpinnakerVersion) -> Self {
+        Self {
+            version: version.build_number.clone().unwrap_or_default(),
+            pending_since: version.created_at,
+            current_version: None,
+            last_deployed_at: None,
+            constraints: version.constraints.iter()
+                .flatten()
+                .map(|constraint| ConstraintState {
+                    constraint_type: constraint.type_.clone(),
+                    status: graphql_enum_name(&constraint.status),
+                    started_at: constraint.started_at,
+                    judged_at: constraint.judged_at,
+                    judged_by: constraint.judged_by.clone(),
+                    comment: constraint.comment.clone()
+                })
+                .collect(),
+            verifications: version.verifications.iter()
+                .flatten()
+                .map(|verification| VerificationState {
+                    id: verification.action_id.clone(),
+                    status: graphql_enum_name(&verification.status),
+                    started_at: verification.started_at,
+                    completed_at: verification.completed_at,
+                    link: verification.link.clone()
+                })
+                .collect()
+        }
+    }
+}
+
+/// Returns the name of a value of a GraphQL enum, such as `PENDING`.
+#[cfg(feature = "spinnaker")]
+fn graphql_enum_name<T: Serialize + std::fmt::Debug>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(name)) => name,
+        _ => format!("{value:?}")
+    }
 }
 
 /// The `GitCommitRange` struct is used to represent a range of commits for which the
@@ -130,6 +616,14 @@ pub struct SpinnakerEnvironment {
 /// - `repo`: A `String` representing the name of the Git repository.
 /// - `start_commit`: A `String` representing the starting commit in the range.
 /// - `end_commit`: A `String` representing the ending commit in the range.
+/// - `merge_base`: Whether to compare the starting commit against its merge base with the ending commit rather
+///   than against the ending commit itself.
+///
+/// The commits can also be given as branches, tags or abbreviated commit IDs, which are resolved to the full
+/// IDs of their commits before the range is compared.
+///
+/// Comparing two diverged release branches against their merge base yields only the changes unique to the
+/// starting branch. The merge base replaces the ending commit in the range recorded in the changelog's metadata.
 ///
 /// When the `CommitSpecifier::CommitRange` variant is used, the changelog is generated based on
 /// the specified range of commits directly.
@@ -144,7 +638,8 @@ pub struct SpinnakerEnvironment {
 ///     project: String::from("my-project"),
 ///     repo: String::from("my-repo"),
 ///     start_commit: String::from("abcdef123456"),
-///     end_commit: String::from("ghijkl789012")
+///     end_commit: String::from("ghijkl789012"),
+///     merge_base: false
 /// };
 /// let commit_specifier = CommitSpecifier::CommitRange(commit_range);
 /// ```
@@ -152,12 +647,222 @@ pub struct SpinnakerEnvironment {
 /// In this example, we create a `GitCommitRange` instance with the project name, repository name,
 /// and starting and ending commit hashes. Then, we use the `GitCommitRange` to create a
 /// `CommitSpecifier` instance with the `CommitRange` variant.
-#[derive(Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
 pub struct GitCommitRange {
     pub project: String,
     pub repo: String,
     pub start_commit: String,
-    pub end_commit: String
+    pub end_commit: String,
+
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub merge_base: bool
+}
+
+impl GitCommitRange {
+    /// Creates the range of commits deployed when going from the `deployed` version of a repository to the
+    /// `deploying` one.
+    ///
+    /// # Arguments
+    ///
+    /// * `deployed` - The version that is currently deployed, which ends the range.
+    /// * `deploying` - The version being deployed, which starts the range.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `GitCommitRange`, or an error if the two versions were built from different
+    /// repositories.
+    pub fn between(deployed: &DeploymentDescriptor, deploying: &DeploymentDescriptor) -> Result<Self> {
+        if deployed.scm_coords != deploying.scm_coords {
+            bail!(
+                "Commit {} is from {} but commit {} is from {}",
+                deployed.commit,
+                deployed.scm_coords,
+                deploying.commit,
+                deploying.scm_coords
+            );
+        }
+
+        Ok(Self {
+            project: deploying.scm_coords.project.clone(),
+            repo: deploying.scm_coords.repo.clone(),
+            start_commit: deploying.commit.clone(),
+            end_commit: deployed.commit.clone(),
+            merge_base: false
+        })
+    }
+}
+
+/// The `TimeWindow` struct describes the commits of a branch within a period of time, for reports such as the
+/// changes of the last two weeks. It contains the following fields:
+///
+/// - `project`: The name of the project, or the owner, of the repository.
+/// - `repo`: The name of the repository.
+/// - `branch`: The branch to report on, or `None` for the default branch of the repository.
+/// - `since`: The start of the period, inclusive.
+/// - `until`: The end of the period, exclusive.
+///
+/// The commits are found by their author dates, walking the branch from its tip, so the period becomes the range
+/// from the last commit before `until` to the last commit before `since`.
+///
+/// # Example
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use deployment_changelog::changelog::{CommitSpecifier, TimeWindow};
+///
+/// let time_window = TimeWindow {
+///     project: String::from("my-project"),
+///     repo: String::from("my-repo"),
+///     branch: Some(String::from("main")),
+///     since: Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap(),
+///     until: Utc.with_ymd_and_hms(2024, 5, 15, 0, 0, 0).unwrap()
+/// };
+/// let commit_specifier = CommitSpecifier::TimeWindow(time_window);
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeWindow {
+    pub project: String,
+    pub repo: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+
+    pub since: DateTime<Utc>,
+    pub until: DateTime<Utc>
+}
+
+impl TimeWindow {
+    /// Finds the range of commits of the branch within the period.
+    ///
+    /// # Arguments
+    ///
+    /// * `scm_provider` - The source control provider hosting the repository.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `GitCommitRange` of the period, or an error if the period is empty or starts
+    /// before the first commit of the branch.
+    pub async fn commit_range(&self, scm_provider: &dyn ScmProvider) -> Result<GitCommitRange> {
+        if self.since >= self.until {
+            bail!("The start of the period ({}) must be before its end ({})", self.since, self.until);
+        }
+
+        let branch = self.branch.as_deref();
+        let branch_name = branch.unwrap_or("the default branch");
+
+        let (start_commit, end_commit) = futures::future::try_join(
+            scm_provider.last_commit_before(&self.project, &self.repo, branch, &self.until),
+            scm_provider.last_commit_before(&self.project, &self.repo, branch, &self.since)
+        )
+            .await
+            .with_context(|| format!("Error finding the commits of {branch_name} in {}/{} by date", self.project, self.repo))?;
+
+        let (start_commit, end_commit) = match (start_commit, end_commit) {
+            (Some(start_commit), Some(end_commit)) => (start_commit, end_commit),
+            (None, _) => bail!("{}/{} has no commit on {branch_name} before {}", self.project, self.repo, self.until),
+            (Some(_), None) => bail!(
+                "{}/{} has no commit on {branch_name} before {}, so the period starts before the branch; use a commit range from its first commit instead",
+                self.project,
+                self.repo,
+                self.since
+            )
+        };
+
+        Ok(GitCommitRange {
+            project: self.project.clone(),
+            repo: self.repo.clone(),
+            start_commit,
+            end_commit,
+            merge_base: false
+        })
+    }
+}
+
+/// The `ReleaseTrain` struct describes a release shipping the Jira issues of a fix version across several
+/// repositories. It contains the following fields:
+///
+/// - `fix_version`: The Jira fix version of the release, such as `2024.06`.
+/// - `repos`: The `ScmCoordinates` of the repositories taking part in the release.
+///
+/// The pull requests of the repositories are scanned for the keys of the fix version's issues in their titles,
+/// descriptions and source branches. Only the pull requests updated since the oldest of the issues was created are
+/// scanned, as a pull request can't reference an issue before it exists.
+///
+/// # Example
+///
+/// ```
+/// use deployment_changelog::changelog::{CommitSpecifier, ReleaseTrain, ScmCoordinates};
+///
+/// let release_train = ReleaseTrain {
+///     fix_version: String::from("2024.06"),
+///     repos: vec![
+///         ScmCoordinates { project: String::from("PAY"), repo: String::from("payments-service") },
+///         ScmCoordinates { project: String::from("PAY"), repo: String::from("payments-web") }
+///     ]
+/// };
+/// let commit_specifier = CommitSpecifier::ReleaseTrain(release_train);
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ReleaseTrain {
+    pub fix_version: String,
+    pub repos: Vec<ScmCoordinates>
+}
+
+impl ReleaseTrain {
+    /// Returns the JQL query matching the Jira issues of the fix version.
+    pub fn jql(&self) -> String {
+        format!("fixVersion = {} ORDER BY key", jql_string(&self.fix_version))
+    }
+}
+
+/// The `ScmCoordinates` struct locates a repository on the source control system. It contains the following
+/// fields:
+///
+/// - `project`: The name of the project, or the owner, of the repository.
+/// - `repo`: The name of the repository.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct ScmCoordinates {
+    pub project: String,
+    pub repo: String
+}
+
+impl Display for ScmCoordinates {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.project, self.repo)
+    }
+}
+
+/// The `DeploymentDescriptor` struct describes a version of a repository as deployed by a deployment system.
+/// It contains the following fields:
+///
+/// - `scm_coords`: The repository the version was built from.
+/// - `commit`: The commit the version was built from.
+///
+/// A deployment system only has to describe the deployed and the deploying versions for `Changelog::between`
+/// to generate the changelog of the deployment, as the Spinnaker integration does.
+///
+/// # Example
+///
+/// ```rust
+/// use deployment_changelog::changelog::{DeploymentDescriptor, ScmCoordinates};
+///
+/// let deployed = DeploymentDescriptor {
+///     scm_coords: ScmCoordinates {
+///         project: String::from("my-project"),
+///         repo: String::from("my-repo")
+///     },
+///     commit: String::from("ghijkl789012")
+/// };
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DeploymentDescriptor {
+    pub scm_coords: ScmCoordinates,
+    pub commit: String
 }
 
 /// The `Changelog` struct represents a changelog containing information about commits,
@@ -166,13 +871,23 @@ pub struct GitCommitRange {
 /// - `commits`: A `Vec<BitbucketCommit>` containing the list of Bitbucket commits.
 /// - `pull_requests`: A `Vec<BitbucketPullRequest>` containing the list of Bitbucket pull requests.
 /// - `issues`: A `Vec<JiraIssue>` containing the list of Jira issues.
+/// - `tracked_issues`: A `Vec<TrackedIssue>` containing the work items from other trackers, such as Trello
+///   or Asana, referenced in the pull request descriptions. It is only populated by `Changelog::link_tracked_issues`.
+/// - `provenance`: A `ChangelogProvenance` cross-referencing the commits, pull requests and issues.
+/// - `stats`: A `ChangelogStats` summarizing the changelog.
+/// - `notes`: Free-form deployment notes, such as "DB migration required", added by whoever generated the changelog.
+/// - `spinnaker_state`: The raw state of the Spinnaker environment, only set when the `SpinnakerClient` fetches
+///   it with a custom query, to carry the extra fields that query selects.
+/// - `segments`: The `ChangelogSegment`s of the versions the changelog deploys, from the oldest to the newest. It
+///   is only populated by `Changelog::segment`.
+/// - `metadata`: A `ChangelogMetadata` describing how and by which tool version the changelog was generated.
 ///
 /// The `Changelog` struct provides methods to generate a changelog from a Spinnaker environment
 /// or a Git commit range. It also implements the `Display` trait to provide a formatted output.
 ///
 /// # Example
 ///
-/// ```
+/// ```ignore
 /// use deployment_changelog::changelog::{Changelog, CommitSpecifier, GitCommitRange};
 /// use deployment_changelog::api::{bitbucket::BitbucketClient, jira::JiraClient};
 ///
@@ -183,7 +898,8 @@ pub struct GitCommitRange {
 ///     project: String::from("my-project"),
 ///     repo: String::from("my-repo"),
 ///     start_commit: String::from("abcdef123456"),
-///     end_commit: String::from("ghijkl789012")
+///     end_commit: String::from("ghijkl789012"),
+///     merge_base: false
 /// };
 ///
 /// let commit_specifier = CommitSpecifier::CommitRange(commit_range);
@@ -196,12 +912,38 @@ pub struct GitCommitRange {
 /// We also create a `GitCommitRange` instance and use it to create a `CommitSpecifier` with the
 /// `CommitRange` variant. Then, we generate a `Changelog` using the `Changelog::new` method and
 /// print the formatted output.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Changelog {
     pub commits: Vec<BitbucketCommit>,
     pub pull_requests: Vec<BitbucketPullRequest>,
-    pub issues: Vec<JiraIssue>
+    pub issues: Vec<JiraIssue>,
+    pub tracked_issues: Vec<TrackedIssue>,
+    pub provenance: ChangelogProvenance,
+    pub stats: ChangelogStats,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spinnaker_state: Option<serde_json::Value>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub migrations: Vec<String>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub feature_flags: Vec<FeatureFlagChange>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub feature_flag_files: Vec<String>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub segments: Vec<ChangelogSegment>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<PullRequestDependency>,
+
+    pub metadata: ChangelogMetadata
 }
 
 impl Display for Changelog {
@@ -213,27 +955,567 @@ impl Display for Changelog {
     }
 }
 
-impl Changelog {
-    /// This method creates a new `Changelog` instance using the provided `BitbucketClient`, `JiraClient`,
-    /// and `CommitSpecifier`. The changelog is generated based on the commit specifier. It can either
-    /// generate a changelog from a Spinnaker environment or a Git commit range.
-    ///
-    /// ### Example
-    ///
-    /// ```rust
-    /// use deployment_changelog::changelog::{Changelog, CommitSpecifier, GitCommitRange};
-    /// use deployment_changelog::api::{bitbucket::BitbucketClient, jira::JiraClient};
-    ///
-    /// // Create a BitbucketClient and JiraClient with their respective server URLs.
-    /// let bitbucket_client = BitbucketClient::new("https://your-bitbucket-url");
-    /// let jira_client = JiraClient::new("https://your-jira-url");
-    ///
-    /// // Define the Git commit range for the changelog.
-    /// let commit_range = GitCommitRange {
-    ///     project: String::from("my-project"),
+/// The `FeatureFlagChange` struct records a feature flag referenced by lines added or removed in the commit
+/// range of a `Changelog`. It contains the following fields:
+///
+/// - `key`: The key of the feature flag.
+/// - `paths`: The paths of the files where the changed references to the flag are.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FeatureFlagChange {
+    pub key: String,
+    pub paths: Vec<String>
+}
+
+impl Display for FeatureFlagChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match serde_json::to_string_pretty(&self) {
+            Ok(json) => write!(f, "{json}"),
+            Err(error) => panic!("Error serializing feature flag change: {error}")
+        }
+    }
+}
+
+/// The `PullRequestDependency` struct records a pull request or issue that one of the pull requests of a
+/// `Changelog` declares it depends on, such as "Depends on PR #45" in its description. Deploying a pull request
+/// without the changes it depends on ships a partial feature. It contains the following fields:
+///
+/// - `pull_request_id`: The ID of the pull request of the changelog declaring the dependency.
+/// - `pull_request_entry_id`: The `entry_id` of that pull request, which tells it apart from the pull requests of
+///   other repositories with the same ID.
+/// - `reference`: The pull request depended on, such as `#45`, or the key of the issue depended on.
+/// - `status`: The state of the pull request, such as `OPEN` or `MERGED`, `INCLUDED` if the changelog contains
+///   it, the status of the issue, such as `In Review`, or `NOT FOUND` and `UNKNOWN` if it couldn't be found.
+/// - `resolved`: Whether the pull request is included in the changelog or merged, or the issue is done.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PullRequestDependency {
+    pub pull_request_id: u64,
+    pub pull_request_entry_id: String,
+    pub reference: String,
+    pub status: String,
+    pub resolved: bool
+}
+
+impl Display for PullRequestDependency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match serde_json::to_string_pretty(&self) {
+            Ok(json) => write!(f, "{json}"),
+            Err(error) => panic!("Error serializing pull request dependency: {error}")
+        }
+    }
+}
+
+/// The `ChangelogSegment` struct records the part of a `Changelog` contributed by one of the versions it deploys,
+/// such as one of several builds pending deployment to a Spinnaker environment. It contains the following fields:
+///
+/// - `version`: The name of the version, such as its build number.
+/// - `commit`: The commit the version was built from.
+/// - `commits`: The IDs of the commits of the changelog that the version adds to the previous one.
+/// - `pull_requests`: The IDs of the pull requests of these commits.
+/// - `issues`: The keys of the Jira issues of these pull requests.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangelogSegment {
+    pub version: String,
+    pub commit: String,
+    pub commits: Vec<String>,
+    pub pull_requests: Vec<u64>,
+    pub issues: Vec<String>
+}
+
+impl Display for ChangelogSegment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match serde_json::to_string_pretty(&self) {
+            Ok(json) => write!(f, "{json}"),
+            Err(error) => panic!("Error serializing changelog segment: {error}")
+        }
+    }
+}
+
+/// The `ChangelogProvenance` struct records how the entries of a `Changelog` relate to each other,
+/// so consumers can reconstruct the commit → pull request → issue graph without re-querying the APIs.
+/// Pull requests are referred to by their `entry_id`, since pull requests of different repositories can share
+/// a number. It contains the following fields:
+///
+/// - `pull_request_commits`: Maps each pull request entry ID to the IDs of the commits in the range that belong to it.
+/// - `issue_pull_requests`: Maps each issue key to the entry IDs of the pull requests that referenced it.
+/// - `tracked_issue_pull_requests`: Maps each tracked issue URL to the entry IDs of the pull requests that referenced it.
+///
+/// # Example
+///
+/// ```ignore
+/// use deployment_changelog::changelog::Changelog;
+///
+/// // Suppose you generated a Changelog named 'changelog'
+/// for issue in &changelog.issues {
+///     let pull_request_entry_ids = &changelog.provenance.issue_pull_requests[&issue.key];
+///
+///     for pull_request in changelog.pull_requests.iter().filter(|pull_request| pull_request.entry_id.as_ref().is_some_and(|entry_id| pull_request_entry_ids.contains(entry_id))) {
+///         println!("{} <- PR #{}: {:?}", issue.key, pull_request.id, changelog.provenance.pull_request_commits.get(pull_request.entry_id.as_ref().unwrap()));
+///     }
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangelogProvenance {
+    pub pull_request_commits: BTreeMap<String, Vec<String>>,
+    pub issue_pull_requests: BTreeMap<String, Vec<String>>,
+    pub tracked_issue_pull_requests: BTreeMap<String, Vec<String>>
+}
+
+impl Display for ChangelogProvenance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match serde_json::to_string_pretty(&self) {
+            Ok(json) => write!(f, "{json}"),
+            Err(error) => panic!("Error serializing changelog provenance: {error}")
+        }
+    }
+}
+
+/// The `ChangelogStats` struct summarizes the contents of a `Changelog`. It contains the following fields:
+///
+/// - `commit_count`: The number of commits in the changelog.
+/// - `pull_request_count`: The number of pull requests in the changelog.
+/// - `issue_count`: The number of Jira issues in the changelog.
+/// - `unique_authors`: The display names of the commit authors, sorted and without duplicates.
+/// - `oldest_commit_date`: The author date of the oldest commit, if there are any commits.
+/// - `newest_commit_date`: The author date of the newest commit, if there are any commits.
+/// - `lead_time_seconds`: The time between the oldest commit and the deployment, in seconds, if there are any commits.
+/// - `pull_request_commits` and `pull_request_files`: The distributions of the numbers of commits and changed files
+///   of the pull requests, once their sizes were found with `Changelog::compute_pull_request_sizes()`.
+/// - `review_latency_seconds`: The distribution of the time between the creation and the merge of the merged pull
+///   requests, in seconds, when the source control server reports when they were merged.
+/// - `time_spent_seconds`: The total time logged on the issues, in seconds, once their worklogs were summed with
+///   `Changelog::aggregate_worklogs()`.
+///
+/// The deployment is considered to happen when the changelog is generated.
+///
+/// # Example
+///
+/// ```ignore
+/// use deployment_changelog::changelog::Changelog;
+///
+/// // Suppose you generated a Changelog named 'changelog'
+/// println!(
+///     "{} commits by {} authors, lead time {:?}s",
+///     changelog.stats.commit_count,
+///     changelog.stats.unique_authors.len(),
+///     changelog.stats.lead_time_seconds
+/// );
+/// ```
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangelogStats {
+    pub commit_count: usize,
+    pub pull_request_count: usize,
+    pub issue_count: usize,
+    pub unique_authors: Vec<String>,
+    pub oldest_commit_date: Option<DateTime<Utc>>,
+    pub newest_commit_date: Option<DateTime<Utc>>,
+    pub lead_time_seconds: Option<i64>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pull_request_commits: Option<Percentiles>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pull_request_files: Option<Percentiles>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub review_latency_seconds: Option<Percentiles>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_spent_seconds: Option<i64>
+}
+
+impl ChangelogStats {
+    /// Computes the statistics of the given commits, pull requests and issues, measuring the lead time
+    /// up to `deployed_at`.
+    pub fn new(
+        commits: &[BitbucketCommit],
+        pull_requests: &[BitbucketPullRequest],
+        issues: &[JiraIssue],
+        deployed_at: &DateTime<Utc>
+    ) -> Self {
+        let unique_authors: Vec<String> = commits.iter()
+            .map(|commit| commit.author.display_name.clone())
+            .collect::<BTreeSet<String>>()
+            .into_iter()
+            .collect();
+
+        let oldest_commit_date = commits.iter()
+            .map(|commit| commit.author_timestamp)
+            .min();
+
+        let newest_commit_date = commits.iter()
+            .map(|commit| commit.author_timestamp)
+            .max();
+
+        Self {
+            commit_count: commits.len(),
+            pull_request_count: pull_requests.len(),
+            issue_count: issues.len(),
+            unique_authors,
+            oldest_commit_date,
+            newest_commit_date,
+            lead_time_seconds: oldest_commit_date.map(|oldest| (*deployed_at - oldest).num_seconds()),
+            pull_request_commits: Percentiles::new(pull_requests.iter()
+                .filter_map(|pull_request| pull_request.size.as_ref())
+                .map(|size| size.commit_count as i64)),
+            pull_request_files: Percentiles::new(pull_requests.iter()
+                .filter_map(|pull_request| pull_request.size.as_ref()?.file_count)
+                .map(|file_count| file_count as i64)),
+            review_latency_seconds: Percentiles::new(pull_requests.iter()
+                .filter_map(BitbucketPullRequest::review_latency_seconds)),
+            time_spent_seconds: issues.iter()
+                .filter_map(|issue| issue.time_spent_seconds)
+                .reduce(|total, time_spent_seconds| total + time_spent_seconds)
+        }
+    }
+}
+
+impl Display for ChangelogStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match serde_json::to_string_pretty(&self) {
+            Ok(json) => write!(f, "{json}"),
+            Err(error) => panic!("Error serializing changelog stats: {error}")
+        }
+    }
+}
+
+/// The `Percentiles` struct summarizes the distribution of a statistic of the pull requests of a `Changelog`, such
+/// as their review latency, with nearest-rank percentiles. It contains the following fields:
+///
+/// - `count`: The number of pull requests the statistic is known for.
+/// - `p50`, `p75`, `p90` and `p95`: The values that 50%, 75%, 90% and 95% of the pull requests are at or below.
+/// - `max`: The largest value.
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Percentiles {
+    pub count: usize,
+    pub p50: i64,
+    pub p75: i64,
+    pub p90: i64,
+    pub p95: i64,
+    pub max: i64
+}
+
+impl Percentiles {
+    /// Computes the percentiles of the given values, or `None` if there are none.
+    pub fn new(values: impl IntoIterator<Item = i64>) -> Option<Self> {
+        let mut values: Vec<i64> = values.into_iter().collect();
+        values.sort_unstable();
+
+        let max = *values.last()?;
+        let percentile = |percent: usize| values[(values.len() * percent).div_ceil(100) - 1];
+
+        Some(Self {
+            count: values.len(),
+            p50: percentile(50),
+            p75: percentile(75),
+            p90: percentile(90),
+            p95: percentile(95),
+            max
+        })
+    }
+}
+
+/// The `ChangelogMetadata` struct records how a `Changelog` was produced, so that a stored changelog
+/// can be traced back to the tool version and settings that generated it. It contains the following fields:
+///
+/// - `schema_version`: The `CHANGELOG_SCHEMA_VERSION` of the JSON representation of the changelog.
+/// - `tool_version`: The version of `deployment-changelog` that generated the changelog.
+/// - `generated_at`: When the changelog was generated.
+/// - `generated_on`: The host name of the machine that generated the changelog, if it could be determined.
+/// - `configuration`: The effective `ChangelogConfiguration` used to generate the changelog.
+/// - `retried_requests`: The requests that were retried or failed while generating the changelog, when the
+///   clients shared a `RetryBudget`.
+/// - `pending_version`: The `PendingVersionStatus` of the Spinnaker version the changelog deploys, if it was
+///   resolved from a Spinnaker environment.
+///
+/// # Example
+///
+/// ```ignore
+/// use deployment_changelog::changelog::Changelog;
+///
+/// // Suppose you loaded a stored Changelog named 'changelog'
+/// println!(
+///     "Generated by deployment-changelog {} on {:?} at {}",
+///     changelog.metadata.tool_version,
+///     changelog.metadata.generated_on,
+///     changelog.metadata.generated_at
+/// );
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangelogMetadata {
+    #[serde(default)]
+    pub schema_version: u32,
+
+    pub tool_version: String,
+    pub generated_at: DateTime<Utc>,
+    pub generated_on: Option<String>,
+    pub configuration: ChangelogConfiguration,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub retried_requests: Vec<RetriedRequest>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pending_version: Option<PendingVersionStatus>
+}
+
+impl ChangelogMetadata {
+    /// Creates a new `ChangelogMetadata` for the given configuration, stamped with the current
+    /// tool version, time and host name.
+    pub fn new(configuration: ChangelogConfiguration) -> Self {
+        Self {
+            schema_version: CHANGELOG_SCHEMA_VERSION,
+            tool_version: env!("CARGO_PKG_VERSION").to_string(),
+            generated_at: Utc::now(),
+            generated_on: gethostname::gethostname().into_string().ok(),
+            configuration,
+            retried_requests: Vec::new(),
+            pending_version: None
+        }
+    }
+}
+
+impl Display for ChangelogMetadata {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match serde_json::to_string_pretty(&self) {
+            Ok(json) => write!(f, "{json}"),
+            Err(error) => panic!("Error serializing changelog metadata: {error}")
+        }
+    }
+}
+
+/// The `ChangelogConfiguration` struct captures the effective settings a `Changelog` was generated with.
+/// Server URLs are stored without any embedded credentials. It contains the following fields:
+///
+/// - `scm_url`: The base URL of the source control server.
+/// - `jira_url`: The base URL of the Jira server.
+/// - `commit_range`: The `GitCommitRange` the changelog was generated for, unless it was generated from a JQL query.
+/// - `spinnaker`: The `SpinnakerConfiguration` the commit range was resolved from, if any.
+/// - `jql`: The JQL query the changelog was generated from, if any.
+/// - `time_window`: The `TimeWindow` the commit range was resolved from, if any.
+/// - `release_train`: The `ReleaseTrain` the changelog was gathered for, if any.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangelogConfiguration {
+    pub scm_url: String,
+    pub jira_url: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit_range: Option<GitCommitRange>,
+
+    pub spinnaker: Option<SpinnakerConfiguration>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jql: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_window: Option<TimeWindow>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub release_train: Option<ReleaseTrain>
+}
+
+/// The `SpinnakerConfiguration` struct captures the Spinnaker settings a commit range was resolved from.
+/// It contains the following fields:
+///
+/// - `spinnaker_url`: The base URL of the Spinnaker API server.
+/// - `app_name`: The name of the Spinnaker application.
+/// - `env`: The name of the Spinnaker environment.
+/// - `baseline`: The `Baseline` the pending version was compared against, if the environment had no current version.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SpinnakerConfiguration {
+    pub spinnaker_url: String,
+    pub app_name: String,
+    pub env: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub baseline: Option<Baseline>
+}
+
+/// The `PendingVersionStatus` struct records the state of the gates of the latest version pending deployment to a
+/// Spinnaker environment, which tell why its deployment hasn't started yet. It contains the following fields:
+///
+/// - `version`: The build number of the pending version.
+/// - `pending_since`: When the pending version was created, if Spinnaker knows it.
+/// - `current_version`: The build number of the current version it replaces, if the environment has one.
+/// - `last_deployed_at`: When the current version was deployed, if Spinnaker knows it.
+/// - `constraints`: The `ConstraintState` of each constraint of the environment, such as a manual judgment or
+///   allowed deployment times.
+/// - `verifications`: The `VerificationState` of each verification of the version.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingVersionStatus {
+    pub version: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pending_since: Option<DateTime<Utc>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current_version: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_deployed_at: Option<DateTime<Utc>>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub constraints: Vec<ConstraintState>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub verifications: Vec<VerificationState>
+}
+
+impl Display for PendingVersionStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match serde_json::to_string_pretty(&self) {
+            Ok(json) => write!(f, "{json}"),
+            Err(error) => panic!("Error serializing pending version status: {error}")
+        }
+    }
+}
+
+/// The `ConstraintState` struct is the state of a constraint of a Spinnaker environment for a version. It contains
+/// the following fields:
+///
+/// - `constraint_type`: The type of the constraint, such as `manual-judgement` or `allowed-times`.
+/// - `status`: The status of the constraint: `BLOCKED`, `PENDING`, `PASS`, `FAIL` or `FORCE_PASS`.
+/// - `started_at`: When the constraint started to be evaluated, if it did.
+/// - `judged_at` and `judged_by`: When and by whom the constraint was judged, if it was.
+/// - `comment`: The comment of the judgment, if any.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ConstraintState {
+    #[serde(rename = "type")]
+    pub constraint_type: String,
+    pub status: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<DateTime<Utc>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub judged_at: Option<DateTime<Utc>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub judged_by: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>
+}
+
+/// The `VerificationState` struct is the state of a verification of a version in a Spinnaker environment. It
+/// contains the following fields:
+///
+/// - `id`: The ID of the verification.
+/// - `status`: The status of the verification: `NOT_EVALUATED`, `PENDING`, `PASS`, `FAIL` or `FORCE_PASS`.
+/// - `started_at` and `completed_at`: When the verification started and completed, if it did.
+/// - `link`: A link to the verification's results, if any.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct VerificationState {
+    pub id: String,
+    pub status: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<DateTime<Utc>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<DateTime<Utc>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>
+}
+
+/// Builds the set of the given glob patterns, which match files in any directory if they have no `/`.
+fn build_glob_set(patterns: &[String]) -> Result<GlobSet> {
+    let mut glob_set_builder = GlobSetBuilder::new();
+
+    patterns.iter()
+        .try_for_each(|pattern| -> Result<()> {
+            glob_set_builder.add(Glob::new(pattern)
+                .with_context(|| format!("Invalid glob pattern {pattern}"))?);
+            Ok(())
+        })?;
+
+    Ok(glob_set_builder.build()?)
+}
+
+/// Collects the results of the requests of a stage, failing with every error of the stage instead of only the first
+/// one. The first error is kept as the cause, so that it can still be inspected with `downcast_ref`.
+fn collect_all<T>(results: Vec<Result<T>>, description: &str) -> Result<Vec<T>> {
+    let total = results.len();
+    let mut values: Vec<T> = Vec::with_capacity(total);
+    let mut errors: Vec<anyhow::Error> = Vec::new();
+
+    results.into_iter()
+        .for_each(|result| match result {
+            Ok(value) => values.push(value),
+            Err(error) => errors.push(error)
+        });
+
+    if errors.len() <= 1 {
+        return match errors.pop() {
+            Some(error) => Err(error),
+            None => Ok(values)
+        };
+    }
+
+    let messages: Vec<String> = errors.iter()
+        .map(|error| format!("{error:#}"))
+        .collect();
+
+    let first_error = errors.swap_remove(0);
+    Err(first_error.context(format!("{} of {total} {description} failed: {}", messages.len(), messages.join("; "))))
+}
+
+/// Computes the stable ID of a changelog entry from the parts identifying it, as the first 16 hexadecimal digits
+/// of their SHA-256 hash, so that the same entry has the same ID in every changelog.
+fn entry_id(parts: &[&str]) -> String {
+    let mut hash = hex::encode(Sha256::digest(parts.join("\0").as_bytes()));
+    hash.truncate(16);
+
+    hash
+}
+
+/// Returns the `entry_id` of a pull request, computing it from the repository it targets, or the given repository
+/// when it's unknown, if it wasn't assigned yet.
+fn pull_request_entry_id(pull_request: &BitbucketPullRequest, repository: Option<(&str, &str)>) -> String {
+    if let Some(entry_id) = &pull_request.entry_id {
+        return entry_id.clone();
+    }
+
+    let (project, repo) = pull_request.target_repository()
+        .or(repository)
+        .unwrap_or_default();
+
+    entry_id(&["pull-request", &project.to_lowercase(), &repo.to_lowercase(), &pull_request.id.to_string()])
+}
+
+impl Changelog {
+    /// This method creates a new `Changelog` instance using the provided `ScmProvider` (such as a
+    /// `BitbucketClient` or a `GerritClient`), `JiraProvider` (such as a `JiraClient`), and `CommitSpecifier`. The changelog is generated based on the commit specifier. It can either
+    /// generate a changelog from a Spinnaker environment or a Git commit range.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,ignore
+    /// use deployment_changelog::changelog::{Changelog, CommitSpecifier, GitCommitRange};
+    /// use deployment_changelog::api::{bitbucket::BitbucketClient, jira::JiraClient};
+    ///
+    /// // Create a BitbucketClient and JiraClient with their respective server URLs.
+    /// let bitbucket_client = BitbucketClient::new("https://your-bitbucket-url");
+    /// let jira_client = JiraClient::new("https://your-jira-url");
+    ///
+    /// // Define the Git commit range for the changelog.
+    /// let commit_range = GitCommitRange {
+    ///     project: String::from("my-project"),
     ///     repo: String::from("my-repo"),
     ///     start_commit: String::from("abcdef123456"),
-    ///     end_commit: String::from("ghijkl789012")
+    ///     end_commit: String::from("ghijkl789012"),
+    ///     merge_base: false
     /// };
     ///
     /// // Create a CommitSpecifier using the Git commit range.
@@ -249,31 +1531,108 @@ impl Changelog {
     /// `CommitRange` variant. Then, we generate a `Changelog` using the `Changelog::new` method and
     /// print the formatted output.
     pub async fn new(
-        bitbucket_client: &BitbucketClient,
-        jira_client: &JiraClient,
+        scm_provider: &dyn ScmProvider,
+        jira_client: &dyn JiraProvider,
         commit_specifier: &CommitSpecifier
     ) -> Result<Changelog> {
         match commit_specifier {
+            #[cfg(feature = "spinnaker")]
             CommitSpecifier::Spinnaker(spinnaker_env) => Self::get_changelog_from_spinnaker(
-                bitbucket_client,
+                scm_provider,
                 jira_client,
                 spinnaker_env
             ).await,
             CommitSpecifier::CommitRange(commit_range) => Self::get_changelog_from_range(
-                bitbucket_client,
+                scm_provider,
                 jira_client,
                 commit_range
+            ).await,
+            CommitSpecifier::Jql(jql) => Self::get_changelog_from_jql(
+                scm_provider,
+                jira_client,
+                jql
+            ).await,
+            CommitSpecifier::TimeWindow(time_window) => Self::get_changelog_from_time_window(
+                scm_provider,
+                jira_client,
+                time_window
+            ).await,
+            CommitSpecifier::ReleaseTrain(release_train) => Self::get_changelog_from_release_train(
+                scm_provider,
+                jira_client,
+                release_train
             ).await
         }
     }
 
+    /// This method creates a new `Changelog` like `Changelog::new`, from providers shared as `Arc`s. Applications
+    /// embedding the crate can hold their source control and Jira providers as `Arc<dyn ScmProvider>` and
+    /// `Arc<dyn JiraProvider>`, and swap them at runtime for mocks or decorators, such as a provider recording
+    /// its requests.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,ignore
+    /// use std::sync::Arc;
+    /// use deployment_changelog::changelog::Changelog;
+    /// use deployment_changelog::api::{bitbucket::BitbucketClient, jira::{JiraClient, JiraProvider}, scm::ScmProvider};
+    ///
+    /// let scm_provider: Arc<dyn ScmProvider> = Arc::new(BitbucketClient::new("https://your-bitbucket-url").unwrap());
+    /// let jira_provider: Arc<dyn JiraProvider> = Arc::new(JiraClient::new("https://your-jira-url").unwrap());
+    ///
+    /// // Suppose you created a CommitSpecifier named 'commit_specifier'
+    /// let changelog = Changelog::from_shared(scm_provider.clone(), jira_provider.clone(), &commit_specifier).await.unwrap();
+    /// ```
+    pub async fn from_shared(
+        scm_provider: Arc<dyn ScmProvider>,
+        jira_provider: Arc<dyn JiraProvider>,
+        commit_specifier: &CommitSpecifier
+    ) -> Result<Changelog> {
+        Self::new(scm_provider.as_ref(), jira_provider.as_ref(), commit_specifier).await
+    }
+
+    /// This method creates a `Changelog` instance for a deployment from the `deployed` version of a repository to
+    /// the `deploying` one, as described by any deployment system.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,ignore
+    /// use deployment_changelog::changelog::{Changelog, DeploymentDescriptor, ScmCoordinates};
+    /// use deployment_changelog::api::{bitbucket::BitbucketClient, jira::JiraClient};
+    ///
+    /// let bitbucket_client = BitbucketClient::new("https://your-bitbucket-url").unwrap();
+    /// let jira_client = JiraClient::new("https://your-jira-url").unwrap();
+    ///
+    /// let scm_coords = ScmCoordinates {
+    ///     project: String::from("my-project"),
+    ///     repo: String::from("my-repo")
+    /// };
+    ///
+    /// let deployed = DeploymentDescriptor { scm_coords: scm_coords.clone(), commit: String::from("ghijkl789012") };
+    /// let deploying = DeploymentDescriptor { scm_coords, commit: String::from("abcdef123456") };
+    ///
+    /// let changelog = Changelog::between(&bitbucket_client, &jira_client, &deployed, &deploying).await.unwrap();
+    /// println!("{}", changelog);
+    /// ```
+    pub async fn between(
+        scm_provider: &dyn ScmProvider,
+        jira_client: &dyn JiraProvider,
+        deployed: &DeploymentDescriptor,
+        deploying: &DeploymentDescriptor
+    ) -> Result<Changelog> {
+        let commit_range = GitCommitRange::between(deployed, deploying)?;
+
+        Self::get_changelog_from_range(scm_provider, jira_client, &commit_range)
+            .await
+    }
+
     /// This method creates a `Changelog` instance for a Spinnaker environment. It fetches the
     /// environment's latest pending and current versions and generates a changelog based on the
     /// commit range between these two versions.
     ///
     /// ### Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// use deployment_changelog::changelog::{Changelog, CommitSpecifier, SpinnakerEnvironment};
     /// use deployment_changelog::api::{bitbucket::BitbucketClient, jira::JiraClient, spinnaker::SpinnakerClient};
     ///
@@ -286,11 +1645,12 @@ impl Changelog {
     /// let spinnaker_env = SpinnakerEnvironment {
     ///     client: spinnaker_client,
     ///     app_name: String::from("my-app"),
-    ///     env: String::from("my-environment")
+    ///     env: String::from("my-environment"),
+    ///     baseline: None
     /// };
     ///
     /// // Create a CommitSpecifier using the Spinnaker environment.
-    /// let commit_specifier = CommitSpecifier::Spinnaker(spinnaker_env);
+    /// let commit_specifier = CommitSpecifier::Spinnaker(Box::new(spinnaker_env));
     ///
     /// // Generate a Changelog using the get_changelog_from_spinnaker method and print the formatted output.
     /// let changelog = Changelog::get_changelog_from_spinnaker(&bitbucket_client, &jira_client, &spinnaker_env).await.unwrap();
@@ -301,117 +1661,45 @@ impl Changelog {
     /// We define a `SpinnakerEnvironment` instance and use it to create a `CommitSpecifier` with the
     /// `Spinnaker` variant. Then, we generate a `Changelog` using the `Changelog::get_changelog_from_spinnaker` method and
     /// print the formatted output.
+    #[cfg(feature = "spinnaker")]
     pub async fn get_changelog_from_spinnaker(
-        bitbucket_client: &BitbucketClient,
-        jira_client: &JiraClient,
+        scm_provider: &dyn ScmProvider,
+        jira_client: &dyn JiraProvider,
         spinnaker_env: &SpinnakerEnvironment
     ) -> Result<Changelog> {
-        let env_state_vars = Variables {
-            app_name: spinnaker_env.app_name.clone(),
-            environments: vec![spinnaker_env.env.clone()]
-        };
-
-        let env_states = spinnaker_env.client.get_environment_states(env_state_vars)
+        let spinnaker_range = with_priority(RequestPriority::Critical, spinnaker_env.commit_range(scm_provider))
             .await?;
 
-        let application = env_states.application
-            .with_context(|| format!("Spinnaker application {} was not found", spinnaker_env.app_name))?;
-
-        let environment = application.environments
-            .into_iter()
-            .next()
-            .with_context(|| format!("Spinnaker application {} has no environment {}", spinnaker_env.app_name, spinnaker_env.env))?;
-
-
-        let artifacts = environment.state
-            .artifacts
-            .with_context(|| format!("No artifacts found for environment {} in Spinnaker application {}", spinnaker_env.env, spinnaker_env.app_name))?;
+        let mut changelog = Self::get_changelog_from_range(
+            scm_provider,
+            jira_client,
+            &spinnaker_range.commit_range
+        ).await?;
 
-        let mut version_map = HashMap::<MdArtifactStatusInEnvironment, Vec<MdEnvironmentStatesQueryApplicationEnvironmentsStateArtifactsVersions>>::with_capacity(1);
+        changelog.metadata.configuration.spinnaker = Some(spinnaker_env.configuration());
+        changelog.metadata.pending_version = Some(spinnaker_range.pending_version);
+        changelog.spinnaker_state = spinnaker_range.spinnaker_state;
 
-        artifacts.into_iter()
-            .for_each(|artifact| {
-                if let Some(versions) = artifact.versions {
-                    versions.into_iter()
-                        .for_each(|version| {
-                            if let Some(status) = &version.status {
-                                version_map.entry(status.clone())
-                                    .or_insert_with(Vec::new)
-                                    .push(version);
-                            }
-                        });
-                }
-            });
+        Ok(changelog)
+    }
 
-        let pending_versions = version_map.remove(&MdArtifactStatusInEnvironment::PENDING)
-            .with_context(|| format!("There are no pending versions for environment {} in Spinnaker application {}", spinnaker_env.env, spinnaker_env.app_name))?;
+    /// This method creates a `Changelog` instance of the commits of a branch within a period of time, recording
+    /// the `TimeWindow` in the changelog's configuration along with the commit range it was resolved to.
+    async fn get_changelog_from_time_window(
+        scm_provider: &dyn ScmProvider,
+        jira_client: &dyn JiraProvider,
+        time_window: &TimeWindow
+    ) -> Result<Changelog> {
+        let commit_range = with_priority(RequestPriority::Critical, time_window.commit_range(scm_provider))
+            .await?;
 
-        let current_versions = version_map.remove(&MdArtifactStatusInEnvironment::CURRENT)
-            .with_context(|| format!("There are no current versions for environment {} in Spinnaker application {}", spinnaker_env.env, spinnaker_env.app_name))?;
+        let mut changelog = Self::get_changelog_from_range(scm_provider, jira_client, &commit_range)
+            .await?;
 
-        let latest_pending_version = pending_versions.into_iter()
-            .max_by_key(|version| version.build_number.clone())
-            .expect("Error getting latest pending version");
+        changelog.metadata.configuration.time_window = Some(time_window.clone());
 
-        let latest_current_version = current_versions.into_iter()
-            .max_by_key(|version| version.build_number.clone())
-            .expect("Error getting latest current version");
-
-        let pending_git_metadata = latest_pending_version.git_metadata
-            .with_context(|| format!(
-                "Error getting Git metadata for the latest pending version for Spinnaker application {}, environment {}",
-                spinnaker_env.app_name,
-                spinnaker_env.env)
-            )?;
-
-        let current_git_metadata = latest_current_version.git_metadata
-            .with_context(|| format!(
-                "Error getting Git metadata for the latest current version for Spinnaker application {}, environment {}",
-                spinnaker_env.app_name,
-                spinnaker_env.env)
-            )?;
-
-        let project = pending_git_metadata.project
-            .with_context(|| format!(
-                "Error getting the Git project for the latest pending version for Spinnaker application {}, environment {}",
-                spinnaker_env.app_name,
-                spinnaker_env.env)
-            )?;
-
-        let repo = pending_git_metadata.repo_name
-            .with_context(|| format!(
-                "Error getting the Git repository name for latest pending version for Spinnaker application {}, environment {}",
-                spinnaker_env.app_name,
-                spinnaker_env.env)
-            )?;
-
-        let start_commit = pending_git_metadata.commit
-            .with_context(|| format!(
-                "Error getting the Git commit for the latest pending version for Spinnaker application {}, environment {}",
-                spinnaker_env.app_name,
-                spinnaker_env.env)
-            )?;
-
-        let end_commit = current_git_metadata.commit
-            .with_context(|| format!(
-                "Error getting the Git commit for the latest current version for Spinnaker application {}, environment {}",
-                spinnaker_env.app_name,
-                spinnaker_env.env)
-            )?;
-
-        let commit_range = GitCommitRange {
-            project,
-            repo,
-            start_commit,
-            end_commit
-        };
-
-        Self::get_changelog_from_range(
-            bitbucket_client,
-            jira_client,
-            &commit_range
-        ).await
-    }
+        Ok(changelog)
+    }
 
     /// This method creates a `Changelog` instance for a specified Git commit range. It fetches
     /// the commits, pull requests, and issues in the range and generates a changelog based on
@@ -419,7 +1707,7 @@ impl Changelog {
     ///
     /// ### Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// use deployment_changelog::changelog::{Changelog, GitCommitRange};
     /// use deployment_changelog::api::{bitbucket::BitbucketClient, jira::JiraClient};
     ///
@@ -432,7 +1720,8 @@ impl Changelog {
     ///     project: String::from("my-project"),
     ///     repo: String::from("my-repo"),
     ///     start_commit: String::from("abcdef123456"),
-    ///     end_commit: String::from("ghijkl789012")
+    ///     end_commit: String::from("ghijkl789012"),
+    ///     merge_base: false
     /// };
     ///
     /// // Generate a Changelog using the get_changelog_from_range method and print the formatted output.
@@ -444,62 +1733,1888 @@ impl Changelog {
     /// We define a `GitCommitRange` instance and use it to generate a `Changelog` with the
     /// `Changelog::get_changelog_from_range` method. Then, we print the formatted output.
     pub async fn get_changelog_from_range(
-        bitbucket_client: &BitbucketClient,
-        jira_client: &JiraClient,
+        scm_provider: &dyn ScmProvider,
+        jira_client: &dyn JiraProvider,
         commit_range: &GitCommitRange
     ) -> Result<Changelog> {
-        let commits: Vec<BitbucketCommit> = bitbucket_client.compare_commits(
+        let (commit_range, commits) = Self::get_commits(scm_provider, commit_range)
+            .await?;
+
+        Self::get_changelog_from_commits(
+            scm_provider,
+            jira_client,
+            &commit_range,
+            commits,
+            &HashSet::new(),
+            &HashSet::new()
+        ).await
+    }
+
+    /// Resolves the ends of a range to full commit IDs and fetches its commits, explaining "not found" errors.
+    async fn get_commits(scm_provider: &dyn ScmProvider, commit_range: &GitCommitRange) -> Result<(GitCommitRange, Vec<BitbucketCommit>)> {
+        progress::start_phase(ProgressPhase::Commits, None);
+
+        let commit_range = with_priority(RequestPriority::Critical, Self::resolve_commit_range(scm_provider, commit_range))
+            .await?;
+
+        match scm_provider.commits_between(
             &commit_range.project,
             &commit_range.repo,
             &commit_range.start_commit,
             &commit_range.end_commit
-        )
-            .all()
-            .await?;
+        ).await {
+            Ok(commits) => Ok((commit_range, commits)),
+            Err(error) if is_not_found(&error) => Err(Self::explain_not_found(scm_provider, &commit_range, error).await),
+            Err(error) => Err(error)
+        }
+    }
 
-        let mut pull_request_pages: Vec<BitbucketPaginated<BitbucketPullRequest>> = commits.iter()
-                .map(|commit| bitbucket_client.get_pull_requests(&commit_range.project, &commit_range.repo, &commit.id))
-                .collect();
+    /// Resolves the pull requests and issues of commits of a range into a `Changelog`, leaving out the given
+    /// pull requests, by entry ID, and issues, such as the ones already in the previous windows of the range.
+    /// The provenance still records how the commits and pull requests of the range relate to the entries left out.
+    async fn get_changelog_from_commits(
+        scm_provider: &dyn ScmProvider,
+        jira_client: &dyn JiraProvider,
+        commit_range: &GitCommitRange,
+        commits: Vec<BitbucketCommit>,
+        excluded_pull_requests: &HashSet<String>,
+        excluded_issues: &HashSet<String>
+    ) -> Result<Changelog> {
+        let commit_pull_requests: Vec<Vec<BitbucketPullRequest>> = collect_all(
+            progress::join_all(
+                ProgressPhase::PullRequests,
+                PULL_REQUEST_CONCURRENCY,
+                commits.iter()
+                    .map(|commit| scm_provider.pull_requests_for_commit(&commit_range.project, &commit_range.repo, &commit.id))
+            ).await,
+            "pull request lookups of commits"
+        )?;
 
-        let pull_requests: Vec<BitbucketPullRequest> = futures::future::join_all(
-            pull_request_pages.iter_mut()
-                .map(|page| page.all())
-        )
-            .await
-            .into_iter()
-            .collect::<Result<Vec<Vec<BitbucketPullRequest>>>>()?
-            .into_iter()
+        let mut provenance = ChangelogProvenance::default();
+        let repository = Some((commit_range.project.as_str(), commit_range.repo.as_str()));
+
+        // Pull request pages line up with the commits they were requested for.
+        commits.iter()
+            .zip(commit_pull_requests.iter())
+            .for_each(|(commit, pull_requests)| {
+                pull_requests.iter()
+                    .for_each(|pull_request| {
+                        provenance.pull_request_commits.entry(pull_request_entry_id(pull_request, repository))
+                            .or_default()
+                            .push(commit.id.clone());
+                    });
+            });
+
+        let pull_requests: Vec<BitbucketPullRequest> = commit_pull_requests.into_iter()
             .flatten()
+            .filter(|pull_request| !excluded_pull_requests.contains(&pull_request_entry_id(pull_request, repository)))
             .collect::<HashSet<BitbucketPullRequest>>()
             .into_iter()
             .collect();
 
-        let pull_request_issues: Vec<BitbucketPullRequestIssue> = futures::future::join_all(
-            pull_requests.iter()
-                .map(|pull_request| bitbucket_client.get_pull_request_issues(&commit_range.project, &commit_range.repo, pull_request.id))
-        )
-            .await
-            .into_iter()
-            .collect::<Result<Vec<Vec<BitbucketPullRequestIssue>>>>()?
-            .into_iter()
+        let pull_request_issues_by_pull_request: Vec<Vec<BitbucketPullRequestIssue>> = collect_all(
+            progress::join_all(
+                ProgressPhase::PullRequestIssues,
+                PULL_REQUEST_ISSUE_CONCURRENCY,
+                pull_requests.iter()
+                    .map(|pull_request| {
+                        // Pull requests from forks and personal repositories are scoped to the repository they target.
+                        let (project, repo) = pull_request.target_repository()
+                            .unwrap_or((&commit_range.project, &commit_range.repo));
+
+                        scm_provider.issues_for_pull_request(project, repo, pull_request.id)
+                    })
+            ).await,
+            "issue lookups of pull requests"
+        )?;
+
+        pull_requests.iter()
+            .zip(pull_request_issues_by_pull_request.iter())
+            .for_each(|(pull_request, pull_request_issues)| {
+                pull_request_issues.iter()
+                    .for_each(|pull_request_issue| {
+                        provenance.issue_pull_requests.entry(pull_request_issue.key.clone())
+                            .or_default()
+                            .push(pull_request_entry_id(pull_request, repository));
+                    });
+            });
+
+        let pull_request_issues: Vec<BitbucketPullRequestIssue> = pull_request_issues_by_pull_request.into_iter()
             .flatten()
+            .filter(|pull_request_issue| !excluded_issues.contains(&pull_request_issue.key))
             .collect::<HashSet<BitbucketPullRequestIssue>>()
             .into_iter()
             .collect();
 
-        let issues = futures::future::join_all(
-            pull_request_issues.iter()
-                .map(|pull_request_issue| jira_client.get_issue(&pull_request_issue.key))
-        )
-            .await
+        // A single permission check per project fails fast where every issue request would fail on its own.
+        let project_keys: Vec<String> = pull_request_issues.iter()
+            .filter_map(|pull_request_issue| pull_request_issue.key.rsplit_once('-'))
+            .map(|(project_key, _)| project_key.to_string())
+            .collect::<BTreeSet<String>>()
             .into_iter()
-            .collect::<Result<Vec<JiraIssue>>>()?;
+            .collect();
+
+        jira_client.check_browse_permission(&project_keys).await?;
 
-        Ok(Changelog {
+        let issues: Vec<JiraIssue> = collect_all(
+            with_priority(RequestPriority::Bulk, progress::join_all(
+                ProgressPhase::Issues,
+                ISSUE_CONCURRENCY,
+                pull_request_issues.iter()
+                    .map(|pull_request_issue| jira_client.issue(&pull_request_issue.key))
+            )).await,
+            "Jira issue requests"
+        )?;
+
+        let metadata = ChangelogMetadata::new(ChangelogConfiguration {
+            scm_url: redact_url(scm_provider.project_base_url(&commit_range.project).as_str()),
+            jira_url: redact_url(jira_client.base_url().as_str()),
+            commit_range: Some(commit_range.clone()),
+            spinnaker: None,
+            jql: None,
+            time_window: None,
+            release_train: None
+        });
+
+        let stats = ChangelogStats::new(&commits, &pull_requests, &issues, &metadata.generated_at);
+
+        let mut changelog = Changelog {
             commits,
             pull_requests,
-            issues
+            issues,
+            tracked_issues: Vec::new(),
+            provenance,
+            stats,
+            notes: Vec::new(),
+            spinnaker_state: None,
+            migrations: Vec::new(),
+            feature_flags: Vec::new(),
+            feature_flag_files: Vec::new(),
+            segments: Vec::new(),
+            dependencies: Vec::new(),
+            metadata
+        };
+
+        changelog.assign_entry_ids();
+        Ok(changelog)
+    }
+
+    /// This method prepares the changelog of a Spinnaker environment or a Git commit range to be generated one
+    /// window of `window_size` commits at a time, so that the pull requests and issues of very large ranges don't
+    /// have to be held in memory all at once. The commits of the range are listed upfront, and the pull requests
+    /// and issues of each window are only fetched when `ChangelogWindows::next` is called.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,ignore
+    /// use deployment_changelog::changelog::{Changelog, CommitSpecifier};
+    ///
+    /// // Suppose you created a BitbucketClient, a JiraClient and a CommitSpecifier
+    /// let mut windows = Changelog::windows(&bitbucket_client, &jira_client, &commit_specifier, 500).await.unwrap();
+    ///
+    /// while let Some(changelog) = windows.next().await.unwrap() {
+    ///     println!("{}", changelog);
+    /// }
+    /// ```
+    pub async fn windows<'a>(
+        scm_provider: &'a dyn ScmProvider,
+        jira_client: &'a dyn JiraProvider,
+        commit_specifier: &CommitSpecifier,
+        window_size: usize
+    ) -> Result<ChangelogWindows<'a>> {
+        if window_size == 0 {
+            bail!("The window size must be at least one commit");
+        }
+
+        let mut time_window = None;
+
+        let (commit_range, spinnaker, pending_version, spinnaker_state) = match commit_specifier {
+            #[cfg(feature = "spinnaker")]
+            CommitSpecifier::Spinnaker(spinnaker_env) => {
+                let spinnaker_range = with_priority(RequestPriority::Critical, spinnaker_env.commit_range(scm_provider))
+                    .await?;
+
+                (
+                    spinnaker_range.commit_range,
+                    Some(spinnaker_env.configuration()),
+                    Some(spinnaker_range.pending_version),
+                    spinnaker_range.spinnaker_state
+                )
+            },
+            CommitSpecifier::CommitRange(commit_range) => (commit_range.clone(), None, None, None),
+            CommitSpecifier::TimeWindow(window) => {
+                let commit_range = with_priority(RequestPriority::Critical, window.commit_range(scm_provider))
+                    .await?;
+
+                time_window = Some(window.clone());
+                (commit_range, None, None, None)
+            },
+            CommitSpecifier::Jql(_) | CommitSpecifier::ReleaseTrain(_) => bail!("Changelogs can only be generated in windows for Spinnaker environments, commit ranges and time windows")
+        };
+
+        let (commit_range, commits) = Self::get_commits(scm_provider, &commit_range)
+            .await?;
+
+        Ok(ChangelogWindows {
+            scm_provider,
+            jira_client,
+            commit_range,
+            spinnaker,
+            time_window,
+            pending_version,
+            spinnaker_state,
+            commits: commits.into_iter(),
+            window_size,
+            seen_pull_requests: HashSet::new(),
+            seen_issues: HashSet::new()
         })
     }
-}
 
+    /// Resolves both ends of a range, which may be branches, tags or abbreviated commit IDs, to the full IDs of
+    /// their commits, so that an invalid end is reported as such rather than as a failed comparison. The end of
+    /// the range is then replaced by the merge base of the two commits if the range asks for it.
+    async fn resolve_commit_range(scm_provider: &dyn ScmProvider, commit_range: &GitCommitRange) -> Result<GitCommitRange> {
+        let resolve_ref = |commit: &'static str, reference: &str| {
+            let reference = reference.to_string();
+
+            async move {
+                scm_provider.resolve_ref(&commit_range.project, &commit_range.repo, &reference)
+                    .await
+                    .map(|commit_id| commit_id.ok_or_else(|| format!("{commit} commit {reference}")))
+                    .with_context(|| format!("Error resolving the {commit} commit {reference} in {}/{}", commit_range.project, commit_range.repo))
+            }
+        };
+
+        let (start_commit, end_commit) = futures::future::try_join(
+            resolve_ref("start", &commit_range.start_commit),
+            resolve_ref("end", &commit_range.end_commit)
+        ).await?;
+
+        let missing_commits: Vec<String> = [&start_commit, &end_commit].into_iter()
+            .filter_map(|commit_id| commit_id.clone().err())
+            .collect();
+
+        let (start_commit, end_commit) = match (start_commit, end_commit) {
+            (Ok(start_commit), Ok(end_commit)) => (start_commit, end_commit),
+            _ => {
+                let error = anyhow::anyhow!(
+                    "The {} {} not found in {}/{}",
+                    missing_commits.join(" and the "),
+                    if missing_commits.len() == 1 { "was" } else { "were" },
+                    commit_range.project,
+                    commit_range.repo
+                );
+
+                return Err(Self::explain_not_found(scm_provider, commit_range, error).await);
+            }
+        };
+
+        let end_commit = match commit_range.merge_base {
+            true => scm_provider.merge_base(&commit_range.project, &commit_range.repo, &start_commit, &end_commit)
+                .await?,
+            false => end_commit
+        };
+
+        Ok(GitCommitRange {
+            project: commit_range.project.clone(),
+            repo: commit_range.repo.clone(),
+            start_commit,
+            end_commit,
+            merge_base: commit_range.merge_base
+        })
+    }
+
+    /// Adds a suggestion to a "not found" error from the source control server when the project or the
+    /// repository of the commit range doesn't exist, based on the projects and repositories it lists.
+    async fn explain_not_found(scm_provider: &dyn ScmProvider, commit_range: &GitCommitRange, error: anyhow::Error) -> anyhow::Error {
+        let projects = scm_provider.list_projects()
+            .await
+            .unwrap_or_default();
+
+        if !projects.is_empty() && !projects.contains(&commit_range.project) {
+            let suggestion = did_you_mean(&commit_range.project, &projects);
+            return error.context(format!("Project {} was not found{suggestion}", commit_range.project));
+        }
+
+        let repos = scm_provider.list_repos(&commit_range.project)
+            .await
+            .unwrap_or_default();
+
+        if !repos.is_empty() && !repos.contains(&commit_range.repo) {
+            let suggestion = did_you_mean(&commit_range.repo, &repos);
+            return error.context(format!("Repository {}/{} was not found{suggestion}", commit_range.project, commit_range.repo));
+        }
+
+        error
+    }
+
+    /// This method creates a `Changelog` instance for the Jira issues matching a JQL query. It is the inverse
+    /// of `get_changelog_from_range`: the issues are searched first, and the commits and pull requests
+    /// linked to them are resolved through Jira's development panel (dev-status API).
+    ///
+    /// The development panel doesn't relate commits to pull requests, so the `pull_request_commits`
+    /// provenance is left empty.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,ignore
+    /// use deployment_changelog::changelog::Changelog;
+    /// use deployment_changelog::api::{bitbucket::BitbucketClient, jira::JiraClient};
+    ///
+    /// let bitbucket_client = BitbucketClient::new("https://your-bitbucket-url");
+    /// let jira_client = JiraClient::new("https://your-jira-url");
+    ///
+    /// let changelog = Changelog::get_changelog_from_jql(&bitbucket_client, &jira_client, "fixVersion = 2024.06").await.unwrap();
+    /// println!("{}", changelog);
+    /// ```
+    pub async fn get_changelog_from_jql(
+        scm_provider: &dyn ScmProvider,
+        jira_client: &dyn JiraProvider,
+        jql: &str
+    ) -> Result<Changelog> {
+        progress::start_phase(ProgressPhase::Issues, None);
+
+        let issues: Vec<JiraIssue> = jira_client.search(jql)
+            .await
+            .with_context(|| format!("Error searching for Jira issues matching {jql}"))?;
+
+        let issue_pull_requests: Vec<Vec<JiraDevStatusPullRequest>> = collect_all(
+            progress::join_all(
+                ProgressPhase::PullRequests,
+                ISSUE_CONCURRENCY,
+                issues.iter()
+                    .map(|issue| jira_client.pull_requests_for_issue(&issue.id))
+            ).await,
+            "pull request lookups of Jira issues"
+        )?;
+
+        let issue_commits: Vec<Vec<JiraDevStatusCommit>> = collect_all(
+            progress::join_all(
+                ProgressPhase::Commits,
+                ISSUE_CONCURRENCY,
+                issues.iter()
+                    .map(|issue| jira_client.commits_for_issue(&issue.id))
+            ).await,
+            "commit lookups of Jira issues"
+        )?;
+
+        let mut provenance = ChangelogProvenance::default();
+        // Pull requests of different repositories can share a number, so they're told apart by their entry IDs.
+        let mut pull_requests_by_id: BTreeMap<String, BitbucketPullRequest> = BTreeMap::new();
+
+        issues.iter()
+            .zip(issue_pull_requests)
+            .try_for_each(|(issue, pull_requests)| -> Result<()> {
+                pull_requests.into_iter()
+                    .try_for_each(|pull_request| {
+                        let pull_request = BitbucketPullRequest::try_from(pull_request)?;
+
+                        let pull_request_entry_id = pull_request_entry_id(&pull_request, None);
+                        let issue_pull_request_ids = provenance.issue_pull_requests.entry(issue.key.clone())
+                            .or_default();
+
+                        if !issue_pull_request_ids.contains(&pull_request_entry_id) {
+                            issue_pull_request_ids.push(pull_request_entry_id.clone());
+                        }
+
+                        pull_requests_by_id.entry(pull_request_entry_id)
+                            .or_insert(pull_request);
+
+                        Ok(())
+                    })
+            })?;
+
+        let mut commits_by_id: BTreeMap<String, BitbucketCommit> = BTreeMap::new();
+
+        issue_commits.into_iter()
+            .flatten()
+            .for_each(|commit| {
+                commits_by_id.entry(commit.id.clone())
+                    .or_insert_with(|| commit.into());
+            });
+
+        let commits: Vec<BitbucketCommit> = commits_by_id.into_values().collect();
+        let pull_requests: Vec<BitbucketPullRequest> = pull_requests_by_id.into_values().collect();
+
+        let metadata = ChangelogMetadata::new(ChangelogConfiguration {
+            scm_url: redact_url(scm_provider.base_url().as_str()),
+            jira_url: redact_url(jira_client.base_url().as_str()),
+            commit_range: None,
+            spinnaker: None,
+            jql: Some(jql.to_string()),
+            time_window: None,
+            release_train: None
+        });
+
+        let stats = ChangelogStats::new(&commits, &pull_requests, &issues, &metadata.generated_at);
+
+        let mut changelog = Changelog {
+            commits,
+            pull_requests,
+            issues,
+            tracked_issues: Vec::new(),
+            provenance,
+            stats,
+            notes: Vec::new(),
+            spinnaker_state: None,
+            migrations: Vec::new(),
+            feature_flags: Vec::new(),
+            feature_flag_files: Vec::new(),
+            segments: Vec::new(),
+            dependencies: Vec::new(),
+            metadata
+        };
+
+        changelog.assign_entry_ids();
+        Ok(changelog)
+    }
+
+    /// This method creates a `Changelog` instance of a release train: the Jira issues of its fix version, and the
+    /// pull requests of its repositories that reference them. The commits of the pull requests are not listed.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,ignore
+    /// use deployment_changelog::changelog::{Changelog, ReleaseTrain};
+    ///
+    /// // Suppose you created a BitbucketClient, a JiraClient and a ReleaseTrain
+    /// let changelog = Changelog::get_changelog_from_release_train(&bitbucket_client, &jira_client, &release_train).await.unwrap();
+    ///
+    /// for pull_request in &changelog.pull_requests {
+    ///     println!("{:?} #{}: {}", pull_request.target_repository(), pull_request.id, pull_request.title);
+    /// }
+    /// ```
+    pub async fn get_changelog_from_release_train(
+        scm_provider: &dyn ScmProvider,
+        jira_client: &dyn JiraProvider,
+        release_train: &ReleaseTrain
+    ) -> Result<Changelog> {
+        progress::start_phase(ProgressPhase::Issues, None);
+
+        let jql = release_train.jql();
+        let issues: Vec<JiraIssue> = jira_client.search(&jql)
+            .await
+            .with_context(|| format!("Error searching for the Jira issues of fix version {}", release_train.fix_version))?;
+
+        let issue_keys: HashSet<&str> = issues.iter()
+            .map(|issue| issue.key.as_str())
+            .collect();
+
+        let repo_pull_requests: Vec<Vec<BitbucketPullRequest>> = match issues.iter().map(|issue| issue.fields.created).min() {
+            Some(oldest_issue_created) => collect_all(
+                progress::join_all(
+                    ProgressPhase::PullRequests,
+                    RELEASE_TRAIN_REPO_CONCURRENCY,
+                    release_train.repos.iter()
+                        .map(|repo| async move {
+                            scm_provider.pull_requests_updated_since(&repo.project, &repo.repo, &oldest_issue_created)
+                                .await
+                                .with_context(|| format!("Error listing the pull requests of {repo}"))
+                        })
+                ).await,
+                "pull request listings of the release train repositories"
+            )?,
+            None => Vec::new()
+        };
+
+        let mut provenance = ChangelogProvenance::default();
+        let mut pull_requests: Vec<BitbucketPullRequest> = Vec::new();
+
+        release_train.repos.iter()
+            .zip(repo_pull_requests)
+            .flat_map(|(repo, pull_requests)| pull_requests.into_iter().map(move |pull_request| (repo, pull_request)))
+            .for_each(|(repo, mut pull_request)| {
+                // Pull requests are listed per repository, so they belong to it even when the server leaves out their refs.
+                pull_request.entry_id = Some(pull_request_entry_id(&pull_request, Some((&repo.project, &repo.repo))));
+
+                let source_branch = pull_request.from_ref.as_ref()
+                    .map(|from_ref| from_ref.display_id.as_str())
+                    .unwrap_or_default();
+
+                let referenced_issue_keys: Vec<String> = extract_issue_keys(&format!("{}\n{}\n{source_branch}", pull_request.title, pull_request.description))
+                    .into_iter()
+                    .filter(|issue_key| issue_keys.contains(issue_key.as_str()))
+                    .collect();
+
+                if referenced_issue_keys.is_empty() {
+                    return;
+                }
+
+                referenced_issue_keys.into_iter()
+                    .for_each(|issue_key| provenance.issue_pull_requests.entry(issue_key)
+                        .or_default()
+                        .push(pull_request_entry_id(&pull_request, None)));
+
+                pull_requests.push(pull_request);
+            });
+
+        let metadata = ChangelogMetadata::new(ChangelogConfiguration {
+            scm_url: redact_url(scm_provider.base_url().as_str()),
+            jira_url: redact_url(jira_client.base_url().as_str()),
+            commit_range: None,
+            spinnaker: None,
+            jql: Some(jql),
+            time_window: None,
+            release_train: Some(release_train.clone())
+        });
+
+        let stats = ChangelogStats::new(&[], &pull_requests, &issues, &metadata.generated_at);
+
+        let mut changelog = Changelog {
+            commits: Vec::new(),
+            pull_requests,
+            issues,
+            tracked_issues: Vec::new(),
+            provenance,
+            stats,
+            notes: Vec::new(),
+            spinnaker_state: None,
+            migrations: Vec::new(),
+            feature_flags: Vec::new(),
+            feature_flag_files: Vec::new(),
+            segments: Vec::new(),
+            dependencies: Vec::new(),
+            metadata
+        };
+
+        changelog.assign_entry_ids();
+        Ok(changelog)
+    }
+
+    /// This method computes the cycle time of each of the changelog's issues from their Jira status
+    /// transition history, as the time between their first transition to `start_status` and their
+    /// last transition to `end_status`.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,ignore
+    /// // Suppose you generated a Changelog named 'changelog'
+    /// changelog.compute_cycle_times("In Progress", "Done");
+    ///
+    /// for issue in &changelog.issues {
+    ///     println!("{}: {:?}", issue.key, issue.cycle_time);
+    /// }
+    /// ```
+    pub fn compute_cycle_times(&mut self, start_status: &str, end_status: &str) {
+        self.issues.iter_mut()
+            .for_each(|issue| issue.cycle_time = issue.compute_cycle_time(start_status, end_status));
+    }
+
+    /// This method assigns a stable `entry_id` to the commits, pull requests, issues and tracked issues of the
+    /// changelog that don't have one yet, so that the systems consuming successive changelogs can tell which
+    /// entries they have already seen. The ID is a hash of what identifies the entry: the SHA of a commit, the
+    /// target repository and ID of a pull request, the key of an issue, and the tracker and ID of a tracked issue.
+    /// Pull requests whose target repository isn't known are assigned the repository of the commit range.
+    ///
+    /// The changelogs generated by this crate already have their entry IDs, and redacting a changelog keeps them.
+    /// This is only needed for changelogs stored before they were recorded, or built by hand.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,ignore
+    /// // Suppose you generated a Changelog named 'changelog'
+    /// changelog.assign_entry_ids();
+    ///
+    /// for issue in &changelog.issues {
+    ///     println!("{}: {:?}", issue.key, issue.entry_id);
+    /// }
+    /// ```
+    pub fn assign_entry_ids(&mut self) {
+        let commit_range = self.metadata.configuration.commit_range.as_ref()
+            .map(|commit_range| (commit_range.project.as_str(), commit_range.repo.as_str()));
+
+        self.commits.iter_mut()
+            .for_each(|commit| {
+                commit.entry_id.get_or_insert_with(|| entry_id(&["commit", &commit.id]));
+            });
+
+        self.pull_requests.iter_mut()
+            .for_each(|pull_request| pull_request.entry_id = Some(pull_request_entry_id(pull_request, commit_range)));
+
+        self.issues.iter_mut()
+            .for_each(|issue| {
+                issue.entry_id.get_or_insert_with(|| entry_id(&["issue", &issue.key]));
+            });
+
+        self.tracked_issues.iter_mut()
+            .for_each(|tracked_issue| {
+                tracked_issue.entry_id.get_or_insert_with(|| entry_id(&["tracked-issue", &tracked_issue.tracker, &tracked_issue.id]));
+            });
+    }
+
+    /// This method extracts the release notes of the changelog's pull requests, the sections of their
+    /// descriptions delimited by `<!-- release-notes -->` markers, which renderers prefer to the pull request
+    /// titles. The release notes are kept when the changelog is redacted for an external audience.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,ignore
+    /// // Suppose you generated a Changelog named 'changelog'
+    /// changelog.extract_release_notes();
+    ///
+    /// for pull_request in &changelog.pull_requests {
+    ///     println!("#{}: {}", pull_request.id, pull_request.release_note.as_deref().unwrap_or(&pull_request.title));
+    /// }
+    /// ```
+    pub fn extract_release_notes(&mut self) {
+        self.pull_requests.iter_mut()
+            .for_each(|pull_request| pull_request.release_note = pull_request.extract_release_note());
+    }
+
+    /// This method links the work items from other issue trackers, such as Trello cards or Asana tasks,
+    /// that are referenced in the titles and descriptions of the changelog's pull requests. The resolved
+    /// work items are stored in `tracked_issues`, and the pull requests referencing them in the provenance.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,ignore
+    /// use deployment_changelog::api::{tracker::IssueTracker, trello::TrelloClient};
+    ///
+    /// // Suppose you generated a Changelog named 'changelog'
+    /// let issue_trackers: Vec<Box<dyn IssueTracker>> = vec![
+    ///     Box::new(TrelloClient::new("https://api.trello.com", "api-key", "token").unwrap())
+    /// ];
+    ///
+    /// changelog.link_tracked_issues(&issue_trackers).await.unwrap();
+    ///
+    /// for tracked_issue in &changelog.tracked_issues {
+    ///     println!("{}: {} ({:?})", tracked_issue.tracker, tracked_issue.title, tracked_issue.status);
+    /// }
+    /// ```
+    pub async fn link_tracked_issues(&mut self, issue_trackers: &[Box<dyn IssueTracker>]) -> Result<()> {
+        let mut references: BTreeMap<(usize, String), Vec<String>> = BTreeMap::new();
+
+        issue_trackers.iter()
+            .enumerate()
+            .for_each(|(tracker_index, issue_tracker)| {
+                self.pull_requests.iter()
+                    .for_each(|pull_request| {
+                        let text = format!("{}\n{}", pull_request.title, pull_request.description);
+
+                        issue_tracker.find_references(&text)
+                            .into_iter()
+                            .for_each(|reference| {
+                                references.entry((tracker_index, reference))
+                                    .or_default()
+                                    .push(pull_request_entry_id(pull_request, None));
+                            });
+                    });
+            });
+
+        let tracked_issues: Vec<TrackedIssue> = collect_all(
+            with_priority(RequestPriority::Bulk, progress::join_all(
+                ProgressPhase::TrackedIssues,
+                TRACKED_ISSUE_CONCURRENCY,
+                references.keys()
+                    .map(|(tracker_index, reference)| issue_trackers[*tracker_index].get_tracked_issue(reference))
+            )).await,
+            "tracked issue requests"
+        )?;
+
+        tracked_issues.iter()
+            .zip(references.into_values())
+            .for_each(|(tracked_issue, pull_request_ids)| {
+                self.provenance.tracked_issue_pull_requests.insert(tracked_issue.url.clone(), pull_request_ids);
+            });
+
+        self.tracked_issues = tracked_issues;
+        self.assign_entry_ids();
+
+        Ok(())
+    }
+
+    /// This method checks the pull requests and issues that the changelog's pull requests declare they depend on
+    /// in their titles and descriptions, such as "Depends on PR #45" or "Blocked by ABC-123", storing them in
+    /// `dependencies`. A pull request dependency is resolved when the changelog includes it or it was merged,
+    /// and an issue dependency when its status is in the done category.
+    ///
+    /// Pull request references are looked up in the repository the depending pull request targets, or the
+    /// repository of the changelog's commit range. They are unresolved, with the `UNKNOWN` status, when neither
+    /// is known.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,ignore
+    /// // Suppose you generated a Changelog named 'changelog' with a BitbucketClient named 'bitbucket_client'
+    /// // and a JiraClient named 'jira_client'
+    /// changelog.check_dependencies(&bitbucket_client, &jira_client).await.unwrap();
+    ///
+    /// for dependency in changelog.unresolved_dependencies() {
+    ///     println!("#{} depends on {} ({})", dependency.pull_request_id, dependency.reference, dependency.status);
+    /// }
+    /// ```
+    pub async fn check_dependencies(&mut self, scm_provider: &dyn ScmProvider, jira_client: &dyn JiraProvider) -> Result<()> {
+        let range_repository = self.metadata.configuration.commit_range.as_ref()
+            .map(|commit_range| (commit_range.project.clone(), commit_range.repo.clone()));
+
+        let mut references: BTreeMap<(String, Option<Repository>), Vec<PullRequestReference>> = BTreeMap::new();
+
+        self.pull_requests.iter()
+            .for_each(|pull_request| {
+                let repository = pull_request.target_repository()
+                    .map(|(project, repo)| (project.to_string(), repo.to_string()))
+                    .or_else(|| range_repository.clone());
+
+                extract_dependencies(&format!("{}\n{}", pull_request.title, pull_request.description))
+                    .into_iter()
+                    .filter(|reference| *reference != format!("#{}", pull_request.id))
+                    .for_each(|reference| {
+                        // Only pull request IDs are scoped to a repository.
+                        let repository = reference.starts_with('#')
+                            .then(|| repository.clone())
+                            .flatten();
+
+                        references.entry((reference, repository))
+                            .or_default()
+                            .push((pull_request.id, pull_request_entry_id(pull_request, None)));
+                    });
+            });
+
+        if references.is_empty() {
+            return Ok(());
+        }
+
+        let statuses: Vec<(String, bool)> = collect_all(
+            with_priority(RequestPriority::Bulk, progress::join_all(
+                ProgressPhase::Dependencies,
+                DEPENDENCY_CONCURRENCY,
+                references.keys()
+                    .map(|(reference, repository)| self.dependency_status(scm_provider, jira_client, reference, repository.as_ref()))
+            )).await,
+            "dependency requests"
+        )?;
+
+        self.dependencies = references.into_iter()
+            .zip(statuses)
+            .flat_map(|(((reference, _), pull_request_ids), (status, resolved))| pull_request_ids.into_iter()
+                .map(move |(pull_request_id, pull_request_entry_id)| PullRequestDependency {
+                    pull_request_id,
+                    pull_request_entry_id,
+                    reference: reference.clone(),
+                    status: status.clone(),
+                    resolved
+                }))
+            .collect();
+
+        self.dependencies.sort_by(|a, b| (a.pull_request_id, &a.pull_request_entry_id).cmp(&(b.pull_request_id, &b.pull_request_entry_id)));
+
+        Ok(())
+    }
+
+    /// Returns the dependencies of the changelog's pull requests that are neither included nor merged or done.
+    pub fn unresolved_dependencies(&self) -> impl Iterator<Item = &PullRequestDependency> {
+        self.dependencies.iter()
+            .filter(|dependency| !dependency.resolved)
+    }
+
+    /// This method finds the size of the changelog's pull requests, the number of their commits and, when the
+    /// source control server reports them, of the files they change, storing it in their `size` and the
+    /// distributions of the sizes in `stats`.
+    ///
+    /// Pull requests are looked up in the repository they target, or the repository of the changelog's commit
+    /// range. Their size is left unknown when neither is known.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,ignore
+    /// // Suppose you generated a Changelog named 'changelog' with a BitbucketClient named 'bitbucket_client'
+    /// changelog.compute_pull_request_sizes(&bitbucket_client).await.unwrap();
+    ///
+    /// if let Some(pull_request_commits) = &changelog.stats.pull_request_commits {
+    ///     println!("Median pull request: {} commits", pull_request_commits.p50);
+    /// }
+    /// ```
+    pub async fn compute_pull_request_sizes(&mut self, scm_provider: &dyn ScmProvider) -> Result<()> {
+        let range_repository = self.metadata.configuration.commit_range.as_ref()
+            .map(|commit_range| (commit_range.project.clone(), commit_range.repo.clone()));
+
+        let pull_requests: Vec<(usize, Repository, u64)> = self.pull_requests.iter()
+            .enumerate()
+            .filter_map(|(index, pull_request)| {
+                let repository = pull_request.target_repository()
+                    .map(|(project, repo)| (project.to_string(), repo.to_string()))
+                    .or_else(|| range_repository.clone())?;
+
+                Some((index, repository, pull_request.id))
+            })
+            .collect();
+
+        let sizes: Vec<BitbucketPullRequestSize> = collect_all(
+            with_priority(RequestPriority::Bulk, progress::join_all(
+                ProgressPhase::PullRequestSizes,
+                PULL_REQUEST_SIZE_CONCURRENCY,
+                pull_requests.iter()
+                    .map(|(_, (project, repo), pull_request_id)| scm_provider.pull_request_size(project, repo, *pull_request_id))
+            )).await,
+            "pull request size requests"
+        )?;
+
+        pull_requests.into_iter()
+            .zip(sizes)
+            .for_each(|((index, _, _), size)| self.pull_requests[index].size = Some(size));
+
+        self.stats = ChangelogStats::new(&self.commits, &self.pull_requests, &self.issues, &self.metadata.generated_at);
+
+        Ok(())
+    }
+
+    /// This method sums the time logged on each of the changelog's issues from their worklogs, storing it in their
+    /// `time_spent_seconds` and the total effort of the deployment in `stats`.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,ignore
+    /// // Suppose you generated a Changelog named 'changelog' with a JiraClient named 'jira_client'
+    /// changelog.aggregate_worklogs(&jira_client).await.unwrap();
+    ///
+    /// if let Some(time_spent_seconds) = changelog.stats.time_spent_seconds {
+    ///     println!("{} hours logged", time_spent_seconds / 3600);
+    /// }
+    /// ```
+    pub async fn aggregate_worklogs(&mut self, jira_client: &dyn JiraProvider) -> Result<()> {
+        let worklogs: Vec<Vec<JiraWorklog>> = collect_all(
+            with_priority(RequestPriority::Bulk, progress::join_all(
+                ProgressPhase::Worklogs,
+                WORKLOG_CONCURRENCY,
+                self.issues.iter()
+                    .map(|issue| jira_client.worklogs(&issue.key))
+            )).await,
+            "worklog requests"
+        )?;
+
+        self.issues.iter_mut()
+            .zip(worklogs)
+            .for_each(|(issue, worklogs)| issue.time_spent_seconds = Some(worklogs.iter()
+                .map(|worklog| worklog.time_spent_seconds)
+                .sum()));
+
+        self.stats = ChangelogStats::new(&self.commits, &self.pull_requests, &self.issues, &self.metadata.generated_at);
+
+        Ok(())
+    }
+
+    /// Finds the status of a pull request or issue depended on, and whether the dependency is resolved.
+    async fn dependency_status(
+        &self,
+        scm_provider: &dyn ScmProvider,
+        jira_client: &dyn JiraProvider,
+        reference: &str,
+        repository: Option<&Repository>
+    ) -> Result<(String, bool)> {
+        if let Some(pull_request_id) = reference.strip_prefix('#') {
+            let pull_request_id: u64 = pull_request_id.parse()
+                .with_context(|| format!("Invalid pull request reference {reference}"))?;
+
+            let (project, repo) = match repository {
+                Some((project, repo)) => (project, repo),
+                None => return Ok((String::from("UNKNOWN"), false))
+            };
+
+            let included = self.pull_requests.iter()
+                .any(|pull_request| pull_request.id == pull_request_id
+                    && pull_request.target_repository().is_none_or(|target| target == (project.as_str(), repo.as_str())));
+
+            if included {
+                return Ok((String::from("INCLUDED"), true));
+            }
+
+            return match scm_provider.pull_request(project, repo, pull_request_id).await {
+                Ok(pull_request) => Ok((
+                    pull_request.state.clone().unwrap_or_else(|| String::from(if pull_request.open { "OPEN" } else { "CLOSED" })),
+                    pull_request.is_merged()
+                )),
+                Err(error) if is_not_found(&error) => Ok((String::from("NOT FOUND"), false)),
+                Err(error) => Err(error.context(format!("Error fetching pull request #{pull_request_id} of {project}/{repo}")))
+            };
+        }
+
+        let fetched_issue;
+        let issue = match self.issues.iter().find(|issue| issue.key == reference) {
+            Some(issue) => issue,
+            None => match jira_client.issue(reference).await {
+                Ok(issue) => {
+                    fetched_issue = issue;
+                    &fetched_issue
+                },
+                Err(error) if is_not_found(&error) => return Ok((String::from("NOT FOUND"), false)),
+                Err(error) => return Err(error.context(format!("Error fetching issue {reference}")))
+            }
+        };
+
+        Ok(match &issue.fields.status {
+            Some(status) => (
+                status.name.clone(),
+                status.status_category.as_ref().is_some_and(|category| category.key == "done")
+            ),
+            None => (String::from("UNKNOWN"), false)
+        })
+    }
+
+    /// This method detects the database migrations changed in the changelog's commit range, storing the
+    /// paths of the changed files matching any of the glob `patterns` in `migrations`. Changelogs generated
+    /// from a JQL query have no commit range, so nothing is detected for them.
+    ///
+    /// Patterns without a `/`, such as `*.sql`, match files in any directory.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,ignore
+    /// // Suppose you generated a Changelog named 'changelog' with a BitbucketClient named 'bitbucket_client'
+    /// let patterns = vec![String::from("**/migrations/**"), String::from("*.sql")];
+    /// changelog.detect_migrations(&bitbucket_client, &patterns).await.unwrap();
+    ///
+    /// if !changelog.migrations.is_empty() {
+    ///     println!("This deployment contains database migrations: {:?}", changelog.migrations);
+    /// }
+    /// ```
+    pub async fn detect_migrations(&mut self, scm_provider: &dyn ScmProvider, patterns: &[String]) -> Result<()> {
+        let commit_range = match &self.metadata.configuration.commit_range {
+            Some(commit_range) if !patterns.is_empty() => commit_range,
+            _ => return Ok(())
+        };
+
+        let glob_set = build_glob_set(patterns)?;
+
+        progress::start_phase(ProgressPhase::ChangedFiles, None);
+
+        let changed_files = scm_provider.changed_files(
+            &commit_range.project,
+            &commit_range.repo,
+            &commit_range.start_commit,
+            &commit_range.end_commit
+        )
+            .await
+            .with_context(|| format!("Error fetching the files changed between {} and {}", commit_range.end_commit, commit_range.start_commit))?;
+
+        self.migrations = changed_files.into_iter()
+            .filter(|path| glob_set.is_match(path))
+            .collect();
+
+        Ok(())
+    }
+
+    /// This method detects the feature flags affected by the changelog's commit range, since flag changes are
+    /// a common deployment risk. Changelogs generated from a JQL query have no commit range, so nothing is
+    /// detected for them.
+    ///
+    /// - The changed files matching any of the glob `file_patterns`, such as flag configuration files, are
+    ///   stored in `feature_flag_files`.
+    /// - The lines added or removed in the range are searched for the regular expressions of
+    ///   `reference_patterns`, such as LaunchDarkly SDK calls. The first capture group of a match is the key
+    ///   of the flag, or the whole match if there is none. The flags found are stored in `feature_flags`.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,ignore
+    /// // Suppose you generated a Changelog named 'changelog' with a BitbucketClient named 'bitbucket_client'
+    /// let file_patterns = vec![String::from("config/flags/**")];
+    /// let reference_patterns = vec![String::from(r#"isEnabled\("([\w.-]+)""#)];
+    ///
+    /// changelog.detect_feature_flags(&bitbucket_client, &file_patterns, &reference_patterns).await.unwrap();
+    ///
+    /// for feature_flag in &changelog.feature_flags {
+    ///     println!("{} changed in {:?}", feature_flag.key, feature_flag.paths);
+    /// }
+    /// ```
+    pub async fn detect_feature_flags(
+        &mut self,
+        scm_provider: &dyn ScmProvider,
+        file_patterns: &[String],
+        reference_patterns: &[String]
+    ) -> Result<()> {
+        let commit_range = match &self.metadata.configuration.commit_range {
+            Some(commit_range) => commit_range,
+            None => return Ok(())
+        };
+
+        let range_context = || format!("Error fetching the changes between {} and {}", commit_range.end_commit, commit_range.start_commit);
+
+        if !file_patterns.is_empty() {
+            let glob_set = build_glob_set(file_patterns)?;
+
+            progress::start_phase(ProgressPhase::ChangedFiles, None);
+
+            self.feature_flag_files = scm_provider.changed_files(
+                &commit_range.project,
+                &commit_range.repo,
+                &commit_range.start_commit,
+                &commit_range.end_commit
+            )
+                .await
+                .with_context(range_context)?
+                .into_iter()
+                .filter(|path| glob_set.is_match(path))
+                .collect();
+        }
+
+        if !reference_patterns.is_empty() {
+            let reference_regexes = reference_patterns.iter()
+                .map(|pattern| Regex::new(pattern)
+                    .with_context(|| format!("Invalid feature flag pattern {pattern}")))
+                .collect::<Result<Vec<Regex>>>()?;
+
+            progress::start_phase(ProgressPhase::ChangedFiles, None);
+
+            let changed_lines = scm_provider.changed_lines(
+                &commit_range.project,
+                &commit_range.repo,
+                &commit_range.start_commit,
+                &commit_range.end_commit
+            )
+                .await
+                .with_context(range_context)?;
+
+            let mut flag_paths: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+
+            changed_lines.iter()
+                .for_each(|diff_line| {
+                    reference_regexes.iter()
+                        .flat_map(|regex| regex.captures_iter(&diff_line.line))
+                        .filter_map(|captures| captures.get(1).or_else(|| captures.get(0)))
+                        .for_each(|key| {
+                            flag_paths.entry(key.as_str().to_string())
+                                .or_default()
+                                .insert(diff_line.path.clone());
+                        });
+                });
+
+            self.feature_flags = flag_paths.into_iter()
+                .map(|(key, paths)| FeatureFlagChange {
+                    key,
+                    paths: paths.into_iter().collect()
+                })
+                .collect();
+        }
+
+        Ok(())
+    }
+
+    /// This method segments the changelog by the versions it deploys, such as several builds pending deployment
+    /// to a Spinnaker environment, so that the contribution of each version is visible within the overall
+    /// deployment. The `versions` are given from the oldest to the newest with the commits they were built from,
+    /// and each segment holds the commits between the previous version, or the end of the changelog's commit
+    /// range, and its own.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,ignore
+    /// // Suppose you generated the Changelog of a SpinnakerEnvironment named 'spinnaker_env' with a BitbucketClient
+    /// // named 'bitbucket_client'
+    /// let pending_versions = spinnaker_env.pending_versions().await.unwrap();
+    /// changelog.segment(&bitbucket_client, &pending_versions).await.unwrap();
+    ///
+    /// for segment in &changelog.segments {
+    ///     println!("Build {} adds {} commits", segment.version, segment.commits.len());
+    /// }
+    /// ```
+    pub async fn segment(&mut self, scm_provider: &dyn ScmProvider, versions: &[(String, DeploymentDescriptor)]) -> Result<()> {
+        let commit_range = self.metadata.configuration.commit_range.as_ref()
+            .with_context(|| "Only the changelogs of commit ranges can be segmented by version")?;
+
+        let scm_coords = &ScmCoordinates {
+            project: commit_range.project.clone(),
+            repo: commit_range.repo.clone()
+        };
+
+        if let Some((version, descriptor)) = versions.iter().find(|(_, descriptor)| &descriptor.scm_coords != scm_coords) {
+            bail!("Version {version} was built from {} but the changelog is for {scm_coords}", descriptor.scm_coords);
+        }
+
+        let previous_commits = std::iter::once(&commit_range.end_commit)
+            .chain(versions.iter().map(|(_, descriptor)| &descriptor.commit));
+
+        let version_commits: Vec<Vec<BitbucketCommit>> = collect_all(
+            progress::join_all(
+                ProgressPhase::Commits,
+                SEGMENT_CONCURRENCY,
+                versions.iter()
+                    .zip(previous_commits)
+                    .map(|((version, descriptor), previous_commit)| async move {
+                        scm_provider.commits_between(&scm_coords.project, &scm_coords.repo, &descriptor.commit, previous_commit)
+                            .await
+                            .with_context(|| format!("Error fetching the commits of version {version}"))
+                    })
+            ).await,
+            "commit lookups of versions"
+        )?;
+
+        let changelog_commits: HashSet<&str> = self.commits.iter()
+            .map(|commit| commit.id.as_str())
+            .collect();
+
+        self.segments = versions.iter()
+            .zip(version_commits)
+            .map(|((version, descriptor), commits)| {
+                let commits: Vec<String> = commits.into_iter()
+                    .map(|commit| commit.id)
+                    .filter(|commit_id| changelog_commits.contains(commit_id.as_str()))
+                    .collect();
+
+                let pull_request_entry_ids: HashSet<&String> = self.provenance.pull_request_commits.iter()
+                    .filter(|(_, pull_request_commits)| pull_request_commits.iter().any(|commit_id| commits.contains(commit_id)))
+                    .map(|(pull_request_entry_id, _)| pull_request_entry_id)
+                    .collect();
+
+                let pull_requests: Vec<u64> = self.pull_requests.iter()
+                    .filter(|pull_request| pull_request.entry_id.as_ref().is_some_and(|entry_id| pull_request_entry_ids.contains(entry_id)))
+                    .map(|pull_request| pull_request.id)
+                    .collect();
+
+                let issues: Vec<String> = self.provenance.issue_pull_requests.iter()
+                    .filter(|(_, issue_pull_requests)| issue_pull_requests.iter().any(|pull_request_entry_id| pull_request_entry_ids.contains(pull_request_entry_id)))
+                    .map(|(issue_key, _)| issue_key.clone())
+                    .collect();
+
+                ChangelogSegment {
+                    version: version.clone(),
+                    commit: descriptor.commit.clone(),
+                    commits,
+                    pull_requests,
+                    issues
+                }
+            })
+            .collect();
+
+        Ok(())
+    }
+
+    /// This method posts a comment on every pull request of the changelog, such as a note telling its author when
+    /// the change reaches production. Pull requests whose target repository is unknown are commented on in the
+    /// repository of the changelog's commit range. With an `ScmRouter`, every comment is posted through the server
+    /// hosting the project of the pull request.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,ignore
+    /// // Suppose you generated a Changelog named 'changelog' with a BitbucketClient named 'bitbucket_client'
+    /// changelog.comment_on_pull_requests(&bitbucket_client, "Shipping to prod in version 42 on 2024-06-01").await.unwrap();
+    /// ```
+    pub async fn comment_on_pull_requests(&self, scm_provider: &dyn ScmProvider, text: &str) -> Result<()> {
+        let commit_range = self.metadata.configuration.commit_range.as_ref();
+
+        let pull_request_repositories: Vec<(&BitbucketPullRequest, &str, &str)> = self.pull_requests.iter()
+            .map(|pull_request| {
+                let (project, repo) = pull_request.target_repository()
+                    .or_else(|| commit_range.map(|commit_range| (commit_range.project.as_str(), commit_range.repo.as_str())))
+                    .with_context(|| format!("The repository of pull request {} is unknown", pull_request.id))?;
+
+                Ok((pull_request, project, repo))
+            })
+            .collect::<Result<_>>()?;
+
+        collect_all(
+            progress::join_all(
+                ProgressPhase::PullRequestComments,
+                PULL_REQUEST_COMMENT_CONCURRENCY,
+                pull_request_repositories.into_iter()
+                    .map(|(pull_request, project, repo)| scm_provider.comment_on_pull_request(project, repo, pull_request.id, text))
+            ).await,
+            "comments on pull requests"
+        )?;
+
+        Ok(())
+    }
+
+    /// This method strips the changelog down to what can be shared outside of the organization: the keys,
+    /// summaries and types of its issues, and the titles of its pull requests.
+    ///
+    /// Commits, authors, issue descriptions and comments, pull request descriptions and branches, linked work
+    /// items, deployment notes, the detected migrations and feature flags, the dependencies of pull requests and the
+    /// time logged on issues are removed, along with the server URLs, commit range and host name recorded in the
+    /// metadata. The counts of the statistics are kept.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,ignore
+    /// // Suppose you generated a Changelog named 'changelog'
+    /// changelog.redact_for_external();
+    ///
+    /// std::fs::write("changes.json", changelog.to_string()).unwrap();
+    /// ```
+    pub fn redact_for_external(&mut self) {
+        self.commits.clear();
+        self.tracked_issues.clear();
+        self.notes.clear();
+        self.spinnaker_state = None;
+        self.migrations.clear();
+        self.feature_flags.clear();
+        self.feature_flag_files.clear();
+        self.dependencies.clear();
+
+        self.segments.iter_mut()
+            .for_each(|segment| segment.commits.clear());
+
+        self.pull_requests.iter_mut()
+            .for_each(|pull_request| {
+                pull_request.description.clear();
+                pull_request.author.user = BitbucketAuthor {
+                    name: String::new(),
+                    email_address: String::new(),
+                    display_name: String::new()
+                };
+                pull_request.from_ref = None;
+                pull_request.to_ref = None;
+            });
+
+        self.issues.iter_mut()
+            .for_each(|issue| {
+                issue.fields.description = None;
+                issue.fields.comment.comments.clear();
+                issue.changelog = None;
+                issue.time_spent_seconds = None;
+            });
+
+        self.provenance.pull_request_commits.clear();
+        self.provenance.tracked_issue_pull_requests.clear();
+        self.stats.unique_authors.clear();
+        self.stats.time_spent_seconds = None;
+
+        self.metadata.generated_on = None;
+        self.metadata.retried_requests.clear();
+        self.metadata.pending_version = None;
+        self.metadata.configuration = ChangelogConfiguration {
+            scm_url: String::new(),
+            jira_url: String::new(),
+            commit_range: None,
+            spinnaker: None,
+            jql: None,
+            time_window: None,
+            release_train: None
+        };
+    }
+
+    /// This method leaves the issues of other Jira projects than `jira_project` out of the changelog, such as
+    /// issues of other teams referenced by the pull requests of a shared repository.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,ignore
+    /// // Suppose you generated a Changelog named 'changelog'
+    /// changelog.retain_jira_project("PAY");
+    ///
+    /// assert!(changelog.issues.iter().all(|issue| issue.key.starts_with("PAY-")));
+    /// ```
+    pub fn retain_jira_project(&mut self, jira_project: &str) {
+        let issue_prefix = format!("{jira_project}-");
+
+        let other_issue_keys: HashSet<String> = self.issues.iter()
+            .filter(|issue| !issue.key.starts_with(&issue_prefix))
+            .map(|issue| issue.key.clone())
+            .collect();
+
+        self.remove_pull_requests_and_issues(&HashSet::new(), &other_issue_keys);
+    }
+
+    /// This method reads a changelog stored as JSON by any version of `deployment-changelog`, upgrading the
+    /// representations of older versions to the current one, such as changelogs stored before their metadata was
+    /// recorded. The missing parts are filled in as best they can be: statistics are recomputed, while unknown
+    /// commit dates and generation times are left at the Unix epoch, without a lead time.
+    ///
+    /// ### Errors
+    ///
+    /// Returns an error if the JSON is not a changelog, or if it was stored by a newer version of
+    /// `deployment-changelog` with a schema version this one doesn't know.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,no_run
+    /// use deployment_changelog::changelog::{Changelog, CHANGELOG_SCHEMA_VERSION};
+    ///
+    /// let json = std::fs::read_to_string("changelogs/2023-05-01.json").unwrap();
+    /// let changelog = Changelog::from_json_any_version(&json).unwrap();
+    ///
+    /// assert_eq!(changelog.metadata.schema_version, CHANGELOG_SCHEMA_VERSION);
+    /// ```
+    pub fn from_json_any_version(json: &str) -> Result<Self> {
+        let mut value: serde_json::Value = serde_json::from_str(json)
+            .with_context(|| "Error parsing the changelog JSON")?;
+
+        let schema_version = value.pointer("/metadata/schemaVersion")
+            .and_then(serde_json::Value::as_u64)
+            .unwrap_or(1);
+
+        if schema_version > u64::from(CHANGELOG_SCHEMA_VERSION) {
+            bail!(
+                "The changelog has schema version {schema_version}, which is newer than the version {CHANGELOG_SCHEMA_VERSION} of deployment-changelog {}",
+                env!("CARGO_PKG_VERSION")
+            );
+        }
+
+        let upgrade = match schema_version {
+            1 => upgrade_unversioned_changelog(&mut value),
+            _ => ChangelogUpgrade::default()
+        };
+
+        let pull_request_references = match schema_version {
+            1 | 2 => Some(take_pull_request_id_references(&mut value)?),
+            _ => None
+        };
+
+        let mut changelog: Changelog = serde_json::from_value(value)
+            .with_context(|| format!("Error deserializing the changelog with schema version {schema_version}"))?;
+
+        changelog.metadata.schema_version = CHANGELOG_SCHEMA_VERSION;
+        changelog.assign_entry_ids();
+
+        if let Some(pull_request_references) = pull_request_references {
+            pull_request_references.upgrade(&mut changelog);
+        }
+
+        if upgrade.missing_stats {
+            changelog.stats = ChangelogStats::new(&changelog.commits, &changelog.pull_requests, &changelog.issues, &changelog.metadata.generated_at);
+
+            if upgrade.missing_commit_dates {
+                changelog.stats.oldest_commit_date = None;
+                changelog.stats.newest_commit_date = None;
+            }
+
+            if upgrade.missing_commit_dates || upgrade.missing_metadata {
+                changelog.stats.lead_time_seconds = None;
+            }
+        }
+
+        Ok(changelog)
+    }
+
+    /// Removes the given pull requests and issues from the changelog, along with their provenance, and
+    /// recomputes its statistics.
+    fn remove_pull_requests_and_issues(&mut self, pull_request_entry_ids: &HashSet<String>, issue_keys: &HashSet<String>) {
+        self.pull_requests.retain(|pull_request| pull_request.entry_id.as_ref().is_none_or(|entry_id| !pull_request_entry_ids.contains(entry_id)));
+        self.issues.retain(|issue| !issue_keys.contains(&issue.key));
+
+        self.provenance.pull_request_commits.retain(|pull_request_entry_id, _| !pull_request_entry_ids.contains(pull_request_entry_id));
+        self.provenance.issue_pull_requests.retain(|issue_key, _| !issue_keys.contains(issue_key));
+
+        self.stats = ChangelogStats::new(&self.commits, &self.pull_requests, &self.issues, &self.metadata.generated_at);
+    }
+}
+
+/// The parts of a stored changelog that were missing and filled in by an upgrade.
+#[derive(Debug, Default)]
+struct ChangelogUpgrade {
+    missing_stats: bool,
+    missing_commit_dates: bool,
+    missing_metadata: bool
+}
+
+/// The references to pull requests by their IDs of a changelog with schema version 2 or earlier, which are
+/// upgraded to their entry IDs once the pull requests of the changelog have them.
+#[derive(Deserialize, Debug, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct PullRequestIdReferences {
+    pull_request_commits: BTreeMap<u64, Vec<String>>,
+    issue_pull_requests: BTreeMap<String, Vec<u64>>,
+    tracked_issue_pull_requests: BTreeMap<String, Vec<u64>>
+}
+
+impl PullRequestIdReferences {
+    /// Replaces the provenance of the changelog with these references, and fills in the entry IDs of its
+    /// dependencies. Pull request IDs are taken to refer to the first pull request of the changelog with that ID,
+    /// and are left out when there is none.
+    fn upgrade(self, changelog: &mut Changelog) {
+        let mut entry_ids: HashMap<u64, String> = HashMap::new();
+
+        changelog.pull_requests.iter()
+            .for_each(|pull_request| {
+                entry_ids.entry(pull_request.id)
+                    .or_insert_with(|| pull_request_entry_id(pull_request, None));
+            });
+
+        let to_entry_ids = |pull_request_ids: Vec<u64>| -> Vec<String> {
+            pull_request_ids.into_iter()
+                .filter_map(|pull_request_id| entry_ids.get(&pull_request_id).cloned())
+                .collect()
+        };
+
+        changelog.provenance = ChangelogProvenance {
+            pull_request_commits: self.pull_request_commits.into_iter()
+                .filter_map(|(pull_request_id, commit_ids)| entry_ids.get(&pull_request_id)
+                    .map(|entry_id| (entry_id.clone(), commit_ids)))
+                .collect(),
+            issue_pull_requests: self.issue_pull_requests.into_iter()
+                .map(|(issue_key, pull_request_ids)| (issue_key, to_entry_ids(pull_request_ids)))
+                .collect(),
+            tracked_issue_pull_requests: self.tracked_issue_pull_requests.into_iter()
+                .map(|(url, pull_request_ids)| (url, to_entry_ids(pull_request_ids)))
+                .collect()
+        };
+
+        changelog.dependencies.iter_mut()
+            .for_each(|dependency| {
+                dependency.pull_request_entry_id = entry_ids.get(&dependency.pull_request_id)
+                    .cloned()
+                    .unwrap_or_default();
+            });
+    }
+}
+
+/// Takes the references to pull requests by their IDs out of the JSON of a changelog with schema version 2 or
+/// earlier, leaving an empty provenance and dependencies without entry IDs to be filled in by
+/// `PullRequestIdReferences::upgrade`.
+fn take_pull_request_id_references(value: &mut serde_json::Value) -> Result<PullRequestIdReferences> {
+    let changelog = match value.as_object_mut() {
+        Some(changelog) => changelog,
+        None => return Ok(PullRequestIdReferences::default())
+    };
+
+    let pull_request_references = match changelog.insert(String::from("provenance"), serde_json::json!(ChangelogProvenance::default())) {
+        Some(provenance) => serde_json::from_value(provenance)
+            .with_context(|| "Error deserializing the provenance of the changelog")?,
+        None => PullRequestIdReferences::default()
+    };
+
+    changelog.get_mut("dependencies")
+        .and_then(serde_json::Value::as_array_mut)
+        .into_iter()
+        .flatten()
+        .filter_map(serde_json::Value::as_object_mut)
+        .for_each(|dependency| {
+            dependency.entry("pullRequestEntryId").or_insert_with(|| serde_json::json!(""));
+        });
+
+    Ok(pull_request_references)
+}
+
+/// Upgrades the JSON of a changelog stored before its schema version was recorded to schema version 2.
+fn upgrade_unversioned_changelog(value: &mut serde_json::Value) -> ChangelogUpgrade {
+    let mut upgrade = ChangelogUpgrade::default();
+
+    let changelog = match value.as_object_mut() {
+        Some(changelog) => changelog,
+        None => return upgrade
+    };
+
+    changelog.get_mut("commits")
+        .and_then(serde_json::Value::as_array_mut)
+        .into_iter()
+        .flatten()
+        .filter_map(serde_json::Value::as_object_mut)
+        .filter(|commit| !commit.contains_key("authorTimestamp"))
+        .for_each(|commit| {
+            commit.insert(String::from("authorTimestamp"), serde_json::json!(0));
+            upgrade.missing_commit_dates = true;
+        });
+
+    changelog.entry("trackedIssues")
+        .or_insert_with(|| serde_json::json!([]));
+
+    if let Some(provenance) = changelog.entry("provenance").or_insert_with(|| serde_json::json!({})).as_object_mut() {
+        ["pullRequestCommits", "issuePullRequests", "trackedIssuePullRequests"].into_iter()
+            .for_each(|field| {
+                provenance.entry(field).or_insert_with(|| serde_json::json!({}));
+            });
+    }
+
+    if !changelog.contains_key("metadata") {
+        upgrade.missing_metadata = true;
+        changelog.insert(String::from("metadata"), serde_json::json!({
+            "toolVersion": "unknown",
+            "generatedAt": "1970-01-01T00:00:00Z",
+            "generatedOn": null,
+            "configuration": {
+                "scmUrl": "",
+                "jiraUrl": "",
+                "spinnaker": null
+            }
+        }));
+    }
+
+    if let Some(configuration) = changelog.get_mut("metadata")
+        .and_then(|metadata| metadata.get_mut("configuration"))
+        .and_then(serde_json::Value::as_object_mut)
+    {
+        if let Some(bitbucket_url) = configuration.remove("bitbucketUrl") {
+            configuration.entry("scmUrl").or_insert(bitbucket_url);
+        }
+    }
+
+    if !changelog.contains_key("stats") {
+        upgrade.missing_stats = true;
+        changelog.insert(String::from("stats"), serde_json::json!(ChangelogStats::default()));
+    }
+
+    upgrade
+}
+
+/// The `ChangelogWindows` struct generates the changelog of a commit range one window of commits at a time. It is
+/// created with `Changelog::windows`.
+///
+/// Every window is a `Changelog` of its commits, and of the pull requests and issues that weren't in a previous
+/// window. Only the entry IDs of the pull requests and the keys of the issues of previous windows are kept, so
+/// memory stays flat however large the range is. The provenance of a window still links its commits to the pull
+/// requests, and its pull requests to the issues, of previous windows, which aren't repeated. The Spinnaker
+/// environment state, if any, is only in the first window.
+pub struct ChangelogWindows<'a> {
+    scm_provider: &'a dyn ScmProvider,
+    jira_client: &'a dyn JiraProvider,
+    commit_range: GitCommitRange,
+    spinnaker: Option<SpinnakerConfiguration>,
+    time_window: Option<TimeWindow>,
+    pending_version: Option<PendingVersionStatus>,
+    spinnaker_state: Option<serde_json::Value>,
+    commits: std::vec::IntoIter<BitbucketCommit>,
+    window_size: usize,
+    seen_pull_requests: HashSet<String>,
+    seen_issues: HashSet<String>
+}
+
+impl ChangelogWindows<'_> {
+    /// Generates the changelog of the next window of commits.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the `Changelog` of the window, or `None` once every commit of the range was in a window.
+    pub async fn next(&mut self) -> Result<Option<Changelog>> {
+        let commits: Vec<BitbucketCommit> = self.commits.by_ref()
+            .take(self.window_size)
+            .collect();
+
+        if commits.is_empty() {
+            return Ok(None);
+        }
+
+        let mut changelog = Changelog::get_changelog_from_commits(
+            self.scm_provider,
+            self.jira_client,
+            &self.commit_range,
+            commits,
+            &self.seen_pull_requests,
+            &self.seen_issues
+        ).await?;
+
+        self.seen_pull_requests.extend(changelog.pull_requests.iter().filter_map(|pull_request| pull_request.entry_id.clone()));
+        self.seen_issues.extend(changelog.issues.iter().map(|issue| issue.key.clone()));
+
+        changelog.metadata.configuration.spinnaker = self.spinnaker.clone();
+        changelog.metadata.configuration.time_window = self.time_window.clone();
+        changelog.metadata.pending_version = self.pending_version.clone();
+        changelog.spinnaker_state = self.spinnaker_state.take();
+
+        Ok(Some(changelog))
+    }
+
+    /// Returns the number of commits of the range that weren't in a window yet.
+    pub fn remaining_commits(&self) -> usize {
+        self.commits.len()
+    }
+}
+
+/// The `EnvironmentComparison` struct compares what is deployed to two environments of a Spinnaker application.
+/// It contains the following fields:
+///
+/// - `app_name`: The name of the Spinnaker application.
+/// - `env_a` and `env_b`: The names of the compared environments.
+/// - `only_in_a`: A `Changelog` of the changes deployed to `env_a` but not to `env_b`.
+/// - `only_in_b`: A `Changelog` of the changes deployed to `env_b` but not to `env_a`.
+///
+/// Each side is generated from the commits reachable from the current version of its environment but not from
+/// the current version of the other one, so they both start from the common ancestor of the two versions.
+/// Pull requests and issues found on both sides, such as cherry-picked fixes, are then left out of both.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use deployment_changelog::changelog::{EnvironmentComparison, SpinnakerEnvironment};
+/// use deployment_changelog::api::{bitbucket::BitbucketClient, jira::JiraClient, spinnaker::SpinnakerClient};
+///
+/// let bitbucket_client = BitbucketClient::new("https://your-bitbucket-url").unwrap();
+/// let jira_client = JiraClient::new("https://your-jira-url").unwrap();
+///
+/// let staging = SpinnakerEnvironment {
+///     client: SpinnakerClient::new("https://your-spinnaker-url").unwrap(),
+///     app_name: String::from("my-app"),
+///     env: String::from("staging"),
+///     baseline: None
+/// };
+///
+/// let production = SpinnakerEnvironment {
+///     client: SpinnakerClient::new("https://your-spinnaker-url").unwrap(),
+///     app_name: String::from("my-app"),
+///     env: String::from("production"),
+///     baseline: None
+/// };
+///
+/// let comparison = EnvironmentComparison::new(&bitbucket_client, &jira_client, &staging, &production).await.unwrap();
+///
+/// for issue in &comparison.only_in_a.issues {
+///     println!("{} is in staging but not in production", issue.key);
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvironmentComparison {
+    pub app_name: String,
+    pub env_a: String,
+    pub env_b: String,
+    pub only_in_a: Changelog,
+    pub only_in_b: Changelog
+}
+
+impl Display for EnvironmentComparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match serde_json::to_string_pretty(&self) {
+            Ok(json) => write!(f, "{json}"),
+            Err(error) => panic!("Error serializing environment comparison: {error}")
+        }
+    }
+}
+
+#[cfg(feature = "spinnaker")]
+impl EnvironmentComparison {
+    /// Compares the current versions of two environments of the same Spinnaker application.
+    ///
+    /// # Arguments
+    ///
+    /// * `scm_provider` - The source control provider hosting the application's repository.
+    /// * `jira_client` - The Jira client used to fetch the issues.
+    /// * `env_a` and `env_b` - The Spinnaker environments to compare.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `EnvironmentComparison`, or an error if the current version of either
+    /// environment could not be found, or if they were built from different repositories.
+    pub async fn new(
+        scm_provider: &dyn ScmProvider,
+        jira_client: &dyn JiraProvider,
+        env_a: &SpinnakerEnvironment,
+        env_b: &SpinnakerEnvironment
+    ) -> Result<Self> {
+        let (mut versions_a, _) = env_a.get_versions().await?;
+        let (mut versions_b, _) = env_b.get_versions().await?;
+
+        let current_a = env_a.latest_deployed_commit(&mut versions_a, MdArtifactStatusInEnvironment::CURRENT)?;
+        let current_b = env_b.latest_deployed_commit(&mut versions_b, MdArtifactStatusInEnvironment::CURRENT)?;
+
+        let range_a = GitCommitRange::between(&current_b, &current_a)
+            .with_context(|| format!("Error comparing environment {} with environment {}", env_a.env, env_b.env))?;
+        let range_b = GitCommitRange::between(&current_a, &current_b)?;
+
+        let (only_in_a, only_in_b) = futures::future::try_join(
+            Changelog::get_changelog_from_range(scm_provider, jira_client, &range_a),
+            Changelog::get_changelog_from_range(scm_provider, jira_client, &range_b)
+        ).await?;
+
+        let mut comparison = Self {
+            app_name: env_a.app_name.clone(),
+            env_a: env_a.env.clone(),
+            env_b: env_b.env.clone(),
+            only_in_a,
+            only_in_b
+        };
+
+        comparison.remove_shared_changes();
+
+        [(&mut comparison.only_in_a, env_a), (&mut comparison.only_in_b, env_b)].into_iter()
+            .for_each(|(changelog, spinnaker_env)| {
+                changelog.metadata.configuration.spinnaker = Some(SpinnakerConfiguration {
+                    spinnaker_url: redact_url(spinnaker_env.client.base_url().as_str()),
+                    app_name: spinnaker_env.app_name.clone(),
+                    env: spinnaker_env.env.clone(),
+                    baseline: None
+                });
+            });
+
+        Ok(comparison)
+    }
+
+    /// Leaves out the pull requests and issues found on both sides of the comparison, which are deployed to both environments.
+    fn remove_shared_changes(&mut self) {
+        let pull_request_entry_ids_a: HashSet<&String> = self.only_in_a.pull_requests.iter()
+            .filter_map(|pull_request| pull_request.entry_id.as_ref())
+            .collect();
+
+        let shared_pull_request_entry_ids: HashSet<String> = self.only_in_b.pull_requests.iter()
+            .filter_map(|pull_request| pull_request.entry_id.clone())
+            .filter(|pull_request_entry_id| pull_request_entry_ids_a.contains(pull_request_entry_id))
+            .collect();
+
+        let issue_keys_a: HashSet<&str> = self.only_in_a.issues.iter()
+            .map(|issue| issue.key.as_str())
+            .collect();
+
+        let shared_issue_keys: HashSet<String> = self.only_in_b.issues.iter()
+            .map(|issue| issue.key.clone())
+            .filter(|issue_key| issue_keys_a.contains(issue_key.as_str()))
+            .collect();
+
+        self.only_in_a.remove_pull_requests_and_issues(&shared_pull_request_entry_ids, &shared_issue_keys);
+        self.only_in_b.remove_pull_requests_and_issues(&shared_pull_request_entry_ids, &shared_issue_keys);
+    }
+}
+
+/// The `DigestService` struct is a service whose deployments are part of a `DeployDigest`. It contains the
+/// following fields:
+///
+/// - `name`: The name of the service in the service catalog.
+/// - `team`: The team owning the service, if the catalog records it.
+/// - `spinnaker_env`: The `SpinnakerEnvironment` the service is deployed to.
+#[cfg(feature = "spinnaker")]
+#[derive(Debug)]
+pub struct DigestService {
+    pub name: String,
+    pub team: Option<String>,
+    pub spinnaker_env: SpinnakerEnvironment
+}
+
+/// The `DeployDigest` struct is the digest of everything deployed to an environment across the services of an
+/// organization since a point in time, grouped by team. It contains the following fields:
+///
+/// - `env`: The environment the services were deployed to.
+/// - `since`: The start of the digest.
+/// - `teams`: The `TeamDeployments` of the teams that deployed, in order of team name, with the services without
+///   a team last.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use chrono::{Duration, Utc};
+/// use deployment_changelog::changelog::{DeployDigest, DigestService, SpinnakerEnvironment};
+/// use deployment_changelog::api::{bitbucket::BitbucketClient, jira::JiraClient, spinnaker::SpinnakerClient};
+///
+/// let bitbucket_client = BitbucketClient::new("https://your-bitbucket-url").unwrap();
+/// let jira_client = JiraClient::new("https://your-jira-url").unwrap();
+///
+/// let services = vec![DigestService {
+///     name: String::from("payments"),
+///     team: Some(String::from("Billing")),
+///     spinnaker_env: SpinnakerEnvironment {
+///         client: SpinnakerClient::new("https://your-spinnaker-url").unwrap(),
+///         app_name: String::from("payments"),
+///         env: String::from("production"),
+///         baseline: None
+///     }
+/// }];
+///
+/// let digest = DeployDigest::new(&bitbucket_client, &jira_client, "production", Utc::now() - Duration::hours(24), &services).await.unwrap();
+///
+/// for team in &digest.teams {
+///     println!("{} deployed {} services", team.team.as_deref().unwrap_or("No team"), team.deployments.len());
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DeployDigest {
+    pub env: String,
+    pub since: DateTime<Utc>,
+    pub teams: Vec<TeamDeployments>
+}
+
+/// The `TeamDeployments` struct is the deployments of the services of a team in a `DeployDigest`. It contains the
+/// following fields:
+///
+/// - `team`: The name of the team, or `None` for the services without a team.
+/// - `deployments`: The `ServiceDeployment` of every service of the team that was deployed, in order of service name.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamDeployments {
+    pub team: Option<String>,
+    pub deployments: Vec<ServiceDeployment>
+}
+
+/// The `ServiceDeployment` struct is what was deployed of a service in a `DeployDigest`. It contains the following
+/// fields:
+///
+/// - `service`: The name of the service.
+/// - `app_name`: The Spinnaker application of the service.
+/// - `versions`: The build numbers of the versions deployed, from the oldest to the newest.
+/// - `deployed_at`: When the newest version was deployed.
+/// - `changelog`: The `Changelog` of the commits deployed, from the version that was current at the start of the
+///   digest to the newest version.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceDeployment {
+    pub service: String,
+    pub app_name: String,
+    pub versions: Vec<String>,
+    pub deployed_at: DateTime<Utc>,
+    pub changelog: Changelog
+}
+
+impl Display for DeployDigest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match serde_json::to_string_pretty(&self) {
+            Ok(json) => write!(f, "{json}"),
+            Err(error) => panic!("Error serializing deploy digest: {error}")
+        }
+    }
+}
+
+impl DeployDigest {
+    /// Returns the changelogs of every deployment of the digest, such as to compute their cycle times.
+    pub fn changelogs_mut(&mut self) -> impl Iterator<Item = &mut Changelog> {
+        self.teams.iter_mut()
+            .flat_map(|team| team.deployments.iter_mut())
+            .map(|deployment| &mut deployment.changelog)
+    }
+}
+
+#[cfg(feature = "spinnaker")]
+impl DeployDigest {
+    /// Gets the digest of the deployments of services to an environment since a point in time. Services whose
+    /// current version was deployed before then are left out.
+    ///
+    /// # Arguments
+    ///
+    /// * `scm_provider` - The source control provider hosting the repositories of the services.
+    /// * `jira_client` - The Jira client used to fetch the issues.
+    /// * `env` - The environment the services are deployed to.
+    /// * `since` - The start of the digest.
+    /// * `services` - The `DigestService`s to get the deployments of.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `DeployDigest`, or an error if the deployments of a service could not be found.
+    pub async fn new(
+        scm_provider: &dyn ScmProvider,
+        jira_client: &dyn JiraProvider,
+        env: &str,
+        since: DateTime<Utc>,
+        services: &[DigestService]
+    ) -> Result<Self> {
+        let mut deployments_by_team: BTreeMap<Option<String>, Vec<ServiceDeployment>> = BTreeMap::new();
+
+        for service in services {
+            let spinnaker_env = &service.spinnaker_env;
+
+            let deployments = match spinnaker_env.deployments_since(&since).await
+                .with_context(|| format!("Error getting the deployments of service {}", service.name))?
+            {
+                Some(deployments) => deployments,
+                None => {
+                    log::info!("Service {} wasn't deployed to environment {} since {since}", service.name, spinnaker_env.env);
+                    continue;
+                }
+            };
+
+            let mut changelog = Changelog::get_changelog_from_range(scm_provider, jira_client, &deployments.commit_range)
+                .await
+                .with_context(|| format!("Error getting the changelog of service {}", service.name))?;
+            changelog.metadata.configuration.spinnaker = Some(spinnaker_env.configuration());
+
+            deployments_by_team.entry(service.team.clone())
+                .or_default()
+                .push(ServiceDeployment {
+                    service: service.name.clone(),
+                    app_name: spinnaker_env.app_name.clone(),
+                    versions: deployments.versions,
+                    deployed_at: deployments.deployed_at,
+                    changelog
+                });
+        }
+
+        // Teams are ordered by name, and the services without a team, ordered first by the map, come last.
+        let (mut teams, unassigned): (Vec<TeamDeployments>, Vec<TeamDeployments>) = deployments_by_team.into_iter()
+            .map(|(team, mut deployments)| {
+                deployments.sort_by(|a, b| a.service.cmp(&b.service));
+                TeamDeployments { team, deployments }
+            })
+            .partition(|team| team.team.is_some());
+        teams.extend(unassigned);
+
+        Ok(Self {
+            env: env.to_string(),
+            since,
+            teams
+        })
+    }
+}
@@ -0,0 +1,179 @@
+//! The `ci` module reports the results of a changelog to the CI system running `deployment-changelog`, so that
+//! pipelines can use them without wrapper scripts parsing the changelog.
+//!
+//! GitHub Actions reads step outputs and the job summary from files, while TeamCity parses service messages
+//! printed on the standard output of the build.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use deployment_changelog::{ci::{teamcity_messages, write_github_outputs}, render::RenderOptions};
+//!
+//! // Suppose you generated a Changelog named 'changelog' and wrote it to 'changelog.json'
+//! write_github_outputs(&changelog, "changelog.json".as_ref(), &RenderOptions::default()).unwrap();
+//!
+//! for message in teamcity_messages(&changelog) {
+//!     println!("{message}");
+//! }
+//! ```
+use std::{env, fs::OpenOptions, io::Write, path::Path};
+
+use anyhow::{Context, Result};
+
+use crate::{changelog::Changelog, render::{render_markdown, RenderOptions}};
+
+/// The environment variable holding the path of the file GitHub Actions reads step outputs from.
+const GITHUB_OUTPUT: &str = "GITHUB_OUTPUT";
+
+/// The environment variable holding the path of the file GitHub Actions reads the job summary from.
+const GITHUB_STEP_SUMMARY: &str = "GITHUB_STEP_SUMMARY";
+
+/// Writes the results of a changelog as GitHub Actions step outputs, and its Markdown rendering as the job summary.
+///
+/// The following outputs are set:
+///
+/// - `changelog-path`: The path of the rendered changelog.
+/// - `commit-count`, `pull-request-count` and `issue-count`: The volume of changes in the changelog.
+/// - `contains-migrations`: `true` if database migrations were detected, `false` otherwise.
+/// - `unresolved-dependencies`: The number of dependencies of pull requests that are neither included nor merged
+///   or done.
+///
+/// # Arguments
+///
+/// * `changelog` - The changelog to report.
+/// * `changelog_path` - The path the rendered changelog was written to.
+/// * `options` - The options the job summary is rendered with.
+///
+/// # Returns
+///
+/// An error if `GITHUB_OUTPUT` is not set, which means the tool doesn't run in GitHub Actions, or if the
+/// outputs can't be written.
+pub fn write_github_outputs(changelog: &Changelog, changelog_path: &Path, options: &RenderOptions) -> Result<()> {
+    let output_path = env::var_os(GITHUB_OUTPUT)
+        .with_context(|| format!("{GITHUB_OUTPUT} is not set, GitHub outputs can only be written in GitHub Actions"))?;
+
+    let outputs = [
+        ("changelog-path", changelog_path.display().to_string()),
+        ("commit-count", changelog.stats.commit_count.to_string()),
+        ("pull-request-count", changelog.stats.pull_request_count.to_string()),
+        ("issue-count", changelog.stats.issue_count.to_string()),
+        ("contains-migrations", (!changelog.migrations.is_empty()).to_string()),
+        ("unresolved-dependencies", changelog.unresolved_dependencies().count().to_string())
+    ];
+
+    let contents: String = outputs.iter()
+        .map(|(name, value)| format!("{name}={value}\n"))
+        .collect();
+
+    append(output_path.as_ref(), &contents)?;
+
+    // The job summary is only available in jobs running on GitHub-hosted or recent self-hosted runners.
+    if let Some(step_summary_path) = env::var_os(GITHUB_STEP_SUMMARY) {
+        append(step_summary_path.as_ref(), &format!("{}\n", render_markdown(changelog, options)))?;
+    }
+
+    Ok(())
+}
+
+fn append(path: &Path, contents: &str) -> Result<()> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(contents.as_bytes()))
+        .with_context(|| format!("Error writing to {}", path.display()))
+}
+
+/// Returns the TeamCity service messages reporting the results of a changelog, to be printed on the standard output.
+///
+/// The messages set the build status text to the change counts, report them as build statistics and as the
+/// `changelog.commitCount`, `changelog.pullRequestCount` and `changelog.issueCount` parameters, and add
+/// warnings to the build log if database migrations or unresolved dependencies of pull requests were detected.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use deployment_changelog::ci::teamcity_messages;
+///
+/// // Suppose you generated a Changelog named 'changelog'
+/// // Prints ##teamcity[buildStatusText text='{build.status.text}; 12 commits, 5 pull requests, 3 issues'] first.
+/// for message in teamcity_messages(&changelog) {
+///     println!("{message}");
+/// }
+/// ```
+pub fn teamcity_messages(changelog: &Changelog) -> Vec<String> {
+    let stats = &changelog.stats;
+
+    let mut messages = vec![teamcity_message("buildStatusText", &[(
+        "text",
+        &format!(
+            "{{build.status.text}}; {} commits, {} pull requests, {} issues",
+            stats.commit_count,
+            stats.pull_request_count,
+            stats.issue_count
+        )
+    )])];
+
+    [
+        ("commitCount", "changelogCommits", stats.commit_count),
+        ("pullRequestCount", "changelogPullRequests", stats.pull_request_count),
+        ("issueCount", "changelogIssues", stats.issue_count)
+    ]
+        .iter()
+        .for_each(|(parameter, statistic, value)| {
+            let value = value.to_string();
+
+            messages.push(teamcity_message("buildStatisticValue", &[("key", statistic), ("value", &value)]));
+            messages.push(teamcity_message("setParameter", &[("name", &format!("changelog.{parameter}")), ("value", &value)]));
+        });
+
+    if !changelog.migrations.is_empty() {
+        messages.push(teamcity_message("message", &[
+            ("text", &format!("The changelog contains database migrations: {}", changelog.migrations.join(", "))),
+            ("status", "WARNING")
+        ]));
+    }
+
+    changelog.unresolved_dependencies()
+        .for_each(|dependency| messages.push(teamcity_message("message", &[
+            ("text", &format!("Pull request #{} depends on {} ({})", dependency.pull_request_id, dependency.reference, dependency.status)),
+            ("status", "WARNING")
+        ])));
+
+    messages
+}
+
+/// Returns the TeamCity service message failing the build with the given problem, such as the error that failed
+/// the changelog generation.
+///
+/// # Arguments
+///
+/// * `description` - The description of the problem shown in TeamCity.
+pub fn teamcity_build_problem(description: &str) -> String {
+    teamcity_message("buildProblem", &[("description", description), ("identity", "deployment-changelog")])
+}
+
+fn teamcity_message(name: &str, attributes: &[(&str, &str)]) -> String {
+    let attributes: String = attributes.iter()
+        .map(|(attribute, value)| format!(" {attribute}='{}'", escape_teamcity(value)))
+        .collect();
+
+    format!("##teamcity[{name}{attributes}]")
+}
+
+/// Escapes a service message attribute value, as documented in
+/// <https://www.jetbrains.com/help/teamcity/service-messages.html#Escaped+Values>.
+fn escape_teamcity(value: &str) -> String {
+    value.chars()
+        .map(|c| match c {
+            '|' => String::from("||"),
+            '\'' => String::from("|'"),
+            '\n' => String::from("|n"),
+            '\r' => String::from("|r"),
+            '[' => String::from("|["),
+            ']' => String::from("|]"),
+            c if !c.is_ascii() => format!("|0x{:04x}", c as u32),
+            c => c.to_string()
+        })
+        .collect()
+}
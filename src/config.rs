@@ -0,0 +1,201 @@
+//! The `config` module loads the configuration file of `deployment-changelog`, which holds the settings that
+//! are too structured to be passed as command line flags.
+//!
+//! The configuration file is written in [TOML](https://toml.io/). Every section is optional, so an empty file
+//! is a valid configuration.
+//!
+//! # Example
+//!
+//! ```toml
+//! # Strip the team prefixes of issue summaries and pull request titles, and expand abbreviations.
+//! [[rewrite-rules]]
+//! pattern = '^\[[A-Z]+\]\s*'
+//!
+//! [[rewrite-rules]]
+//! pattern = '\bDB\b'
+//! replacement = 'database'
+//!
+//! # Map service names to their repository, Spinnaker application, Jira project and team.
+//! [services.payments]
+//! project = 'PAY'
+//! repo = 'payments-service'
+//! spinnaker-app = 'payments'
+//! jira-project = 'PAY'
+//! team = 'Billing'
+//!
+//! # Render customer-facing release notes along with the changelog.
+//! [outputs.customers]
+//! path = 'release-notes.md'
+//! format = 'release-notes'
+//! external = true
+//!
+//! # Cache the responses of the servers for a day.
+//! [cache]
+//! directory = '/var/cache/deployment-changelog'
+//! ttl = 86400
+//! ```
+//!
+//! ```rust,no_run
+//! use deployment_changelog::config::Config;
+//!
+//! let config = Config::load("deployment-changelog.toml").unwrap();
+//! println!("{} rewrite rules", config.rewrite_rules.len());
+//! ```
+use std::{collections::BTreeMap, fs, path::{Path, PathBuf}};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::{api::suggest::did_you_mean, changelog::Changelog, render::{render_bytes, OutputFormat, RenderOptions, RewriteRule, StatusStyles}};
+
+/// The `Config` struct is the contents of a configuration file. It contains the following fields:
+///
+/// - `rewrite_rules`: The `RewriteRule`s applied to issue summaries and pull request titles when rendering a
+///   changelog, in order, from the `[[rewrite-rules]]` tables.
+/// - `services`: The catalog of services, mapping each service name to its `ServiceConfig`, from the
+///   `[services.<name>]` tables.
+/// - `outputs`: The named `OutputConfig`s every changelog is also rendered to, from the `[outputs.<name>]` tables.
+/// - `statuses`: The `StatusStyles` of the issue statuses, added to the default ones, from the `[statuses]` table
+///   mapping status names or status categories to a `symbol` and a `color`.
+/// - `cache`: The `CacheConfig` of the response cache, from the `[cache]` table.
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Config {
+    #[serde(default)]
+    pub rewrite_rules: Vec<RewriteRule>,
+
+    #[serde(default)]
+    pub services: BTreeMap<String, ServiceConfig>,
+
+    #[serde(default)]
+    pub outputs: BTreeMap<String, OutputConfig>,
+
+    #[serde(default)]
+    pub statuses: StatusStyles,
+
+    #[serde(default)]
+    pub cache: CacheConfig
+}
+
+/// The `CacheConfig` struct holds the settings of the response cache, which the `--cache-dir`, `--cache-ttl` and
+/// `--cache-memory-entries` flags take precedence over.
+///
+/// - `directory`: The directory the responses are cached in. Responses are only cached with a directory.
+/// - `ttl`: The number of seconds cached responses are used for.
+/// - `memory_entries`: The maximum number of cached responses kept in memory.
+#[derive(Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct CacheConfig {
+    pub directory: Option<PathBuf>,
+    pub ttl: Option<u64>,
+    pub memory_entries: Option<u64>
+}
+
+/// The `ServiceConfig` struct holds the coordinates of a service, so that its changelog can be generated from its
+/// name alone. Every field is optional, only the ones needed by the way the changelog is generated are required.
+///
+/// - `project` and `repo`: The source control project and repository of the service, used for commit ranges.
+/// - `spinnaker_app`: The Spinnaker application of the service, used for environments.
+/// - `jira_project`: The key of the Jira project of the service. Issues of other projects are left out of its changelogs.
+/// - `team`: The team owning the service, which the deployments of a digest are grouped by.
+#[derive(Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct ServiceConfig {
+    pub project: Option<String>,
+    pub repo: Option<String>,
+    pub spinnaker_app: Option<String>,
+    pub jira_project: Option<String>,
+    pub team: Option<String>
+}
+
+/// The `OutputConfig` struct is a rendering of a changelog written to a file, so that a single run can produce
+/// the notes of several audiences, such as engineering notes, customer-facing release notes and a compliance log.
+///
+/// - `path`: The path of the file the rendered changelog is written to.
+/// - `format`: The `OutputFormat` of the output, which defaults to JSON.
+/// - `title`: The title of the Markdown documents, which defaults to `Changelog` or `Release notes`.
+/// - `external`: Whether the changelog is redacted for readers outside of the organization, like with `--external`.
+/// - `jira_project`: The key of the Jira project whose issues are kept. Issues of other projects are left out.
+/// - `rewrite_rules`: The `RewriteRule`s applied after the top-level ones, from the `[[outputs.<name>.rewrite-rules]]`
+///   tables.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct OutputConfig {
+    pub path: PathBuf,
+    pub format: Option<OutputFormat>,
+    pub title: Option<String>,
+
+    #[serde(default)]
+    pub external: bool,
+
+    pub jira_project: Option<String>,
+
+    #[serde(default)]
+    pub rewrite_rules: Vec<RewriteRule>
+}
+
+impl OutputConfig {
+    /// Renders a changelog for this output. The changelog is filtered on a copy, so that the same changelog can
+    /// be rendered for every output.
+    ///
+    /// # Arguments
+    ///
+    /// * `changelog` - The changelog to render, before it is redacted.
+    /// * `options` - The options the changelog is rendered with, to which the title and rewrite rules of the
+    ///   output are added.
+    ///
+    /// # Returns
+    ///
+    /// The rendered changelog, as bytes since PDF documents are binary.
+    pub fn render(&self, changelog: &Changelog, options: &RenderOptions) -> Vec<u8> {
+        let mut changelog = changelog.clone();
+
+        if let Some(jira_project) = &self.jira_project {
+            changelog.retain_jira_project(jira_project);
+        }
+
+        if self.external {
+            changelog.redact_for_external();
+        }
+
+        let mut options = options.clone();
+        options.title = self.title.clone().or(options.title);
+        options.rewrite_rules.extend(self.rewrite_rules.iter().cloned());
+
+        render_bytes(&changelog, self.format.unwrap_or(OutputFormat::Json), &options)
+    }
+}
+
+impl Config {
+    /// Loads the configuration file at the given path.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the TOML configuration file.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the `Config`, or an error if the file can't be read or is not a valid configuration.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Error reading the configuration file {}", path.display()))?;
+
+        toml::from_str(&contents)
+            .with_context(|| format!("Error parsing the configuration file {}", path.display()))
+    }
+
+    /// Returns the configuration of the service with the given name.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the `ServiceConfig`, or an error suggesting close matches if the service is not in the catalog.
+    pub fn service(&self, name: &str) -> Result<&ServiceConfig> {
+        self.services.get(name)
+            .with_context(|| {
+                let service_names: Vec<String> = self.services.keys().cloned().collect();
+                format!("Service {name} is not in the configuration file{}", did_you_mean(name, &service_names))
+            })
+    }
+}
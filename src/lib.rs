@@ -1,2 +1,44 @@
+//! `deployment_changelog` generates changelogs of deployments: the commits, pull requests and Jira issues between
+//! two versions of a repository, resolved from a commit range, a Spinnaker environment or a JQL query.
+//!
+//! The library is organized as follows:
+//!
+//! - `changelog`: The `Changelog` model and its generation from a `CommitSpecifier`.
+//! - `api`: The clients of the source control systems, Jira, Spinnaker and the other integrations, and the
+//!   `ScmProvider` and `JiraProvider` traits the changelog is generated from.
+//! - `render`: The renderings of a changelog, such as Markdown, release notes or Graphviz.
+//! - `config`: The configuration file, with rewrite rules and the service catalog.
+//! - `ci`, `metrics` and `progress`: Reporting changelogs to CI systems and metrics targets, and the progress of
+//!   their generation.
+//! - `notify`: Sending changelogs to notification destinations through the `Notifier` trait.
+//!
+//! The `deployment-changelog` command line interface is a thin binary over this library, built with the default
+//! `cli` feature.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use deployment_changelog::{api::{bitbucket::BitbucketClient, jira::JiraClient}, changelog::{Changelog, CommitSpecifier, GitCommitRange}};
+//!
+//! let bitbucket_client = BitbucketClient::new("https://bitbucket.example.com").unwrap();
+//! let jira_client = JiraClient::new("https://jira.example.com").unwrap();
+//!
+//! let commit_specifier = CommitSpecifier::CommitRange(GitCommitRange {
+//!     project: String::from("my-project"),
+//!     repo: String::from("my-repo"),
+//!     start_commit: String::from("abcdef123456"),
+//!     end_commit: String::from("ghijkl789012"),
+//!     merge_base: false
+//! });
+//!
+//! let changelog = Changelog::new(&bitbucket_client, &jira_client, &commit_specifier).await.unwrap();
+//! println!("{changelog}");
+//! ```
 pub mod api;
 pub mod changelog;
+pub mod ci;
+pub mod config;
+pub mod render;
+pub mod metrics;
+pub mod notify;
+pub mod progress;
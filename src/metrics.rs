@@ -0,0 +1,318 @@
+//! The `metrics` module exports per-deployment delivery metrics computed from a `Changelog`, so that
+//! DORA metrics such as lead time for changes and deployment frequency can be computed from the history
+//! of changelogs.
+//!
+//! Each changelog is turned into a single `DeploymentRecord`, which can be appended to a CSV file or a
+//! SQLite database, or pushed to a Prometheus Pushgateway.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use deployment_changelog::metrics::{DeploymentRecord, MetricsTarget};
+//!
+//! // Suppose you generated a Changelog named 'changelog'
+//! let record = DeploymentRecord::from(&changelog);
+//! let target: MetricsTarget = "csv:deployments.csv".parse().unwrap();
+//!
+//! target.export(&record).await.unwrap();
+//! ```
+use std::{fmt::{Debug, Display, Write as _}, fs::OpenOptions, io::Write, path::PathBuf, str::FromStr};
+
+use anyhow::{anyhow, Context, Result};
+use base64::{Engine, engine::general_purpose::URL_SAFE as BASE64_URL};
+use chrono::{DateTime, Utc};
+use reqwest::{header::{HeaderMap, HeaderValue, CONTENT_TYPE}, Url};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+
+use crate::{api::{rest::RestClient, secret::redact_url}, changelog::Changelog};
+
+/// The columns of the CSV export, in order.
+const CSV_HEADER: &str = "deployed_at,project,repo,start_commit,end_commit,app_name,env,commit_count,pull_request_count,issue_count,author_count,lead_time_seconds";
+
+/// The Pushgateway job the metrics are grouped under.
+const PUSHGATEWAY_JOB: &str = "deployment_changelog";
+
+/// The content type of the Prometheus text format the metrics are pushed in.
+const PUSHGATEWAY_CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+/// The `DeploymentRecord` struct holds the delivery metrics of a single deployment. It contains the following fields:
+///
+/// - `deployed_at`: When the deployment happened, which is when its changelog was generated.
+/// - `project`, `repo`, `start_commit` and `end_commit`: The commit range that was deployed, empty for changelogs
+///   generated from a JQL query.
+/// - `app_name` and `env`: The Spinnaker application and environment, if the changelog was generated from Spinnaker.
+/// - `commit_count`, `pull_request_count` and `issue_count`: The volume of changes in the deployment.
+/// - `author_count`: The number of distinct commit authors.
+/// - `lead_time_seconds`: The time between the oldest commit and the deployment, if there are any commits.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DeploymentRecord {
+    pub deployed_at: DateTime<Utc>,
+    pub project: String,
+    pub repo: String,
+    pub start_commit: String,
+    pub end_commit: String,
+    pub app_name: Option<String>,
+    pub env: Option<String>,
+    pub commit_count: usize,
+    pub pull_request_count: usize,
+    pub issue_count: usize,
+    pub author_count: usize,
+    pub lead_time_seconds: Option<i64>
+}
+
+impl From<&Changelog> for DeploymentRecord {
+    fn from(changelog: &Changelog) -> Self {
+        let configuration = &changelog.metadata.configuration;
+        let commit_range = configuration.commit_range.clone().unwrap_or_default();
+        let stats = &changelog.stats;
+
+        Self {
+            deployed_at: changelog.metadata.generated_at,
+            project: commit_range.project,
+            repo: commit_range.repo,
+            start_commit: commit_range.start_commit,
+            end_commit: commit_range.end_commit,
+            app_name: configuration.spinnaker.as_ref().map(|spinnaker| spinnaker.app_name.clone()),
+            env: configuration.spinnaker.as_ref().map(|spinnaker| spinnaker.env.clone()),
+            commit_count: stats.commit_count,
+            pull_request_count: stats.pull_request_count,
+            issue_count: stats.issue_count,
+            author_count: stats.unique_authors.len(),
+            lead_time_seconds: stats.lead_time_seconds
+        }
+    }
+}
+
+impl Display for DeploymentRecord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match serde_json::to_string_pretty(&self) {
+            Ok(json) => write!(f, "{json}"),
+            Err(error) => panic!("Error serializing deployment record: {error}")
+        }
+    }
+}
+
+/// The `MetricsTarget` enum represents where `DeploymentRecord`s are exported to.
+///
+/// - `Csv`: Appends a row to a CSV file, writing the header first if the file is new.
+/// - `Sqlite`: Inserts a row into the `deployments` table of a SQLite database, creating it if needed.
+/// - `Pushgateway`: Pushes gauges to a Prometheus Pushgateway, grouped by repository and environment.
+///
+/// It can be parsed from `csv:<path>`, `sqlite:<path>` or `pushgateway:<url>`.
+///
+/// # Example
+///
+/// ```rust
+/// use deployment_changelog::metrics::MetricsTarget;
+///
+/// let target: MetricsTarget = "pushgateway:http://pushgateway.example.com:9091".parse().unwrap();
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub enum MetricsTarget {
+    Csv(PathBuf),
+    Sqlite(PathBuf),
+    Pushgateway(Url)
+}
+
+impl Debug for MetricsTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MetricsTarget::Csv(path) => f.debug_tuple("Csv").field(path).finish(),
+            MetricsTarget::Sqlite(path) => f.debug_tuple("Sqlite").field(path).finish(),
+            MetricsTarget::Pushgateway(url) => f.debug_tuple("Pushgateway").field(&redact_url(url.as_str())).finish()
+        }
+    }
+}
+
+impl FromStr for MetricsTarget {
+    type Err = anyhow::Error;
+
+    fn from_str(target: &str) -> Result<Self> {
+        let (kind, location) = target.split_once(':')
+            .with_context(|| format!("Invalid metrics target {target}, expected csv:<path>, sqlite:<path> or pushgateway:<url>"))?;
+
+        match kind {
+            "csv" => Ok(MetricsTarget::Csv(PathBuf::from(location))),
+            "sqlite" => Ok(MetricsTarget::Sqlite(PathBuf::from(location))),
+            "pushgateway" => Ok(MetricsTarget::Pushgateway(
+                Url::parse(location).with_context(|| format!("Invalid Pushgateway URL {location}"))?
+            )),
+            _ => Err(anyhow!("Invalid metrics target kind {kind}, expected csv, sqlite or pushgateway"))
+        }
+    }
+}
+
+impl MetricsTarget {
+    /// Exports a `DeploymentRecord` to this target.
+    ///
+    /// # Arguments
+    ///
+    /// * `record` - The deployment record to export.
+    pub async fn export(&self, record: &DeploymentRecord) -> Result<()> {
+        self.export_with_client(record, None).await
+    }
+
+    /// Exports a `DeploymentRecord` to this target like `export`, pushing it to a Pushgateway with the given
+    /// `RestClient`, such as one with a proxy, custom root certificates and retries, instead of a default one.
+    ///
+    /// # Arguments
+    ///
+    /// * `record` - The deployment record to export.
+    /// * `pushgateway_client` - The `RestClient` whose base URL is the URL of the Pushgateway, if any.
+    pub async fn export_with_client(&self, record: &DeploymentRecord, pushgateway_client: Option<&RestClient>) -> Result<()> {
+        match (self, pushgateway_client) {
+            (MetricsTarget::Csv(path), _) => export_csv(path, record),
+            (MetricsTarget::Sqlite(path), _) => export_sqlite(path, record),
+            (MetricsTarget::Pushgateway(_), Some(client)) => export_pushgateway(client, record).await,
+            (MetricsTarget::Pushgateway(url), None) => export_pushgateway(&RestClient::new(url.as_str())?, record).await
+        }
+    }
+}
+
+fn export_csv(path: &PathBuf, record: &DeploymentRecord) -> Result<()> {
+    let is_new = !path.exists();
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Error opening metrics CSV file {}", path.display()))?;
+
+    let mut contents = String::new();
+
+    if is_new {
+        let _ = writeln!(contents, "{CSV_HEADER}");
+    }
+
+    let row = [
+        record.deployed_at.to_rfc3339(),
+        record.project.clone(),
+        record.repo.clone(),
+        record.start_commit.clone(),
+        record.end_commit.clone(),
+        record.app_name.clone().unwrap_or_default(),
+        record.env.clone().unwrap_or_default(),
+        record.commit_count.to_string(),
+        record.pull_request_count.to_string(),
+        record.issue_count.to_string(),
+        record.author_count.to_string(),
+        record.lead_time_seconds.map(|lead_time| lead_time.to_string()).unwrap_or_default()
+    ];
+
+    let _ = writeln!(
+        contents,
+        "{}",
+        row.iter()
+            .map(|field| escape_csv(field))
+            .collect::<Vec<String>>()
+            .join(",")
+    );
+
+    file.write_all(contents.as_bytes())
+        .with_context(|| format!("Error writing to metrics CSV file {}", path.display()))
+}
+
+fn export_sqlite(path: &PathBuf, record: &DeploymentRecord) -> Result<()> {
+    let connection = Connection::open(path)
+        .with_context(|| format!("Error opening metrics SQLite database {}", path.display()))?;
+
+    connection.execute(
+        "CREATE TABLE IF NOT EXISTS deployments (
+            deployed_at TEXT NOT NULL,
+            project TEXT NOT NULL,
+            repo TEXT NOT NULL,
+            start_commit TEXT NOT NULL,
+            end_commit TEXT NOT NULL,
+            app_name TEXT,
+            env TEXT,
+            commit_count INTEGER NOT NULL,
+            pull_request_count INTEGER NOT NULL,
+            issue_count INTEGER NOT NULL,
+            author_count INTEGER NOT NULL,
+            lead_time_seconds INTEGER
+        )",
+        []
+    ).with_context(|| format!("Error creating the deployments table in {}", path.display()))?;
+
+    connection.execute(
+        "INSERT INTO deployments VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+        params![
+            record.deployed_at.to_rfc3339(),
+            record.project,
+            record.repo,
+            record.start_commit,
+            record.end_commit,
+            record.app_name,
+            record.env,
+            record.commit_count,
+            record.pull_request_count,
+            record.issue_count,
+            record.author_count,
+            record.lead_time_seconds
+        ]
+    ).with_context(|| format!("Error inserting the deployment record into {}", path.display()))?;
+
+    Ok(())
+}
+
+async fn export_pushgateway(client: &RestClient, record: &DeploymentRecord) -> Result<()> {
+    let mut labels = vec![("project", record.project.as_str()), ("repo", record.repo.as_str())];
+
+    if let Some(env) = &record.env {
+        labels.push(("env", env));
+    }
+
+    // The path segments are appended to the base URL, since joining a relative path would replace its last segment.
+    let mut push_url = client.base_url.clone();
+    push_url.path_segments_mut()
+        .map_err(|_| anyhow!("The Pushgateway URL {} can't have a path", redact_url(client.base_url.as_str())))?
+        .pop_if_empty()
+        .extend(["metrics", "job", PUSHGATEWAY_JOB]);
+
+    labels.into_iter()
+        .for_each(|(name, value)| {
+            push_url.path_segments_mut()
+                .expect("The Pushgateway URL was checked to have a path")
+                .extend([format!("{name}@base64"), pushgateway_label_value(value)]);
+        });
+
+    let mut body = String::new();
+    let mut gauge = |name: &str, help: &str, value: String| {
+        let _ = writeln!(body, "# HELP {name} {help}\n# TYPE {name} gauge\n{name} {value}");
+    };
+
+    gauge("deployment_changelog_deployed_at_seconds", "The time of the last deployment.", record.deployed_at.timestamp().to_string());
+    gauge("deployment_changelog_commits", "The number of commits in the last deployment.", record.commit_count.to_string());
+    gauge("deployment_changelog_pull_requests", "The number of pull requests in the last deployment.", record.pull_request_count.to_string());
+    gauge("deployment_changelog_issues", "The number of issues in the last deployment.", record.issue_count.to_string());
+    gauge("deployment_changelog_authors", "The number of commit authors in the last deployment.", record.author_count.to_string());
+
+    if let Some(lead_time_seconds) = record.lead_time_seconds {
+        gauge("deployment_changelog_lead_time_seconds", "The time between the oldest commit and the last deployment.", lead_time_seconds.to_string());
+    }
+
+    let mut headers = HeaderMap::new();
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static(PUSHGATEWAY_CONTENT_TYPE));
+
+    client.post_bytes(push_url.as_str(), body.into_bytes(), headers).await
+        .with_context(|| format!("Error pushing metrics to the Pushgateway at {}", redact_url(push_url.as_str())))
+}
+
+/// Encodes a grouping label value of the Pushgateway in base64url, so that values with a `/` or empty values, which
+/// the Pushgateway reads as `=`, can be in the path.
+fn pushgateway_label_value(value: &str) -> String {
+    match value.is_empty() {
+        true => String::from("="),
+        false => BASE64_URL.encode(value)
+    }
+}
+
+fn escape_csv(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
@@ -0,0 +1,301 @@
+//! The `notify` module sends changelogs to notification destinations, such as desktop notifications, chat
+//! channels or webhooks, through the `Notifier` trait.
+//!
+//! A `FanOutNotifier` sends a changelog to several notifiers concurrently. A failing notifier doesn't keep the
+//! changelog from the others: every notifier runs to completion, and the failures are reported together.
+//!
+//! A `WebhookNotifier` posts changelogs to any endpoint as JSON, optionally signed with a shared secret, so that
+//! internal systems can subscribe to them without a dedicated integration.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use deployment_changelog::notify::{FanOutNotifier, NotificationMetadata, Notifier};
+//!
+//! // Suppose you generated a Changelog named 'changelog', and implemented Notifier for SlackNotifier
+//! let mut notifier = FanOutNotifier::new();
+//! notifier.add(Box::new(SlackNotifier::new("#deployments")));
+//!
+//! let metadata = NotificationMetadata {
+//!     title: String::from("New payments deployment pending in prod"),
+//!     app_name: Some(String::from("payments")),
+//!     env: Some(String::from("prod"))
+//! };
+//!
+//! notifier.notify(&changelog, &metadata).await.unwrap();
+//! ```
+use std::fmt::Debug;
+
+use anyhow::{bail, Context, Result};
+use futures::future::join_all;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE};
+use serde::Serialize;
+
+use crate::{api::{hmac::hmac_sha256, rest::RestClient, secret::SecretString}, changelog::Changelog};
+#[cfg(feature = "desktop-notifications")]
+use crate::render::RenderOptions;
+
+/// The `NotificationMetadata` struct describes the event a changelog is sent for. It contains the following
+/// fields:
+///
+/// - `title`: A one-line summary of the event, such as `New payments deployment pending in prod`.
+/// - `app_name`: The Spinnaker application of the changelog, if any.
+/// - `env`: The Spinnaker environment of the changelog, if any.
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationMetadata {
+    pub title: String,
+    pub app_name: Option<String>,
+    pub env: Option<String>
+}
+
+impl From<&Changelog> for NotificationMetadata {
+    /// Describes the generation of a changelog, titled after the Spinnaker environment, the release train, the time
+    /// window, the commit range or the JQL query it was generated from.
+    fn from(changelog: &Changelog) -> Self {
+        let configuration = &changelog.metadata.configuration;
+
+        let title = match (&configuration.spinnaker, &configuration.release_train, &configuration.time_window, &configuration.commit_range, &configuration.jql) {
+            (Some(spinnaker), _, _, _, _) => format!("Changelog of {} in {}", spinnaker.app_name, spinnaker.env),
+            (None, Some(release_train), _, _, _) => format!("Release train {}", release_train.fix_version),
+            (None, None, Some(time_window), _, _) => format!(
+                "Changelog of {}/{} from {} to {}",
+                time_window.project,
+                time_window.repo,
+                time_window.since.format("%Y-%m-%d"),
+                time_window.until.format("%Y-%m-%d")
+            ),
+            (None, None, None, Some(commit_range), _) => format!(
+                "Changelog of {}/{} from {} to {}",
+                commit_range.project,
+                commit_range.repo,
+                commit_range.start_commit.chars().take(11).collect::<String>(),
+                commit_range.end_commit.chars().take(11).collect::<String>()
+            ),
+            (None, None, None, None, Some(jql)) => format!("Changelog of {jql}"),
+            (None, None, None, None, None) => String::from("Changelog")
+        };
+
+        Self {
+            title,
+            app_name: configuration.spinnaker.as_ref().map(|spinnaker| spinnaker.app_name.clone()),
+            env: configuration.spinnaker.as_ref().map(|spinnaker| spinnaker.env.clone())
+        }
+    }
+}
+
+/// The `Notifier` trait is implemented by the destinations changelogs are sent to.
+#[async_trait::async_trait]
+pub trait Notifier: Send + Sync {
+    /// Returns the name of the notifier, such as `desktop`, naming it in logs and errors.
+    fn name(&self) -> &str;
+
+    /// Sends a changelog.
+    ///
+    /// # Arguments
+    ///
+    /// * `changelog` - The changelog to send.
+    /// * `metadata` - The `NotificationMetadata` of the event the changelog is sent for.
+    async fn notify(&self, changelog: &Changelog, metadata: &NotificationMetadata) -> Result<()>;
+}
+
+/// The `FanOutNotifier` struct sends changelogs to all of its notifiers concurrently. Every notifier runs to
+/// completion whether the others fail or not, and the failures are logged and returned together.
+#[derive(Default)]
+pub struct FanOutNotifier {
+    notifiers: Vec<Box<dyn Notifier>>
+}
+
+impl FanOutNotifier {
+    /// Creates a new `FanOutNotifier` without notifiers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a notifier changelogs are sent to.
+    pub fn add(&mut self, notifier: Box<dyn Notifier>) {
+        self.notifiers.push(notifier);
+    }
+
+    /// Returns whether the fan-out has no notifiers.
+    pub fn is_empty(&self) -> bool {
+        self.notifiers.is_empty()
+    }
+}
+
+#[async_trait::async_trait]
+impl Notifier for FanOutNotifier {
+    fn name(&self) -> &str {
+        "fan-out"
+    }
+
+    async fn notify(&self, changelog: &Changelog, metadata: &NotificationMetadata) -> Result<()> {
+        let results = join_all(self.notifiers.iter()
+            .map(|notifier| async move { (notifier.name(), notifier.notify(changelog, metadata).await) }))
+            .await;
+
+        let failed: Vec<&str> = results.into_iter()
+            .filter_map(|(name, result)| {
+                let error = result.err()?;
+                log::warn!("Error sending the changelog to the {name} notifier: {error:#}");
+
+                Some(name)
+            })
+            .collect();
+
+        if !failed.is_empty() {
+            bail!("{} of {} notifiers failed: {}", failed.len(), self.notifiers.len(), failed.join(", "));
+        }
+
+        Ok(())
+    }
+}
+
+/// The header carrying the signature of the webhook payloads, as `sha256=<hex HMAC-SHA256 of the body>`.
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "x-changelog-signature-256";
+
+/// The body of the requests of a `WebhookNotifier`.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WebhookPayload<'a> {
+    notification: &'a NotificationMetadata,
+    changelog: &'a Changelog
+}
+
+/// The `WebhookNotifier` struct posts changelogs to an endpoint as JSON, with the `NotificationMetadata` under
+/// `notification` and the changelog under `changelog`.
+///
+/// With a secret, the payloads are signed with HMAC-SHA256 in the `X-Changelog-Signature-256` header, as
+/// `sha256=<hex digest>`, so that the endpoint can check that they come from a holder of the secret.
+///
+/// # Example
+///
+/// ```rust
+/// use deployment_changelog::{api::{rest::RestClient, secret::SecretString}, notify::WebhookNotifier};
+///
+/// let client = RestClient::new("https://hooks.example.com/deployments").unwrap();
+/// let notifier = WebhookNotifier::new(client, Some(SecretString::from("shared-secret")));
+/// ```
+pub struct WebhookNotifier {
+    name: String,
+    client: RestClient,
+    secret: Option<SecretString>
+}
+
+impl WebhookNotifier {
+    /// Creates a new `WebhookNotifier`.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The `RestClient` whose base URL is the endpoint the changelogs are posted to.
+    /// * `secret` - The secret the payloads are signed with, if any.
+    ///
+    /// # Returns
+    ///
+    /// A new `WebhookNotifier` instance.
+    pub fn new(client: RestClient, secret: Option<SecretString>) -> Self {
+        // The URL can hold a token, so only its host names the notifier.
+        let name = format!("webhook {}", client.base_url.host_str().unwrap_or_default());
+
+        Self {
+            name,
+            client,
+            secret
+        }
+    }
+}
+
+impl Debug for WebhookNotifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebhookNotifier")
+            .field("client", &self.client)
+            .field("secret", &self.secret)
+            .finish()
+    }
+}
+
+#[async_trait::async_trait]
+impl Notifier for WebhookNotifier {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn notify(&self, changelog: &Changelog, metadata: &NotificationMetadata) -> Result<()> {
+        let body = serde_json::to_vec(&WebhookPayload {
+            notification: metadata,
+            changelog
+        })
+            .context("Error serializing the webhook payload")?;
+
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+
+        if let Some(secret) = &self.secret {
+            let signature = hex::encode(hmac_sha256(secret.expose_secret().as_bytes(), &body));
+            headers.insert(HeaderName::from_static(WEBHOOK_SIGNATURE_HEADER), HeaderValue::from_str(&format!("sha256={signature}"))?);
+        }
+
+        self.client.post_bytes("", body, headers).await
+            .with_context(|| format!("Error posting the changelog to the {}", self.name))
+    }
+}
+
+/// The `DesktopNotifier` struct shows a desktop notification summarizing a changelog, with its counts of pull
+/// requests and issues, its issues counted by the symbol of their status, such as `✅ 3  🚧 1`, and when its
+/// pending version was created and the current one deployed. It is only available with the
+/// `desktop-notifications` feature.
+#[cfg(feature = "desktop-notifications")]
+#[derive(Debug, Default)]
+pub struct DesktopNotifier {
+    pub render_options: RenderOptions
+}
+
+#[cfg(feature = "desktop-notifications")]
+#[async_trait::async_trait]
+impl Notifier for DesktopNotifier {
+    fn name(&self) -> &str {
+        "desktop"
+    }
+
+    async fn notify(&self, changelog: &Changelog, metadata: &NotificationMetadata) -> Result<()> {
+        let mut body = format!(
+            "{} pull requests and {} issues",
+            changelog.stats.pull_request_count,
+            changelog.stats.issue_count
+        );
+
+        let mut status_counts: Vec<(&str, usize)> = Vec::new();
+        changelog.issues.iter()
+            .filter_map(|issue| self.render_options.statuses.get(issue))
+            .for_each(|style| match status_counts.iter_mut().find(|(symbol, _)| *symbol == style.symbol) {
+                Some((_, count)) => *count += 1,
+                None => status_counts.push((&style.symbol, 1))
+            });
+
+        if !status_counts.is_empty() {
+            let status_counts: Vec<String> = status_counts.iter()
+                .map(|(symbol, count)| format!("{symbol} {count}"))
+                .collect();
+
+            body.push_str(&format!("\n{}", status_counts.join("  ")));
+        }
+
+        if let Some(pending_version) = &changelog.metadata.pending_version {
+            if let Some(pending_since) = &pending_version.pending_since {
+                body.push_str(&format!("\nPending since {}", self.render_options.format_date(pending_since)));
+            }
+
+            if let Some(last_deployed_at) = &pending_version.last_deployed_at {
+                body.push_str(&format!("\nLast deployed at {}", self.render_options.format_date(last_deployed_at)));
+            }
+        }
+
+        notify_rust::Notification::new()
+            .appname("deployment-changelog")
+            .summary(&metadata.title)
+            .body(&body)
+            .show()?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,180 @@
+//! The `progress` module reports the progress of changelog generation as machine-readable events, so that
+//! wrapper scripts and CI interfaces can show live progress without parsing the human-readable logs.
+//!
+//! Generating a changelog goes through a sequence of `ProgressPhase`s, such as fetching the commits of a
+//! range and then the pull requests of every commit. A `ProgressEvent` is reported whenever an item of the
+//! current phase is done, and whenever a request is made to a server.
+//!
+//! Like logging, progress reporting is global: nothing is reported until a reporter is installed with
+//! `set_reporter`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use deployment_changelog::progress::{set_reporter, JsonLinesReporter};
+//!
+//! set_reporter(Box::new(JsonLinesReporter)).unwrap();
+//!
+//! // Generating a changelog now prints lines such as
+//! // {"phase":"pullRequests","done":3,"total":12,"url":"https://bitbucket.example.com/..."}
+//! // on stderr.
+//! ```
+use std::{future::Future, io::Write, sync::{Mutex, OnceLock}};
+
+use anyhow::{anyhow, Result};
+use futures::{future, stream, FutureExt, StreamExt};
+use serde::Serialize;
+
+static REPORTER: OnceLock<Box<dyn ProgressReporter>> = OnceLock::new();
+static STATE: Mutex<Option<ProgressState>> = Mutex::new(None);
+
+/// The `ProgressPhase` enum lists the steps of changelog generation.
+///
+/// - `SpinnakerVersions`: Fetching the versions deployed to a Spinnaker environment.
+/// - `Commits`: Fetching the commits of a range, or the commits linked to Jira issues.
+/// - `PullRequests`: Fetching the pull requests of every commit, or the pull requests linked to Jira issues.
+/// - `PullRequestIssues`: Fetching the Jira issue keys referenced by every pull request.
+/// - `Issues`: Fetching the Jira issues, or searching for them with JQL.
+/// - `TrackedIssues`: Fetching the issues of other trackers, such as Trello cards, referenced by pull requests.
+/// - `ChangedFiles`: Fetching the files or lines changed in a range, to detect database migrations and feature flag changes.
+/// - `PullRequestComments`: Commenting on the pull requests of the changelog.
+/// - `Dependencies`: Fetching the pull requests and issues that the pull requests of the changelog depend on.
+/// - `PullRequestSizes`: Fetching the commits and changed files of the pull requests of the changelog.
+/// - `Worklogs`: Fetching the work logged on the issues of the changelog.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ProgressPhase {
+    SpinnakerVersions,
+    Commits,
+    PullRequests,
+    PullRequestIssues,
+    Issues,
+    TrackedIssues,
+    ChangedFiles,
+    PullRequestComments,
+    Dependencies,
+    PullRequestSizes,
+    Worklogs
+}
+
+/// The `ProgressEvent` struct is a progress update. It contains the following fields:
+///
+/// - `phase`: The current `ProgressPhase`.
+/// - `done`: The number of items of the phase that are done.
+/// - `total`: The number of items in the phase, when it is known upfront.
+/// - `url`: The URL of the request being made, for events reported when a request is made.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProgressEvent {
+    pub phase: ProgressPhase,
+    pub done: usize,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total: Option<usize>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>
+}
+
+/// The `ProgressReporter` trait receives the `ProgressEvent`s of changelog generation.
+pub trait ProgressReporter: Send + Sync {
+    /// Reports a progress event.
+    fn report(&self, event: &ProgressEvent);
+}
+
+/// The `JsonLinesReporter` reports progress events as JSON lines on stderr.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonLinesReporter;
+
+impl ProgressReporter for JsonLinesReporter {
+    fn report(&self, event: &ProgressEvent) {
+        match serde_json::to_string(event) {
+            Ok(json) => {
+                // Progress is best effort, a closed stderr shouldn't fail the changelog.
+                let _ = writeln!(std::io::stderr().lock(), "{json}");
+            },
+            Err(error) => panic!("Error serializing progress event: {error}")
+        }
+    }
+}
+
+/// Installs the global progress reporter. It can only be installed once.
+pub fn set_reporter(reporter: Box<dyn ProgressReporter>) -> Result<()> {
+    REPORTER.set(reporter)
+        .map_err(|_| anyhow!("A progress reporter is already installed"))
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ProgressState {
+    phase: ProgressPhase,
+    done: usize,
+    total: Option<usize>
+}
+
+/// Starts a new phase of `total` items, or an unknown number of items if `None`.
+pub(crate) fn start_phase(phase: ProgressPhase, total: Option<usize>) {
+    update(|_| Some(ProgressState { phase, done: 0, total }), None);
+}
+
+/// Reports that a request is being made to the given URL, as part of the current phase. Requests made before
+/// the first phase starts are not reported.
+pub(crate) fn request(url: &str) {
+    update(|state| state, Some(url));
+}
+
+/// Runs the futures of a phase concurrently, at most `limit` at a time, reporting every future that completes as
+/// an item done. A new future is started as soon as any running one completes, so a slow request only holds up
+/// its own slot. The outputs are returned in the order of the futures.
+pub(crate) async fn join_all<I>(phase: ProgressPhase, limit: usize, futures: I) -> Vec<<I::Item as Future>::Output>
+where
+    I: IntoIterator,
+    I::Item: Future
+{
+    let futures: Vec<I::Item> = futures.into_iter().collect();
+    start_phase(phase, Some(futures.len()));
+
+    let mut outputs: Vec<Option<<I::Item as Future>::Output>> = futures.iter()
+        .map(|_| None)
+        .collect();
+
+    stream::iter(futures.into_iter().enumerate())
+        .map(|(index, future)| future.map(move |output| (index, output)))
+        .buffer_unordered(limit.max(1))
+        .for_each(|(index, output)| {
+            update(|state| state.map(|state| ProgressState { done: state.done + 1, ..state }), None);
+            outputs[index] = Some(output);
+
+            future::ready(())
+        })
+        .await;
+
+    outputs.into_iter()
+        .map(|output| output.expect("Every future of the phase completed"))
+        .collect()
+}
+
+fn update(next_state: impl FnOnce(Option<ProgressState>) -> Option<ProgressState>, url: Option<&str>) {
+    let reporter = match REPORTER.get() {
+        Some(reporter) => reporter,
+        None => return
+    };
+
+    let mut state = match STATE.lock() {
+        Ok(state) => state,
+        Err(poisoned) => poisoned.into_inner()
+    };
+
+    *state = next_state(*state);
+
+    let next = match *state {
+        Some(next) => next,
+        None => return
+    };
+
+    reporter.report(&ProgressEvent {
+        phase: next.phase,
+        done: next.done,
+        total: next.total,
+        url: url.map(str::to_string)
+    });
+}
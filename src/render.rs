@@ -0,0 +1,1317 @@
+//! The `render` module turns a `Changelog` into the different output formats supported by the crate.
+//!
+//! Besides the default JSON representation, a changelog can be rendered as a human-readable Markdown or plain
+//! text document, or as a commit → pull request → issue relationship graph, either as a [Mermaid](https://mermaid.js.org/) flowchart or as a Graphviz DOT digraph.
+//! The graph is built from the changelog's provenance information, so no additional API calls are needed.
+//!
+//! Timestamps are stored in UTC in the changelog model and are only converted to the timezone selected in the
+//! `RenderOptions` when the changelog is rendered, so the same changelog renders identically on every machine.
+//! The text renderers show each date both as an absolute timestamp and relative to the time of rendering
+//! (e.g. `2023-04-12 09:30:00 +00:00 (3 days ago)`).
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use deployment_changelog::changelog::Changelog;
+//! use deployment_changelog::render::{render, OutputFormat, RenderOptions};
+//!
+//! // Suppose you generated a Changelog named 'changelog'
+//! let mermaid = render(&changelog, OutputFormat::Mermaid, &RenderOptions::default());
+//! println!("{}", mermaid);
+//! ```
+use std::{borrow::Cow, fmt::Write, collections::BTreeMap, str::FromStr};
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Duration, FixedOffset, Local, Offset, Utc, format::{Item, StrftimeItems}};
+use chrono_tz::Tz;
+use regex::Regex;
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+
+use crate::{api::{bitbucket::BitbucketPullRequest, jira::JiraIssue}, changelog::{Changelog, DeployDigest, EnvironmentComparison, Percentiles}};
+
+mod lint;
+
+pub use lint::{lint_release_notes, LintKind, LintWarning};
+
+#[cfg(feature = "pdf")]
+mod pdf;
+
+#[cfg(feature = "pdf")]
+pub use pdf::render_pdf;
+
+/// The ID of the node holding the deployment notes in the DOT and Mermaid graphs.
+const NOTES_NODE: &str = "deployment_notes";
+
+/// The `OutputFormat` enum lists the formats a `Changelog` can be rendered in.
+///
+/// - `Json`: The pretty-printed JSON representation, identical to the `Display` implementation of `Changelog`.
+/// - `Markdown`: A Markdown document listing the issues, pull requests and commits of the changelog.
+/// - `Text`: A plain text document listing the issues, pull requests and commits of the changelog.
+/// - `Dot`: A Graphviz DOT digraph of the commit → pull request → issue relationships.
+/// - `Mermaid`: A Mermaid flowchart of the commit → pull request → issue relationships.
+/// - `ReleaseNotes`: Markdown release notes listing the issues by type, along with the pull requests that implemented them.
+/// - `Pdf`: The release notes as a PDF document, available with the `pdf` feature. See `render_bytes`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use deployment_changelog::render::{render, OutputFormat, RenderOptions};
+///
+/// // Suppose you generated a Changelog named 'changelog'
+/// let dot = render(&changelog, OutputFormat::Dot, &RenderOptions::default());
+/// std::fs::write("changelog.dot", dot).unwrap();
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum OutputFormat {
+    Json,
+    Markdown,
+    Text,
+    Dot,
+    Mermaid,
+    ReleaseNotes,
+    #[cfg(feature = "pdf")]
+    Pdf
+}
+
+impl OutputFormat {
+    /// Returns the file extension of documents rendered in this format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            OutputFormat::Json => "json",
+            OutputFormat::Markdown | OutputFormat::ReleaseNotes => "md",
+            OutputFormat::Text => "txt",
+            OutputFormat::Dot => "dot",
+            OutputFormat::Mermaid => "mmd",
+            #[cfg(feature = "pdf")]
+            OutputFormat::Pdf => "pdf"
+        }
+    }
+
+    /// Returns whether documents rendered in this format are binary rather than text.
+    pub fn is_binary(&self) -> bool {
+        match self {
+            #[cfg(feature = "pdf")]
+            OutputFormat::Pdf => true,
+            _ => false
+        }
+    }
+
+    /// Returns the MIME type of documents rendered in this format.
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            OutputFormat::Json => "application/json",
+            OutputFormat::Markdown | OutputFormat::ReleaseNotes => "text/markdown",
+            OutputFormat::Text | OutputFormat::Mermaid => "text/plain",
+            OutputFormat::Dot => "text/vnd.graphviz",
+            #[cfg(feature = "pdf")]
+            OutputFormat::Pdf => "application/pdf"
+        }
+    }
+}
+
+/// The `DisplayTimezone` enum represents the timezone timestamps are displayed in when rendering a `Changelog`.
+///
+/// - `Utc`: Coordinated Universal Time, the default.
+/// - `Local`: The timezone of the machine rendering the changelog.
+/// - `Named`: An IANA timezone, such as `Europe/Paris`.
+///
+/// It can be parsed from `UTC`, `local` or an IANA timezone name, case-insensitively for the first two.
+///
+/// # Example
+///
+/// ```rust
+/// use deployment_changelog::render::DisplayTimezone;
+///
+/// let timezone: DisplayTimezone = "America/New_York".parse().unwrap();
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DisplayTimezone {
+    #[default]
+    Utc,
+    Local,
+    Named(Tz)
+}
+
+impl DisplayTimezone {
+    /// Converts a UTC timestamp to this timezone.
+    pub fn convert(&self, timestamp: &DateTime<Utc>) -> DateTime<FixedOffset> {
+        match self {
+            DisplayTimezone::Utc => (*timestamp).into(),
+            DisplayTimezone::Local => timestamp.with_timezone(&Local).into(),
+            DisplayTimezone::Named(timezone) => {
+                let timestamp = timestamp.with_timezone(timezone);
+                timestamp.with_timezone(&timestamp.offset().fix())
+            }
+        }
+    }
+}
+
+impl FromStr for DisplayTimezone {
+    type Err = anyhow::Error;
+
+    fn from_str(timezone: &str) -> Result<Self> {
+        match timezone.to_lowercase().as_str() {
+            "utc" => Ok(DisplayTimezone::Utc),
+            "local" => Ok(DisplayTimezone::Local),
+            _ => timezone.parse::<Tz>()
+                .map(DisplayTimezone::Named)
+                .map_err(|error| anyhow!("Invalid timezone {timezone}, expected UTC, local or an IANA timezone name: {error}"))
+        }
+    }
+}
+
+/// The `DateFormat` struct holds a validated [strftime](https://docs.rs/chrono/latest/chrono/format/strftime/index.html)
+/// pattern used to display absolute timestamps in the text renderers.
+///
+/// The default pattern is `%Y-%m-%d %H:%M:%S %:z`.
+///
+/// # Example
+///
+/// ```rust
+/// use deployment_changelog::render::DateFormat;
+///
+/// let date_format: DateFormat = "%d %b %Y".parse().unwrap();
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DateFormat(String);
+
+impl Default for DateFormat {
+    fn default() -> Self {
+        DateFormat(String::from("%Y-%m-%d %H:%M:%S %:z"))
+    }
+}
+
+impl FromStr for DateFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(date_format: &str) -> Result<Self> {
+        match StrftimeItems::new(date_format).any(|item| item == Item::Error) {
+            true => Err(anyhow!("Invalid date format {date_format}, expected a strftime pattern such as %Y-%m-%d")),
+            false => Ok(DateFormat(date_format.to_string()))
+        }
+    }
+}
+
+/// The `RenderOptions` struct holds the settings that control how a `Changelog` is rendered, independently
+/// of the output format.
+///
+/// - `timezone`: The timezone timestamps are displayed in.
+/// - `date_format`: The pattern absolute timestamps are displayed with in the text renderers.
+/// - `title`: The title of the Markdown documents, which defaults to `Changelog` or `Release notes`.
+/// - `rewrite_rules`: The `RewriteRule`s applied to issue summaries and pull request titles by the Markdown,
+///   text and release notes renderers.
+/// - `statuses`: The `StatusStyles` issues are marked with by their status in the Markdown, text and graph
+///   renderers, and in desktop notifications.
+#[derive(Clone, Debug, Default)]
+pub struct RenderOptions {
+    pub timezone: DisplayTimezone,
+    pub date_format: DateFormat,
+    pub title: Option<String>,
+    pub rewrite_rules: Vec<RewriteRule>,
+    pub statuses: StatusStyles
+}
+
+/// The `RewriteRule` struct is a regular expression replacement applied to issue summaries and pull request
+/// titles when they are rendered, such as stripping a `[BACKEND]` prefix or expanding an abbreviation, so that
+/// published notes read cleanly. The `replacement` can refer to the capture groups of the `pattern` with `$1`
+/// or `${name}`, and defaults to removing the matches.
+///
+/// # Example
+///
+/// ```rust
+/// use deployment_changelog::render::RewriteRule;
+/// use regex::Regex;
+///
+/// let rule = RewriteRule {
+///     pattern: Regex::new(r"^\[[A-Z]+\]\s*").unwrap(),
+///     replacement: String::new()
+/// };
+///
+/// assert_eq!(rule.apply("[BACKEND] Fix the login page"), "Fix the login page");
+/// ```
+#[derive(Clone, Debug, Deserialize)]
+pub struct RewriteRule {
+    #[serde(deserialize_with = "deserialize_regex")]
+    pub pattern: Regex,
+
+    #[serde(default)]
+    pub replacement: String
+}
+
+impl RewriteRule {
+    /// Replaces every match of the rule's pattern in the given text.
+    pub fn apply<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        self.pattern.replace_all(text, self.replacement.as_str())
+    }
+}
+
+fn deserialize_regex<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Regex, D::Error> {
+    let pattern = String::deserialize(deserializer)?;
+
+    Regex::new(&pattern)
+        .map_err(|error| serde::de::Error::custom(format!("Invalid rewrite pattern {pattern}: {error}")))
+}
+
+/// The `StatusStyle` struct is how an issue status is displayed: the `symbol` issues are prefixed with, such as
+/// `✅`, and the `color` of their node in the DOT and Mermaid graphs, a color name or `#rrggbb` hex code.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct StatusStyle {
+    pub symbol: String,
+    pub color: Option<String>
+}
+
+impl StatusStyle {
+    fn new(symbol: &str, color: &str) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            color: Some(color.to_string())
+        }
+    }
+}
+
+/// The `StatusStyles` struct maps Jira statuses to their `StatusStyle`, making the deploy readiness of a changelog
+/// visible at a glance. An issue is styled after its status name, such as `Blocked`, or else the name or key of
+/// its status category, such as `In Progress` or `indeterminate`. The names are compared case-insensitively.
+///
+/// By default, the `done` category is marked with ✅, the `indeterminate` one with 🚧, the `new` one with ⬜, and
+/// the `Blocked` status with ❗. The styles of a configuration file are added to the default ones, replacing
+/// those of the same name.
+///
+/// # Example
+///
+/// ```rust
+/// use deployment_changelog::render::{StatusStyle, StatusStyles};
+///
+/// let mut statuses = StatusStyles::default();
+/// statuses.insert("In Review", StatusStyle { symbol: String::from("👀"), color: Some(String::from("orange")) });
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StatusStyles(BTreeMap<String, StatusStyle>);
+
+impl Default for StatusStyles {
+    fn default() -> Self {
+        let mut statuses = StatusStyles(BTreeMap::new());
+        statuses.insert("done", StatusStyle::new("✅", "palegreen"));
+        statuses.insert("indeterminate", StatusStyle::new("🚧", "khaki"));
+        statuses.insert("new", StatusStyle::new("⬜", "lightgray"));
+        statuses.insert("blocked", StatusStyle::new("❗", "lightcoral"));
+
+        statuses
+    }
+}
+
+impl StatusStyles {
+    /// Sets the style of a status name, or of a status category name or key.
+    pub fn insert(&mut self, status: &str, style: StatusStyle) {
+        self.0.insert(status.to_lowercase(), style);
+    }
+
+    /// Returns the style of an issue, after its status name or else its status category, if any.
+    pub fn get(&self, issue: &JiraIssue) -> Option<&StatusStyle> {
+        let status = issue.fields.status.as_ref()?;
+        let category = status.status_category.as_ref();
+
+        [Some(&status.name), category.map(|category| &category.name), category.map(|category| &category.key)].into_iter()
+            .flatten()
+            .find_map(|name| self.0.get(&name.to_lowercase()))
+    }
+
+    /// Returns the symbol of an issue followed by a space, or an empty string if its status has no style.
+    fn prefix(&self, issue: &JiraIssue) -> String {
+        self.get(issue)
+            .map(|style| format!("{} ", style.symbol))
+            .unwrap_or_default()
+    }
+}
+
+impl<'de> Deserialize<'de> for StatusStyles {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let mut statuses = StatusStyles::default();
+
+        BTreeMap::<String, StatusStyle>::deserialize(deserializer)?
+            .into_iter()
+            .for_each(|(status, style)| statuses.insert(&status, style));
+
+        Ok(statuses)
+    }
+}
+
+impl RenderOptions {
+    /// Formats a timestamp for the text renderers, as an absolute timestamp followed by the time elapsed
+    /// since then, e.g. `2023-04-12 09:30:00 +00:00 (3 days ago)`.
+    pub fn format_date(&self, timestamp: &DateTime<Utc>) -> String {
+        let absolute = self.timezone.convert(timestamp)
+            .format(&self.date_format.0);
+
+        format!("{absolute} ({})", relative_date(timestamp, &Utc::now()))
+    }
+
+    /// Applies the rewrite rules to an issue summary or a pull request title, in order.
+    pub fn rewrite(&self, text: &str) -> String {
+        self.rewrite_rules.iter()
+            .fold(text.to_string(), |text, rule| rule.apply(&text).into_owned())
+    }
+}
+
+/// Renders the given `Changelog` in the specified `OutputFormat`.
+///
+/// # Arguments
+///
+/// * `changelog` - The changelog to render.
+/// * `format` - The format to render the changelog in.
+/// * `options` - The options controlling how the changelog is rendered.
+///
+/// # Returns
+///
+/// A `String` containing the rendered changelog. Since a PDF document is binary, the `Pdf` format returns the
+/// Markdown release notes it is laid out from; use `render_bytes` to get the PDF document itself.
+pub fn render(changelog: &Changelog, format: OutputFormat, options: &RenderOptions) -> String {
+    match format {
+        OutputFormat::Json => render_json(changelog, options),
+        OutputFormat::Markdown => render_markdown(changelog, options),
+        OutputFormat::Text => render_text(changelog, options),
+        OutputFormat::Dot => render_dot(changelog, options),
+        OutputFormat::Mermaid => render_mermaid(changelog, options),
+        OutputFormat::ReleaseNotes => render_release_notes(changelog, options),
+        #[cfg(feature = "pdf")]
+        OutputFormat::Pdf => render_release_notes(changelog, options)
+    }
+}
+
+/// Renders the given `Changelog` in the specified `OutputFormat` as the bytes of a document, such as a file to write
+/// or attach.
+///
+/// This is the same as `render` for the text formats, and returns the PDF document for the `Pdf` format.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use deployment_changelog::render::{render_bytes, OutputFormat, RenderOptions};
+///
+/// // Suppose you generated a Changelog named 'changelog'
+/// let format = OutputFormat::ReleaseNotes;
+/// std::fs::write(format!("release-notes.{}", format.extension()), render_bytes(&changelog, format, &RenderOptions::default())).unwrap();
+/// ```
+pub fn render_bytes(changelog: &Changelog, format: OutputFormat, options: &RenderOptions) -> Vec<u8> {
+    match format {
+        #[cfg(feature = "pdf")]
+        OutputFormat::Pdf => render_pdf(changelog, options),
+        _ => render(changelog, format, options).into_bytes()
+    }
+}
+
+/// Renders an `EnvironmentComparison` in the given `OutputFormat`.
+///
+/// JSON renders the whole comparison as one document. The other formats render the changes found only in
+/// each environment one after the other, titled after the environment they are deployed to.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use deployment_changelog::render::{render_comparison, OutputFormat, RenderOptions};
+///
+/// // Suppose you compared two environments into an EnvironmentComparison named 'comparison'
+/// println!("{}", render_comparison(&comparison, OutputFormat::Markdown, &RenderOptions::default()));
+/// ```
+pub fn render_comparison(comparison: &EnvironmentComparison, format: OutputFormat, options: &RenderOptions) -> String {
+    if let OutputFormat::Json = format {
+        let mut json = match serde_json::to_value(comparison) {
+            Ok(json) => json,
+            Err(error) => panic!("Error serializing environment comparison: {error}")
+        };
+
+        convert_timestamps(&mut json, &options.timezone);
+
+        return match serde_json::to_string_pretty(&json) {
+            Ok(json) => json,
+            Err(error) => panic!("Error serializing environment comparison: {error}")
+        };
+    }
+
+    [
+        (&comparison.only_in_a, &comparison.env_a, &comparison.env_b),
+        (&comparison.only_in_b, &comparison.env_b, &comparison.env_a)
+    ]
+        .into_iter()
+        .map(|(changelog, env, other_env)| {
+            let title = format!("{} changes in {env} but not in {other_env}", comparison.app_name);
+
+            match format {
+                OutputFormat::Text => format!("{title}\n\n{}", render_text(changelog, options)),
+                _ => render(changelog, format, &RenderOptions { title: Some(title), ..options.clone() })
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Renders a `DeployDigest` in the given `OutputFormat`.
+///
+/// JSON renders the whole digest as one document. The other formats render the changelog of every deployed service
+/// one after the other, team by team, titled after the team, the service and the versions deployed.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use deployment_changelog::render::{render_digest, OutputFormat, RenderOptions};
+///
+/// // Suppose you got the deployments of the last day into a DeployDigest named 'digest'
+/// println!("{}", render_digest(&digest, OutputFormat::Markdown, &RenderOptions::default()));
+/// ```
+pub fn render_digest(digest: &DeployDigest, format: OutputFormat, options: &RenderOptions) -> String {
+    if let OutputFormat::Json = format {
+        let mut json = match serde_json::to_value(digest) {
+            Ok(json) => json,
+            Err(error) => panic!("Error serializing deploy digest: {error}")
+        };
+
+        convert_timestamps(&mut json, &options.timezone);
+
+        return match serde_json::to_string_pretty(&json) {
+            Ok(json) => json,
+            Err(error) => panic!("Error serializing deploy digest: {error}")
+        };
+    }
+
+    if digest.teams.is_empty() {
+        return format!("Nothing was deployed to {} since {}", digest.env, options.format_date(&digest.since));
+    }
+
+    digest.teams.iter()
+        .flat_map(|team| team.deployments.iter()
+            .map(move |deployment| (team.team.as_deref().unwrap_or("No team"), deployment)))
+        .map(|(team, deployment)| {
+            let title = format!(
+                "{team}: {} {} deployed to {} {}",
+                deployment.service,
+                deployment.versions.join(", "),
+                digest.env,
+                options.format_date(&deployment.deployed_at)
+            );
+
+            match format {
+                OutputFormat::Text => format!("{title}\n\n{}", render_text(&deployment.changelog, options)),
+                _ => render(&deployment.changelog, format, &RenderOptions { title: Some(title), ..options.clone() })
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Renders a `Changelog` as pretty-printed JSON, with its timestamps converted to the timezone of the `RenderOptions`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use deployment_changelog::render::{render_json, RenderOptions, DisplayTimezone};
+///
+/// // Suppose you generated a Changelog named 'changelog'
+/// let options = RenderOptions { timezone: DisplayTimezone::Local };
+/// println!("{}", render_json(&changelog, &options));
+/// ```
+pub fn render_json(changelog: &Changelog, options: &RenderOptions) -> String {
+    let mut json = match serde_json::to_value(changelog) {
+        Ok(json) => json,
+        Err(error) => panic!("Error serializing changelog: {error}")
+    };
+
+    convert_timestamps(&mut json, &options.timezone);
+
+    match serde_json::to_string_pretty(&json) {
+        Ok(json) => json,
+        Err(error) => panic!("Error serializing changelog: {error}")
+    }
+}
+
+/// Renders a `Changelog` as a Markdown document, with a section for each of its issues, pull requests,
+/// linked work items and commits.
+///
+/// Pull requests are listed with their release note instead of their title when they have one.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use deployment_changelog::render::{render_markdown, RenderOptions};
+///
+/// // Suppose you generated a Changelog named 'changelog'
+/// std::fs::write("CHANGELOG.md", render_markdown(&changelog, &RenderOptions::default())).unwrap();
+/// ```
+pub fn render_markdown(changelog: &Changelog, options: &RenderOptions) -> String {
+    let mut markdown = format!("# {}\n\n", options.title.as_deref().unwrap_or("Changelog"));
+
+    let _ = writeln!(
+        markdown,
+        "Generated {} by deployment-changelog {}.",
+        options.format_date(&changelog.metadata.generated_at),
+        changelog.metadata.tool_version
+    );
+
+    markdown.push_str("\n## Summary\n\n");
+
+    stats_lines(changelog, options).iter()
+        .for_each(|line| {
+            let _ = writeln!(markdown, "- {}", escape_markdown(line));
+        });
+
+    if !changelog.notes.is_empty() {
+        markdown.push_str("\n## Deployment notes\n\n");
+
+        changelog.notes.iter()
+            .for_each(|note| {
+                let _ = writeln!(markdown, "- {}", escape_markdown(note));
+            });
+    }
+
+    if let Some(pending_version) = changelog.metadata.pending_version.as_ref()
+        .filter(|pending_version| !pending_version.constraints.is_empty() || !pending_version.verifications.is_empty())
+    {
+        let _ = write!(markdown, "\n## Pending version {}\n\n", escape_markdown(&pending_version.version));
+
+        pending_version.constraints.iter()
+            .for_each(|constraint| {
+                let judgment = match (&constraint.judged_by, &constraint.judged_at) {
+                    (Some(judged_by), Some(judged_at)) => format!(" (judged by {} {})", escape_markdown(judged_by), options.format_date(judged_at)),
+                    (Some(judged_by), None) => format!(" (judged by {})", escape_markdown(judged_by)),
+                    _ => String::new()
+                };
+
+                let _ = writeln!(markdown, "- Constraint **{}**: {}{judgment}", escape_markdown(&constraint.constraint_type), constraint.status);
+            });
+
+        pending_version.verifications.iter()
+            .for_each(|verification| {
+                let link = verification.link.as_ref()
+                    .map(|link| format!(" ([results]({link}))"))
+                    .unwrap_or_default();
+
+                let _ = writeln!(markdown, "- Verification **{}**: {}{link}", escape_markdown(&verification.id), verification.status);
+            });
+    }
+
+    if !changelog.migrations.is_empty() {
+        markdown.push_str("\n## Database migrations\n\n");
+
+        changelog.migrations.iter()
+            .for_each(|migration| {
+                let _ = writeln!(markdown, "- `{migration}`");
+            });
+    }
+
+    if changelog.unresolved_dependencies().next().is_some() {
+        markdown.push_str("\n## Unresolved dependencies\n\n");
+
+        changelog.unresolved_dependencies()
+            .for_each(|dependency| {
+                let _ = writeln!(markdown, "- #{} depends on **{}** ({})", dependency.pull_request_id, dependency.reference, dependency.status);
+            });
+    }
+
+    if !changelog.feature_flags.is_empty() || !changelog.feature_flag_files.is_empty() {
+        markdown.push_str("\n## Feature flags\n\n");
+
+        changelog.feature_flags.iter()
+            .for_each(|feature_flag| {
+                let paths: Vec<String> = feature_flag.paths.iter()
+                    .map(|path| format!("`{path}`"))
+                    .collect();
+
+                let _ = writeln!(markdown, "- **{}** in {}", escape_markdown(&feature_flag.key), paths.join(", "));
+            });
+
+        changelog.feature_flag_files.iter()
+            .for_each(|feature_flag_file| {
+                let _ = writeln!(markdown, "- `{feature_flag_file}` (flag configuration)");
+            });
+    }
+
+    if !changelog.segments.is_empty() {
+        markdown.push_str("\n## Versions\n\n");
+
+        changelog.segments.iter()
+            .for_each(|segment| {
+                let pull_requests: Vec<String> = segment.pull_requests.iter()
+                    .map(|pull_request_id| format!("#{pull_request_id}"))
+                    .collect();
+
+                let _ = writeln!(
+                    markdown,
+                    "- **{}** (`{}`): {} commits, pull requests: {}, issues: {}",
+                    escape_markdown(&segment.version),
+                    segment.commit.chars().take(11).collect::<String>(),
+                    segment.commits.len(),
+                    if pull_requests.is_empty() { String::from("none") } else { pull_requests.join(", ") },
+                    if segment.issues.is_empty() { String::from("none") } else { segment.issues.join(", ") }
+                );
+            });
+    }
+
+    if !changelog.issues.is_empty() {
+        markdown.push_str("\n## Issues\n\n");
+
+        changelog.issues.iter()
+            .for_each(|issue| {
+                let _ = writeln!(
+                    markdown,
+                    "- {}**{}** {} (updated {}{})",
+                    options.statuses.prefix(issue),
+                    issue.key,
+                    escape_markdown(&options.rewrite(&issue.fields.summary)),
+                    options.format_date(&issue.fields.updated),
+                    cycle_time_suffix(issue)
+                );
+            });
+    }
+
+    if !changelog.pull_requests.is_empty() {
+        markdown.push_str("\n## Pull requests\n\n");
+
+        changelog.pull_requests.iter()
+            .for_each(|pull_request| {
+                let _ = writeln!(
+                    markdown,
+                    "- **#{}** {}{} (updated {})",
+                    pull_request.id,
+                    escape_markdown(&options.rewrite(pull_request_text(pull_request))),
+                    escape_markdown(&author_suffix(pull_request)),
+                    options.format_date(&pull_request.updated_date)
+                );
+            });
+    }
+
+    if !changelog.tracked_issues.is_empty() {
+        markdown.push_str("\n## Linked work items\n\n");
+
+        changelog.tracked_issues.iter()
+            .for_each(|tracked_issue| {
+                let _ = writeln!(
+                    markdown,
+                    "- [{}]({}) ({}{})",
+                    escape_markdown(&tracked_issue.title),
+                    tracked_issue.url,
+                    tracked_issue.tracker,
+                    tracked_issue.status.as_ref().map(|status| format!(", {status}")).unwrap_or_default()
+                );
+            });
+    }
+
+    if !changelog.commits.is_empty() {
+        markdown.push_str("\n## Commits\n\n");
+
+        changelog.commits.iter()
+            .for_each(|commit| {
+                let _ = writeln!(
+                    markdown,
+                    "- `{}` {} ({})",
+                    commit.display_id,
+                    escape_markdown(first_line(&commit.message)),
+                    escape_markdown(&commit.author.display_name)
+                );
+            });
+    }
+
+    markdown
+}
+
+/// Renders a `Changelog` as a plain text document, with a section for each of its issues, pull requests,
+/// linked work items and commits.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use deployment_changelog::render::{render_text, RenderOptions};
+///
+/// // Suppose you generated a Changelog named 'changelog'
+/// println!("{}", render_text(&changelog, &RenderOptions::default()));
+/// ```
+pub fn render_text(changelog: &Changelog, options: &RenderOptions) -> String {
+    let mut text = String::new();
+
+    let _ = writeln!(
+        text,
+        "Changelog generated {} by deployment-changelog {}",
+        options.format_date(&changelog.metadata.generated_at),
+        changelog.metadata.tool_version
+    );
+
+    text.push_str("\nSummary:\n");
+
+    stats_lines(changelog, options).iter()
+        .for_each(|line| {
+            let _ = writeln!(text, "  {line}");
+        });
+
+    if !changelog.notes.is_empty() {
+        text.push_str("\nDeployment notes:\n");
+
+        changelog.notes.iter()
+            .for_each(|note| {
+                let _ = writeln!(text, "  - {note}");
+            });
+    }
+
+    if !changelog.migrations.is_empty() {
+        text.push_str("\nDatabase migrations:\n");
+
+        changelog.migrations.iter()
+            .for_each(|migration| {
+                let _ = writeln!(text, "  - {migration}");
+            });
+    }
+
+    if changelog.unresolved_dependencies().next().is_some() {
+        text.push_str("\nUnresolved dependencies:\n");
+
+        changelog.unresolved_dependencies()
+            .for_each(|dependency| {
+                let _ = writeln!(text, "  #{} depends on {} ({})", dependency.pull_request_id, dependency.reference, dependency.status);
+            });
+    }
+
+    if !changelog.feature_flags.is_empty() || !changelog.feature_flag_files.is_empty() {
+        text.push_str("\nFeature flags:\n");
+
+        changelog.feature_flags.iter()
+            .for_each(|feature_flag| {
+                let _ = writeln!(text, "  {} in {}", feature_flag.key, feature_flag.paths.join(", "));
+            });
+
+        changelog.feature_flag_files.iter()
+            .for_each(|feature_flag_file| {
+                let _ = writeln!(text, "  {feature_flag_file} (flag configuration)");
+            });
+    }
+
+    if !changelog.issues.is_empty() {
+        text.push_str("\nIssues:\n");
+
+        changelog.issues.iter()
+            .for_each(|issue| {
+                let _ = writeln!(text, "  {}{} {}", options.statuses.prefix(issue), issue.key, options.rewrite(&issue.fields.summary));
+                let _ = writeln!(text, "      updated {}{}", options.format_date(&issue.fields.updated), cycle_time_suffix(issue));
+            });
+    }
+
+    if !changelog.pull_requests.is_empty() {
+        text.push_str("\nPull requests:\n");
+
+        changelog.pull_requests.iter()
+            .for_each(|pull_request| {
+                let author = match pull_request.author.user.display_name.as_str() {
+                    "" => String::new(),
+                    display_name => format!("by {display_name}, ")
+                };
+
+                let _ = writeln!(text, "  #{} {}", pull_request.id, options.rewrite(pull_request_text(pull_request)));
+                let _ = writeln!(text, "      {author}updated {}", options.format_date(&pull_request.updated_date));
+            });
+    }
+
+    if !changelog.tracked_issues.is_empty() {
+        text.push_str("\nLinked work items:\n");
+
+        changelog.tracked_issues.iter()
+            .for_each(|tracked_issue| {
+                let _ = writeln!(text, "  {} ({})", tracked_issue.title, tracked_issue.url);
+            });
+    }
+
+    if !changelog.commits.is_empty() {
+        text.push_str("\nCommits:\n");
+
+        changelog.commits.iter()
+            .for_each(|commit| {
+                let _ = writeln!(text, "  {} {} ({})", commit.display_id, first_line(&commit.message), commit.author.display_name);
+            });
+    }
+
+    text
+}
+
+/// Renders a `Changelog` as Markdown release notes, meant for readers outside of the development team.
+///
+/// The issues are grouped by issue type, and each of them is listed with the pull requests that implemented
+/// it. Commits are left out. The pull requests with a release note, written between `<!-- release-notes -->`
+/// markers of their descriptions, are listed with it after the issues.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use deployment_changelog::render::{render_release_notes, RenderOptions};
+///
+/// // Suppose you generated a Changelog named 'changelog' from the issues of a fix version
+/// let options = RenderOptions {
+///     title: Some(String::from("Release notes 2024.06")),
+///     ..RenderOptions::default()
+/// };
+///
+/// println!("{}", render_release_notes(&changelog, &options));
+/// ```
+pub fn render_release_notes(changelog: &Changelog, options: &RenderOptions) -> String {
+    let mut release_notes = format!("# {}\n\n", options.title.as_deref().unwrap_or("Release notes"));
+
+    let _ = writeln!(
+        release_notes,
+        "{} issues, {} pull requests. Generated {}.",
+        changelog.stats.issue_count,
+        changelog.stats.pull_request_count,
+        options.format_date(&changelog.metadata.generated_at)
+    );
+
+    if let Some(migrations_line) = migrations_line(changelog) {
+        let _ = writeln!(release_notes, "\n**{migrations_line}**");
+    }
+
+    if !changelog.notes.is_empty() {
+        release_notes.push_str("\n## Deployment notes\n\n");
+
+        changelog.notes.iter()
+            .for_each(|note| {
+                let _ = writeln!(release_notes, "- {}", escape_markdown(note));
+            });
+    }
+
+    let mut issues_by_type: BTreeMap<&str, Vec<&JiraIssue>> = BTreeMap::new();
+
+    changelog.issues.iter()
+        .for_each(|issue| {
+            let issue_type = issue.fields.issue_type.as_ref()
+                .map(|issue_type| issue_type.name.as_str())
+                .unwrap_or("Other");
+
+            issues_by_type.entry(issue_type)
+                .or_default()
+                .push(issue);
+        });
+
+    issues_by_type.iter_mut()
+        .for_each(|(issue_type, issues)| {
+            issues.sort_by(|a, b| a.key.cmp(&b.key));
+
+            let _ = writeln!(release_notes, "\n## {}\n", escape_markdown(issue_type));
+
+            issues.iter()
+                .for_each(|issue| {
+                    let pull_requests = changelog.provenance.issue_pull_requests.get(&issue.key)
+                        .map(|pull_request_entry_ids| {
+                            let pull_request_ids: Vec<String> = pull_request_entry_ids.iter()
+                                .filter_map(|pull_request_entry_id| pull_request_by_entry_id(changelog, pull_request_entry_id))
+                                .map(|pull_request| format!("#{}", pull_request.id))
+                                .collect();
+
+                            format!(" ({})", pull_request_ids.join(", "))
+                        })
+                        .unwrap_or_default();
+
+                    let _ = writeln!(
+                        release_notes,
+                        "- **{}** {}{}",
+                        issue.key,
+                        escape_markdown(&options.rewrite(&issue.fields.summary)),
+                        pull_requests
+                    );
+                });
+        });
+
+    let pull_request_release_notes: Vec<(u64, &str)> = changelog.pull_requests.iter()
+        .filter_map(|pull_request| pull_request.release_note.as_deref()
+            .map(|release_note| (pull_request.id, release_note)))
+        .collect();
+
+    if !pull_request_release_notes.is_empty() {
+        release_notes.push_str("\n## Pull requests\n\n");
+
+        pull_request_release_notes.iter()
+            .for_each(|(pull_request_id, release_note)| {
+                let _ = writeln!(
+                    release_notes,
+                    "- {} (#{pull_request_id})",
+                    escape_markdown(&options.rewrite(release_note))
+                );
+            });
+    }
+
+    release_notes
+}
+
+/// Renders the commit → pull request → issue relationship graph of a `Changelog` as a Graphviz DOT digraph.
+///
+/// The graph is titled with the commit, pull request and issue counts of the changelog.
+/// Commits are drawn as boxes, pull requests as ellipses and issues as notes, with edges pointing from each
+/// commit to its pull requests and from each pull request to the issues it references. Issues are filled with the
+/// color of their status, if any. Deployment notes, if any, are drawn as a separate highlighted note.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use deployment_changelog::render::{render_dot, RenderOptions};
+///
+/// // Suppose you generated a Changelog named 'changelog'
+/// println!("{}", render_dot(&changelog, &RenderOptions::default()));
+/// ```
+pub fn render_dot(changelog: &Changelog, options: &RenderOptions) -> String {
+    let mut dot = String::from("digraph changelog {\n    rankdir=LR;\n");
+    let _ = writeln!(dot, "    label=\"{}\";\n    labelloc=t;", escape_dot(&graph_title(changelog)));
+
+    changelog.commits.iter()
+        .for_each(|commit| {
+            let label = format!("{}\n{}", commit.display_id, first_line(&commit.message));
+            let _ = writeln!(dot, "    \"{}\" [label=\"{}\", shape=box];", commit_node(&commit.id), escape_dot(&label));
+        });
+
+    changelog.pull_requests.iter()
+        .for_each(|pull_request| {
+            let label = format!("PR #{}\n{}", pull_request.id, pull_request.title);
+            let _ = writeln!(dot, "    \"{}\" [label=\"{}\", shape=ellipse];", pull_request_node(pull_request), escape_dot(&label));
+        });
+
+    changelog.issues.iter()
+        .for_each(|issue| {
+            let label = format!("{}{}\n{}", options.statuses.prefix(issue), issue.key, issue.fields.summary);
+            let fill = status_color(options, issue)
+                .map(|color| format!(", style=filled, fillcolor=\"{color}\""))
+                .unwrap_or_default();
+
+            let _ = writeln!(dot, "    \"{}\" [label=\"{}\", shape=note{fill}];", issue_node(&issue.key), escape_dot(&label));
+        });
+
+    if !changelog.notes.is_empty() {
+        let _ = writeln!(dot, "    \"{NOTES_NODE}\" [label=\"{}\", shape=note, style=filled, fillcolor=lightyellow];", escape_dot(&notes_label(changelog)));
+    }
+
+    graph_edges(changelog).iter()
+        .for_each(|(from, to)| {
+            let _ = writeln!(dot, "    \"{from}\" -> \"{to}\";");
+        });
+
+    dot.push_str("}\n");
+    dot
+}
+
+/// Renders the commit → pull request → issue relationship graph of a `Changelog` as a Mermaid flowchart.
+///
+/// The flowchart is titled with the commit, pull request and issue counts of the changelog.
+/// The output can be embedded directly into Markdown or Confluence pages that support Mermaid diagrams. Issues are
+/// filled with the color of their status, if any.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use deployment_changelog::render::{render_mermaid, RenderOptions};
+///
+/// // Suppose you generated a Changelog named 'changelog'
+/// println!("{}", render_mermaid(&changelog, &RenderOptions::default()));
+/// ```
+pub fn render_mermaid(changelog: &Changelog, options: &RenderOptions) -> String {
+    let mut mermaid = format!("---\ntitle: {}\n---\ngraph LR\n", escape_mermaid(&graph_title(changelog)));
+
+    changelog.commits.iter()
+        .for_each(|commit| {
+            let label = format!("{}: {}", commit.display_id, first_line(&commit.message));
+            let _ = writeln!(mermaid, "    {}[\"{}\"]", commit_node(&commit.id), escape_mermaid(&label));
+        });
+
+    changelog.pull_requests.iter()
+        .for_each(|pull_request| {
+            let label = format!("PR #{}: {}", pull_request.id, pull_request.title);
+            let _ = writeln!(mermaid, "    {}([\"{}\"])", pull_request_node(pull_request), escape_mermaid(&label));
+        });
+
+    changelog.issues.iter()
+        .for_each(|issue| {
+            let label = format!("{}{}: {}", options.statuses.prefix(issue), issue.key, issue.fields.summary);
+            let _ = writeln!(mermaid, "    {}{{{{\"{}\"}}}}", issue_node(&issue.key), escape_mermaid(&label));
+
+            if let Some(color) = status_color(options, issue) {
+                let _ = writeln!(mermaid, "    style {} fill:{color}", issue_node(&issue.key));
+            }
+        });
+
+    if !changelog.notes.is_empty() {
+        let _ = writeln!(mermaid, "    {NOTES_NODE}>\"{}\"]", escape_mermaid(&notes_label(changelog)));
+    }
+
+    graph_edges(changelog).iter()
+        .for_each(|(from, to)| {
+            let _ = writeln!(mermaid, "    {from} --> {to}");
+        });
+
+    mermaid
+}
+
+/// Returns the color of the status of an issue in the graphs, keeping only the characters of color names and hex
+/// codes so that it can't break out of the attribute.
+fn status_color(options: &RenderOptions, issue: &JiraIssue) -> Option<String> {
+    let color: String = options.statuses.get(issue)?
+        .color.as_ref()?
+        .chars()
+        .filter(|character| character.is_ascii_alphanumeric() || *character == '#')
+        .collect();
+
+    (!color.is_empty()).then_some(color)
+}
+
+fn convert_timestamps(json: &mut Value, timezone: &DisplayTimezone) {
+    match json {
+        Value::String(string) => {
+            if let Ok(timestamp) = DateTime::parse_from_rfc3339(string) {
+                *string = timezone.convert(&timestamp.with_timezone(&Utc)).to_rfc3339();
+            }
+        },
+        Value::Array(values) => values.iter_mut()
+            .for_each(|value| convert_timestamps(value, timezone)),
+        Value::Object(map) => map.values_mut()
+            .for_each(|value| convert_timestamps(value, timezone)),
+        _ => ()
+    }
+}
+
+fn stats_lines(changelog: &Changelog, options: &RenderOptions) -> Vec<String> {
+    let stats = &changelog.stats;
+
+    let mut lines: Vec<String> = migrations_line(changelog).into_iter()
+        .chain(dependencies_line(changelog))
+        .collect();
+
+    lines.push(format!("{} commits, {} pull requests, {} issues", stats.commit_count, stats.pull_request_count, stats.issue_count));
+
+    // External changelogs have their authors removed.
+    if !stats.unique_authors.is_empty() || stats.commit_count == 0 {
+        lines.push(format!("{} authors: {}", stats.unique_authors.len(), stats.unique_authors.join(", ")));
+    }
+
+    if let (Some(oldest), Some(newest)) = (&stats.oldest_commit_date, &stats.newest_commit_date) {
+        lines.push(format!("Oldest commit: {}", options.format_date(oldest)));
+        lines.push(format!("Newest commit: {}", options.format_date(newest)));
+    }
+
+    if let Some(lead_time_seconds) = stats.lead_time_seconds {
+        lines.push(format!("Lead time: {}", format_duration(lead_time_seconds)));
+    }
+
+    if let Some(pull_request_commits) = &stats.pull_request_commits {
+        lines.push(format!("Pull request commits: {}", percentiles_label(pull_request_commits, |count| count.to_string())));
+    }
+
+    if let Some(pull_request_files) = &stats.pull_request_files {
+        lines.push(format!("Pull request files: {}", percentiles_label(pull_request_files, |count| count.to_string())));
+    }
+
+    if let Some(review_latency_seconds) = &stats.review_latency_seconds {
+        lines.push(format!("Review latency: {}", percentiles_label(review_latency_seconds, format_duration)));
+    }
+
+    if let Some(time_spent_seconds) = stats.time_spent_seconds {
+        let logged_issue_count = changelog.issues.iter()
+            .filter(|issue| issue.time_spent_seconds.is_some_and(|time_spent_seconds| time_spent_seconds > 0))
+            .count();
+
+        lines.push(format!("Time logged: {} on {logged_issue_count} issues", format_duration(time_spent_seconds)));
+    }
+
+    if let Some(pending_version) = &changelog.metadata.pending_version {
+        if let Some(pending_since) = &pending_version.pending_since {
+            lines.push(format!("Version {} pending since {}", pending_version.version, options.format_date(pending_since)));
+        }
+
+        if let (Some(current_version), Some(last_deployed_at)) = (&pending_version.current_version, &pending_version.last_deployed_at) {
+            lines.push(format!("Version {current_version} last deployed at {}", options.format_date(last_deployed_at)));
+        }
+    }
+
+    if !changelog.feature_flags.is_empty() || !changelog.feature_flag_files.is_empty() {
+        lines.push(format!(
+            "Feature flags: {} flags referenced, {} flag configuration files changed",
+            changelog.feature_flags.len(),
+            changelog.feature_flag_files.len()
+        ));
+    }
+
+    lines
+}
+
+/// Returns the warning shown at the top of the summary when the changelog contains database migrations.
+fn migrations_line(changelog: &Changelog) -> Option<String> {
+    match changelog.migrations.len() {
+        0 => None,
+        1 => Some(String::from("\u{26a0} Contains database migrations (1 file)")),
+        count => Some(format!("\u{26a0} Contains database migrations ({count} files)"))
+    }
+}
+
+/// Returns the warning shown at the top of the summary when pull requests of the changelog depend on changes
+/// that are neither included nor merged or done.
+fn dependencies_line(changelog: &Changelog) -> Option<String> {
+    match changelog.unresolved_dependencies().count() {
+        0 => None,
+        1 => Some(String::from("\u{26a0} 1 unresolved dependency")),
+        count => Some(format!("\u{26a0} {count} unresolved dependencies"))
+    }
+}
+
+/// Returns the text of a pull request entry: its release note if it has one, or else its title.
+fn pull_request_text(pull_request: &BitbucketPullRequest) -> &str {
+    pull_request.release_note.as_deref()
+        .unwrap_or(&pull_request.title)
+}
+
+/// Returns ` by <author>` for a pull request, or nothing if its author was removed from an external changelog.
+fn author_suffix(pull_request: &BitbucketPullRequest) -> String {
+    match pull_request.author.user.display_name.as_str() {
+        "" => String::new(),
+        display_name => format!(" by {display_name}")
+    }
+}
+
+fn cycle_time_suffix(issue: &JiraIssue) -> String {
+    issue.cycle_time.as_ref()
+        .map(|cycle_time| format!(", cycle time {}", format_duration(cycle_time.cycle_time_seconds)))
+        .unwrap_or_default()
+}
+
+/// Returns the percentiles of a statistic of the pull requests, such as `p50 2, p75 3, p90 5, p95 7, max 9 (12 pull requests)`,
+/// with its values formatted by `format_value`.
+fn percentiles_label(percentiles: &Percentiles, format_value: impl Fn(i64) -> String) -> String {
+    format!(
+        "p50 {}, p75 {}, p90 {}, p95 {}, max {} ({} pull requests)",
+        format_value(percentiles.p50),
+        format_value(percentiles.p75),
+        format_value(percentiles.p90),
+        format_value(percentiles.p95),
+        format_value(percentiles.max),
+        percentiles.count
+    )
+}
+
+fn notes_label(changelog: &Changelog) -> String {
+    format!("Deployment notes: {}", changelog.notes.join("; "))
+}
+
+fn graph_title(changelog: &Changelog) -> String {
+    let stats = &changelog.stats;
+
+    format!(
+        "{} commits, {} pull requests, {} issues",
+        stats.commit_count,
+        stats.pull_request_count,
+        stats.issue_count
+    )
+}
+
+fn format_duration(seconds: i64) -> String {
+    let duration = Duration::seconds(seconds);
+
+    match (duration.num_days(), duration.num_hours() % 24, duration.num_minutes() % 60) {
+        (0, 0, minutes) => format!("{minutes}m"),
+        (0, hours, minutes) => format!("{hours}h {minutes}m"),
+        (days, hours, _) => format!("{days}d {hours}h")
+    }
+}
+
+fn relative_date(timestamp: &DateTime<Utc>, now: &DateTime<Utc>) -> String {
+    let elapsed = now.signed_duration_since(*timestamp);
+    let (duration, in_future) = match elapsed < Duration::zero() {
+        true => (-elapsed, true),
+        false => (elapsed, false)
+    };
+
+    let (count, unit) = if duration < Duration::minutes(1) {
+        return String::from("just now");
+    } else if duration < Duration::hours(1) {
+        (duration.num_minutes(), "minute")
+    } else if duration < Duration::days(1) {
+        (duration.num_hours(), "hour")
+    } else if duration < Duration::days(30) {
+        (duration.num_days(), "day")
+    } else if duration < Duration::days(365) {
+        (duration.num_days() / 30, "month")
+    } else {
+        (duration.num_days() / 365, "year")
+    };
+
+    let plural = if count == 1 { "" } else { "s" };
+
+    match in_future {
+        true => format!("in {count} {unit}{plural}"),
+        false => format!("{count} {unit}{plural} ago")
+    }
+}
+
+fn graph_edges(changelog: &Changelog) -> Vec<(String, String)> {
+    let commit_edges = changelog.provenance.pull_request_commits.iter()
+        .filter_map(|(pull_request_entry_id, commit_ids)| pull_request_by_entry_id(changelog, pull_request_entry_id)
+            .map(|pull_request| (pull_request, commit_ids)))
+        .flat_map(|(pull_request, commit_ids)| {
+            commit_ids.iter()
+                .map(move |commit_id| (commit_node(commit_id), pull_request_node(pull_request)))
+        });
+
+    let issue_edges = changelog.provenance.issue_pull_requests.iter()
+        .flat_map(|(issue_key, pull_request_entry_ids)| {
+            pull_request_entry_ids.iter()
+                .filter_map(|pull_request_entry_id| pull_request_by_entry_id(changelog, pull_request_entry_id))
+                .map(move |pull_request| (pull_request_node(pull_request), issue_node(issue_key)))
+        });
+
+    commit_edges.chain(issue_edges)
+        .collect()
+}
+
+fn pull_request_by_entry_id<'a>(changelog: &'a Changelog, entry_id: &str) -> Option<&'a BitbucketPullRequest> {
+    changelog.pull_requests.iter()
+        .find(|pull_request| pull_request.entry_id.as_deref() == Some(entry_id))
+}
+
+fn commit_node(commit_id: &str) -> String {
+    format!("commit_{}", sanitize_id(commit_id))
+}
+
+/// Pull requests of different repositories can share a number, so their nodes are named after their entry IDs.
+fn pull_request_node(pull_request: &BitbucketPullRequest) -> String {
+    match &pull_request.entry_id {
+        Some(entry_id) => format!("pr_{}", sanitize_id(entry_id)),
+        None => format!("pr_{}", pull_request.id)
+    }
+}
+
+fn issue_node(issue_key: &str) -> String {
+    format!("issue_{}", sanitize_id(issue_key))
+}
+
+fn sanitize_id(id: &str) -> String {
+    id.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+fn first_line(text: &str) -> &str {
+    text.lines()
+        .next()
+        .unwrap_or_default()
+}
+
+fn escape_dot(label: &str) -> String {
+    label.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn escape_markdown(text: &str) -> String {
+    text.chars()
+        .fold(String::with_capacity(text.len()), |mut escaped, c| {
+            if matches!(c, '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' | '|') {
+                escaped.push('\\');
+            }
+
+            escaped.push(c);
+            escaped
+        })
+}
+
+fn escape_mermaid(label: &str) -> String {
+    label.replace('"', "#quot;")
+        .replace('\n', " ")
+}
@@ -0,0 +1,215 @@
+//! Linting of rendered release notes, to catch content that shouldn't be published as is.
+//!
+//! The release notes are checked line by line for links with an empty or malformed target, sections without
+//! any content, leftover `TODO`, `FIXME`, `TBD` and `XXX` markers, and control characters, which usually come
+//! from text pasted into issue summaries. Link targets are only checked to be well formed, they are not fetched,
+//! and backslash-escaped brackets, such as those of escaped issue summaries, aren't read as links.
+use std::fmt::Display;
+
+use regex::Regex;
+use reqwest::Url;
+
+/// The `LintKind` enum lists the problems the release notes linter reports.
+///
+/// - `BrokenLink`: A Markdown link with an empty or malformed target.
+/// - `EmptySection`: A heading with no content before the next heading of the same or a higher level.
+/// - `TodoMarker`: A `TODO`, `FIXME`, `TBD` or `XXX` marker.
+/// - `ControlCharacter`: A control character other than a tab.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintKind {
+    BrokenLink,
+    EmptySection,
+    TodoMarker,
+    ControlCharacter
+}
+
+/// The `LintWarning` struct is a problem found in release notes, on the given 1-based `line`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintWarning {
+    pub line: usize,
+    pub kind: LintKind,
+    pub message: String
+}
+
+impl Display for LintWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// Lints Markdown release notes, such as the output of `render_release_notes`, returning the problems found
+/// in order of appearance.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use deployment_changelog::render::{lint_release_notes, render_release_notes, RenderOptions};
+///
+/// // Suppose you generated a Changelog named 'changelog'
+/// let release_notes = render_release_notes(&changelog, &RenderOptions::default());
+///
+/// for warning in lint_release_notes(&release_notes) {
+///     eprintln!("Warning: {warning}");
+/// }
+/// ```
+pub fn lint_release_notes(markdown: &str) -> Vec<LintWarning> {
+    // Link texts can hold escaped brackets, such as the ones `escape_markdown` adds to issue summaries.
+    let link_regex = Regex::new(r"\[(?:[^\[\]\\]|\\.)*\]\(([^)]*)\)")
+        .expect("Error compiling link regex");
+
+    let todo_regex = Regex::new(r"\b(TODO|FIXME|TBD|XXX)\b")
+        .expect("Error compiling TODO marker regex");
+
+    let mut warnings: Vec<LintWarning> = Vec::new();
+
+    // The open heading's line, level and title, and whether any content followed it.
+    let mut open_section: Option<(usize, usize, &str, bool)> = None;
+
+    markdown.lines()
+        .enumerate()
+        .for_each(|(index, line)| {
+            let line_number = index + 1;
+
+            match heading(line) {
+                Some((level, title)) => {
+                    if let Some(section) = open_section.filter(|section| section.1 >= level) {
+                        warnings.extend(empty_section_warning(section));
+                        open_section = None;
+                    }
+
+                    // A heading nested under the open section counts as its content.
+                    if let Some(section) = open_section.as_mut() {
+                        section.3 = true;
+                    }
+
+                    open_section = Some((line_number, level, title, false));
+                },
+                None if !line.trim().is_empty() => {
+                    if let Some(section) = open_section.as_mut() {
+                        section.3 = true;
+                    }
+                },
+                None => ()
+            }
+
+            link_regex.captures_iter(line)
+                .filter(|captures| captures.get(0).is_some_and(|link| !is_escaped(line, link.start())))
+                .filter_map(|captures| captures.get(1))
+                .filter_map(|target| broken_link_reason(target.as_str()))
+                .for_each(|reason| warnings.push(LintWarning {
+                    line: line_number,
+                    kind: LintKind::BrokenLink,
+                    message: reason
+                }));
+
+            todo_regex.find_iter(line)
+                .for_each(|marker| warnings.push(LintWarning {
+                    line: line_number,
+                    kind: LintKind::TodoMarker,
+                    message: format!("{} marker", marker.as_str())
+                }));
+
+            line.chars()
+                .filter(|c| c.is_control() && *c != '\t')
+                .for_each(|c| warnings.push(LintWarning {
+                    line: line_number,
+                    kind: LintKind::ControlCharacter,
+                    message: format!("control character U+{:04X}", c as u32)
+                }));
+        });
+
+    if let Some(section) = open_section {
+        warnings.extend(empty_section_warning(section));
+    }
+
+    warnings.sort_by_key(|warning| warning.line);
+    warnings
+}
+
+/// Returns the level and title of a Markdown ATX heading.
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars()
+        .take_while(|c| *c == '#')
+        .count();
+
+    match (level, line[level..].strip_prefix(' ')) {
+        (1..=6, Some(title)) => Some((level, title.trim())),
+        _ => None
+    }
+}
+
+/// Returns whether the character at the given byte index of a line is escaped by an odd number of backslashes.
+fn is_escaped(line: &str, index: usize) -> bool {
+    line[..index].chars()
+        .rev()
+        .take_while(|c| *c == '\\')
+        .count() % 2 == 1
+}
+
+fn empty_section_warning((line, _, title, has_content): (usize, usize, &str, bool)) -> Option<LintWarning> {
+    (!has_content).then(|| LintWarning {
+        line,
+        kind: LintKind::EmptySection,
+        message: format!("empty section \"{title}\"")
+    })
+}
+
+fn broken_link_reason(target: &str) -> Option<String> {
+    let target = target.trim();
+
+    if target.is_empty() {
+        return Some(String::from("link with an empty target"));
+    }
+
+    if target.contains(char::is_whitespace) {
+        return Some(format!("link target \"{target}\" contains whitespace"));
+    }
+
+    match target.contains("://") {
+        true => Url::parse(target)
+            .err()
+            .map(|error| format!("malformed link target \"{target}\": {error}")),
+        false => None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(markdown: &str) -> Vec<(usize, LintKind)> {
+        lint_release_notes(markdown).into_iter()
+            .map(|warning| (warning.line, warning.kind))
+            .collect()
+    }
+
+    #[test]
+    fn nested_sections() {
+        assert_eq!(kinds("# Release\n## Features\n- New report\n## Fixes\n### Backend\n# Next"), vec![
+            (5, LintKind::EmptySection),
+            (6, LintKind::EmptySection)
+        ]);
+
+        assert!(kinds("# Release\n## Features\n### Reports\n- New report").is_empty());
+    }
+
+    #[test]
+    fn escaped_brackets() {
+        assert!(kinds(r"- \[x\](y z) by Jane").is_empty());
+        assert!(kinds(r"- \[x](y z)").is_empty());
+        assert!(kinds(r"- [PAY-1 \[backend\]](https://jira.example.com/browse/PAY-1)").is_empty());
+
+        assert_eq!(kinds(r"- [x](y z)"), vec![(1, LintKind::BrokenLink)]);
+        assert_eq!(kinds(r"- \\[x](y z)"), vec![(1, LintKind::BrokenLink)]);
+        assert_eq!(kinds(r"- \[see [docs]()\]"), vec![(1, LintKind::BrokenLink)]);
+    }
+
+    #[test]
+    fn control_characters() {
+        assert_eq!(lint_release_notes("# Release\n- Pasted\u{7} summary\twith a tab"), vec![LintWarning {
+            line: 2,
+            kind: LintKind::ControlCharacter,
+            message: String::from("control character U+0007")
+        }]);
+    }
+}
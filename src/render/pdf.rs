@@ -0,0 +1,186 @@
+//! PDF rendering of release notes, available with the `pdf` feature.
+//!
+//! The release notes are laid out as A4 pages of plain text using the standard Helvetica fonts, which every
+//! PDF reader provides, so no font has to be embedded. Characters outside of the Windows-1252 character set
+//! are replaced with `?`.
+use pdf_writer::{Content, Finish, Name, Pdf, Rect, Ref, Str};
+
+use crate::changelog::Changelog;
+
+use super::{render_release_notes, RenderOptions};
+
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+const MARGIN: f32 = 50.0;
+
+/// The average width of a Helvetica character relative to the font size, used to wrap lines.
+const AVERAGE_CHARACTER_WIDTH: f32 = 0.55;
+
+const REGULAR_FONT: Name = Name(b"F1");
+const BOLD_FONT: Name = Name(b"F2");
+
+/// Renders the release notes of a `Changelog` as a PDF document.
+///
+/// The document has the same contents as `render_release_notes`: the issues grouped by type, each listed with the
+/// pull requests that implemented them.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use deployment_changelog::render::{render_pdf, RenderOptions};
+///
+/// // Suppose you generated a Changelog named 'changelog'
+/// std::fs::write("release-notes.pdf", render_pdf(&changelog, &RenderOptions::default())).unwrap();
+/// ```
+pub fn render_pdf(changelog: &Changelog, options: &RenderOptions) -> Vec<u8> {
+    let release_notes = render_release_notes(changelog, options);
+
+    let mut pages = vec![Content::new()];
+    let mut y = PAGE_HEIGHT - MARGIN;
+
+    for line in release_notes.lines() {
+        let (font, size, text) = if let Some(title) = line.strip_prefix("# ") {
+            (BOLD_FONT, 18.0, title)
+        } else if let Some(heading) = line.strip_prefix("## ") {
+            (BOLD_FONT, 14.0, heading)
+        } else {
+            (REGULAR_FONT, 10.0, line)
+        };
+
+        let leading = size * 1.4;
+        let text = unescape_markdown(text);
+
+        if text.is_empty() {
+            y -= leading / 2.0;
+            continue;
+        }
+
+        for wrapped_line in wrap(&text, ((PAGE_WIDTH - 2.0 * MARGIN) / (size * AVERAGE_CHARACTER_WIDTH)) as usize) {
+            if y - leading < MARGIN {
+                pages.push(Content::new());
+                y = PAGE_HEIGHT - MARGIN;
+            }
+
+            y -= leading;
+
+            let content = pages.last_mut()
+                .expect("Error getting the current PDF page");
+
+            content.begin_text();
+            content.set_font(font, size);
+            content.next_line(MARGIN, y);
+            content.show(Str(&encode_win_ansi(&wrapped_line)));
+            content.end_text();
+        }
+    }
+
+    let catalog_id = Ref::new(1);
+    let page_tree_id = Ref::new(2);
+    let regular_font_id = Ref::new(3);
+    let bold_font_id = Ref::new(4);
+
+    // Each page is followed by its content stream.
+    let page_ids: Vec<Ref> = (0..pages.len())
+        .map(|index| Ref::new(5 + 2 * index as i32))
+        .collect();
+
+    let mut pdf = Pdf::new();
+    pdf.catalog(catalog_id).pages(page_tree_id);
+    pdf.pages(page_tree_id).kids(page_ids.iter().copied()).count(page_ids.len() as i32);
+
+    pdf.type1_font(regular_font_id)
+        .base_font(Name(b"Helvetica"))
+        .encoding_predefined(Name(b"WinAnsiEncoding"));
+
+    pdf.type1_font(bold_font_id)
+        .base_font(Name(b"Helvetica-Bold"))
+        .encoding_predefined(Name(b"WinAnsiEncoding"));
+
+    pages.into_iter()
+        .zip(page_ids.iter())
+        .for_each(|(content, page_id)| {
+            let content_id = Ref::new(page_id.get() + 1);
+
+            let mut page = pdf.page(*page_id);
+            page.parent(page_tree_id)
+                .media_box(Rect::new(0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT))
+                .contents(content_id);
+            page.resources()
+                .fonts()
+                .pair(REGULAR_FONT, regular_font_id)
+                .pair(BOLD_FONT, bold_font_id);
+            page.finish();
+
+            pdf.stream(content_id, &content.finish());
+        });
+
+    pdf.finish()
+}
+
+/// Turns a line of the Markdown release notes into plain text, dropping emphasis and escapes.
+fn unescape_markdown(line: &str) -> String {
+    let line = line.strip_prefix("- ")
+        .map(|item| format!("\u{2022} {item}"))
+        .unwrap_or_else(|| line.to_string());
+
+    let line = line.replace("**", "");
+    let mut text = String::with_capacity(line.len());
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('\\', Some(escaped)) => {
+                text.push(*escaped);
+                chars.next();
+            },
+            _ => text.push(c)
+        }
+    }
+
+    text
+}
+
+/// Wraps a line at word boundaries so that it fits in `width` characters, indenting the continuation lines.
+fn wrap(line: &str, width: usize) -> Vec<String> {
+    const INDENT: &str = "   ";
+
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = String::new();
+
+    line.split(' ')
+        .for_each(|word| {
+            let current_width = current.chars().count();
+
+            if current_width == 0 {
+                current.push_str(word);
+            } else if current_width + word.chars().count() + 1 > width {
+                lines.push(std::mem::replace(&mut current, format!("{INDENT}{word}")));
+            } else {
+                current.push(' ');
+                current.push_str(word);
+            }
+        });
+
+    lines.push(current);
+    lines
+}
+
+/// Encodes text in the Windows-1252 encoding the standard fonts use, replacing unsupported characters with `?`.
+fn encode_win_ansi(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| match c {
+            '\u{20}'..='\u{7e}' | '\u{a0}'..='\u{ff}' => c as u8,
+            '\u{2022}' => 0x95,
+            '\u{2013}' => 0x96,
+            '\u{2014}' => 0x97,
+            '\u{2018}' => 0x91,
+            '\u{2019}' => 0x92,
+            '\u{201c}' => 0x93,
+            '\u{201d}' => 0x94,
+            '\u{2026}' => 0x85,
+            '\u{20ac}' => 0x80,
+            '\u{26a0}' => b'!',
+            _ => b'?'
+        })
+        .collect()
+}